@@ -0,0 +1,133 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+use folsum::{hash_file, TestTree, TestTreeOptions};
+
+// Fixed seed so every run hashes the same files and results can be compared between commits.
+const BENCH_SEED: u64 = 3152;
+
+/// Generate the tree that every benchmark hashes.
+///
+/// Override its shape with `FOLSUM_BENCH_FILE_COUNT` and `FOLSUM_BENCH_MAX_FILE_SIZE` (in bytes).
+fn bench_tree() -> TestTree {
+    let default_options = TestTreeOptions::default();
+    let tree_options = TestTreeOptions {
+        seed: BENCH_SEED,
+        file_count: env_or("FOLSUM_BENCH_FILE_COUNT", 200),
+        max_file_size: env_or("FOLSUM_BENCH_MAX_FILE_SIZE", 256 * 1024),
+        ..default_options
+    };
+    TestTree::new(&tree_options).expect("Failed to create benchmark tree")
+}
+
+// Read a size from an environment variable, falling back to a default if it's missing or not a number.
+fn env_or(variable_name: &str, default_value: usize) -> usize {
+    env::var(variable_name)
+        .ok()
+        .and_then(|raw_value| raw_value.parse().ok())
+        .unwrap_or(default_value)
+}
+
+// Paths of every file in the tree, sorted so work is split between workers the same way each run.
+fn tree_files(test_tree: &TestTree) -> Vec<PathBuf> {
+    let mut file_paths: Vec<PathBuf> = test_tree
+        .file_hashes
+        .keys()
+        .map(|relative_path| test_tree.path_of(relative_path))
+        .collect();
+    file_paths.sort();
+    file_paths
+}
+
+// Total size of the tree, so Criterion can report throughput in bytes per second.
+fn tree_bytes(file_paths: &[PathBuf]) -> u64 {
+    file_paths
+        .iter()
+        .map(|file_path| fs::metadata(file_path).map_or(0, |metadata| metadata.len()))
+        .sum()
+}
+
+/// Compare hashing algorithms on the same files, one file at a time.
+fn bench_algorithms(criterion: &mut Criterion) {
+    let test_tree = bench_tree();
+    let file_paths: Vec<PathBuf> = tree_files(&test_tree);
+    // Read the files up front so these benchmarks measure hashing and not the disk.
+    let file_contents: Vec<Vec<u8>> = file_paths
+        .iter()
+        .map(|file_path| fs::read(file_path).unwrap())
+        .collect();
+    let mut benchmark_group = criterion.benchmark_group("hash_algorithms");
+    benchmark_group.throughput(Throughput::Bytes(tree_bytes(&file_paths)));
+    benchmark_group.bench_function("md5", |bencher| {
+        bencher.iter(|| {
+            for contents in &file_contents {
+                Md5::digest(contents);
+            }
+        })
+    });
+    benchmark_group.bench_function("sha256", |bencher| {
+        bencher.iter(|| {
+            for contents in &file_contents {
+                Sha256::digest(contents);
+            }
+        })
+    });
+    // FolSum doesn't hash with BLAKE3, but it's here to show what switching to a faster hash would gain.
+    benchmark_group.bench_function("blake3", |bencher| {
+        bencher.iter(|| {
+            for contents in &file_contents {
+                blake3::hash(contents);
+            }
+        })
+    });
+    // What FolSum actually does: both hashes in one read, straight from disk.
+    benchmark_group.bench_function("md5_and_sha256_from_disk", |bencher| {
+        bencher.iter(|| {
+            for file_path in &file_paths {
+                hash_file(file_path).unwrap();
+            }
+        })
+    });
+    benchmark_group.finish();
+}
+
+/// Compare hashing the whole tree with different numbers of worker threads.
+fn bench_workers(criterion: &mut Criterion) {
+    let test_tree = bench_tree();
+    let file_paths: Vec<PathBuf> = tree_files(&test_tree);
+    let mut benchmark_group = criterion.benchmark_group("hash_workers");
+    benchmark_group.throughput(Throughput::Bytes(tree_bytes(&file_paths)));
+    for worker_count in [1, 4, 8] {
+        benchmark_group.bench_with_input(
+            BenchmarkId::from_parameter(worker_count),
+            &worker_count,
+            |bencher, &worker_count| {
+                bencher.iter(|| hash_with_workers(&file_paths, worker_count));
+            },
+        );
+    }
+    benchmark_group.finish();
+}
+
+// Split the files into even chunks and hash each chunk in its own thread.
+fn hash_with_workers(file_paths: &[PathBuf], worker_count: usize) {
+    let chunk_size: usize = (file_paths.len() / worker_count).max(1);
+    thread::scope(|scope| {
+        for file_chunk in file_paths.chunks(chunk_size) {
+            scope.spawn(move || {
+                for file_path in file_chunk {
+                    hash_file(file_path).unwrap();
+                }
+            });
+        }
+    });
+}
+
+criterion_group!(benches, bench_algorithms, bench_workers);
+criterion_main!(benches);
@@ -0,0 +1,24 @@
+use std::process::Command;
+
+fn main() {
+    // Record which commit FolSum was built from so bug reports can be traced back to the code.
+    let git_commit: String = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|git_output| git_output.status.success())
+        .map(|git_output| {
+            String::from_utf8_lossy(&git_output.stdout)
+                .trim()
+                .to_string()
+        })
+        // Builds from a source tarball (like from crates.io) don't have a Git history.
+        .unwrap_or_else(|| String::from("unknown"));
+    println!("cargo:rustc-env=FOLSUM_GIT_COMMIT={}", git_commit);
+    // Record the day that FolSum was built.
+    let build_date: String = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    println!("cargo:rustc-env=FOLSUM_BUILD_DATE={}", build_date);
+    // Only rerun when the checked-out commit changes, instead of on every change to FolSum's files.
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/index");
+}
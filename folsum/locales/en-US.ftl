@@ -0,0 +1,325 @@
+## Top menu bar.
+
+menu-file = File
+menu-new-session = New session
+menu-quit = Quit
+menu-view = View
+menu-tools = Tools
+menu-quick-hash = Quick hash...
+menu-settings = Settings
+menu-help = Help
+menu-walkthrough = Show walkthrough
+menu-about = About FolSum
+menu-validate-tool = Validate tool
+menu-validate-tool-hover = Check MD5, SHA-1, and SHA-256 against published test vectors, like when validating FolSum for lab use
+
+## About window.
+
+about-title = About FolSum
+about-version = Version
+about-commit = Commit
+about-build-date = Built
+about-os = Operating system
+about-copy = Copy version info
+about-save-diagnostics = Save diagnostics bundle...
+about-save-diagnostics-hover = Zip up your settings, recent log, last summary, and latest crash report to attach to a bug report. Check what's in it before sharing it.
+about-open-action-log = Open action log
+about-open-action-log-hover = Show the append-only log of every summarization, export, and diagnostics bundle made on this computer, with when and by which OS user
+about-diagnostics-saved = Saved diagnostics bundle to { $bundle_path }
+
+## Self-test window.
+
+self-test-title = Tool self-test
+self-test-algorithm-passed = ✔ Gave the published answers
+self-test-algorithm-failed = ✖ Gave the wrong answers
+self-test-passed = Tool self-test passed. The result is recorded in the log and in every export's header.
+self-test-failed = Tool self-test failed. Hashes from this copy of FolSum can't be trusted, so don't rely on its checksums or exports.
+
+## Settings window.
+
+settings-language = Language
+settings-theme = Theme
+theme-default = Default
+theme-high-contrast = High contrast
+theme-colorblind-safe = Colorblind-safe
+settings-summarization = Summarization
+settings-profile = Profile
+settings-profile-custom = (custom)
+settings-profile-delete = Delete profile
+settings-profile-name = Profile name
+settings-profile-save = Save as profile
+settings-profile-save-hover = Save the summarization settings below under this name, replacing the profile with the same name if there is one.
+settings-same-file-system = Stay on one filesystem
+settings-same-file-system-hover = Don't descend into other volumes that are mounted inside of the chosen directory
+settings-limit-depth = Limit depth to
+settings-depth-levels = levels
+settings-ignore-metadata = Ignore OS metadata from:
+settings-ignore-metadata-hover = Leave out files that operating systems scatter around, like .DS_Store, Thumbs.db, and .Trash folders, when summarizing, exporting, and verifying checksum files
+settings-ignore-macos = MacOS
+settings-ignore-windows = Windows
+settings-ignore-linux = Linux
+settings-verify-checksums = Verify checksum files
+settings-verify-checksums-hover = Check the files that are listed in checksum files (like checksums.md5 or SHA256SUMS) found while summarizing
+settings-extra-streams = Look for hidden data streams
+settings-extra-streams-hover = List alternate data streams (on Windows) and extended attributes (on MacOS and Linux) that are attached to files, with their sizes and hashes
+settings-retry-locked = Retry locked files at the end
+settings-retry-locked-hover = Check listed files that another program had locked one more time, after every other file's been checked
+settings-size-only-extensions = Only size up extensions:
+settings-size-only-extensions-hover = Comma-separated extensions (like mov, braw) of listed files that only get their size recorded instead of being hashed, because hashing them takes too long
+settings-size-only-larger = Only size up files larger than
+settings-size-only-mib = MiB
+settings-severity = Finding severity
+settings-severity-hint = Critical findings fail the audit and warnings leave it unconfirmed. Changes apply from the next summarization.
+settings-severity-info-extensions = Only note findings for extensions:
+settings-severity-info-extensions-hover = Comma-separated extensions, like "tmp, log", of scratch files that are expected to change. Their findings never count against the audit.
+severity-info = Info
+severity-warning = Warning
+severity-critical = Critical
+settings-read-buffer = Read buffer
+settings-read-buffer-hover = Files are read in chunks while they're hashed. The chunk size is tuned between these limits to whatever reads fastest, which is usually bigger on network shares.
+settings-read-buffer-min = from
+settings-read-buffer-max = to
+settings-read-buffer-kib = KiB
+settings-readers-per-device = Files read at once from each disk
+settings-readers-per-device-hover = Summarizations, exports, and quick hashes take turns reading from the same disk. Hard drives are fastest with one reader, while SSDs and network shares can handle more.
+settings-shutdown-timeout = Wait for work to stop when closing
+settings-shutdown-timeout-hover = When FolSum's closed during a summarization or export, it's asked to stop and given this long to. Exports that haven't finished by then are thrown away instead of being left half-written.
+settings-shutdown-timeout-seconds = seconds
+settings-system-log = Record events in the system log
+settings-system-log-hover = Write when summarizations start and finish, and every checksum discrepancy, to syslog (on MacOS and Linux) or the Windows Event Log so they reach central logging
+settings-export-filenames = Export Filenames
+settings-filename-template = Filename template:
+settings-placeholders = Placeholders: { $placeholders }
+settings-date-format = Date format:
+settings-date-format-hover = How { "{date}" } is written, using strftime specifiers like %Y (year), %m (month), and %d (day)
+settings-date-iso = ISO 8601
+settings-date-legacy = YY_MM_DD
+settings-dates-in-utc = Date exports in UTC
+settings-dates-in-utc-hover = Use UTC instead of this computer's timezone for filename dates and export timestamps
+settings-preview = Preview:
+settings-reset-template = Reset to default
+settings-results-api = Results API
+settings-api-enabled = Serve results to other programs
+settings-api-enabled-hover = Let programs on this computer (like case management systems) read the summary and checksum findings as JSON from GET /summary and GET /checksums
+settings-api-port = Port:
+settings-api-token = Token:
+settings-api-token-hover = Secret that programs must send as "Authorization: Bearer <token>". The API won't start without one
+settings-api-listening = Listening on { $address }
+
+## Left panel.
+
+choose-directory = Choose a Directory to Summarize
+open-directory = Open directory...
+chosen-directory = Chosen directory:
+no-directory-selected = No directory selected
+summarize = Summarize
+summarization-estimating = Sizing up the directory...
+summarization-in-progress = Summarizing...
+summarization-failed = Summarization failed: { $reason }
+large-directory-title = Summarize this directory?
+large-directory-description = { $directory } contains { $counted ->
+        [all] about
+       *[some] more than
+    } { $file_count } files ({ $total_size }). Summarizing it could take a long time and slow down this computer. Continue?
+system-directory-warning = { $directory } is the root of a drive or holds the operating system, which usually isn't what needs to be summarized.
+summarized-files = Summarized { $file_count ->
+        [one] { $shown_file_count } file
+       *[other] { $shown_file_count } files
+    } in { $duration }
+pinned-options = Some options were pinned by this folder's { $project_file }
+summarized-bytes = Total size: { $total_size } ({ $allocated_size } on disk)
+sparse-files = { $sparse_count ->
+        [one] { $shown_sparse_count } sparse file takes up less room on disk than its length
+       *[other] { $shown_sparse_count } sparse files take up less room on disk than their length
+    }
+skipped-hardlinks = Skipped { $hardlink_count } extra hardlinks to files that were already counted
+performance = Performance
+performance-walk = Walking the directory
+performance-checksum-verification = Verifying checksum files
+performance-export-write = Writing the export
+performance-not-run = Not run yet
+checksum-progress = Checked { $checked } of { $listed } listed files
+checksum-summary = Checksum files: { $verified } verified, { $mismatched } mismatched, { $missing } missing, { $unreadable } unreadable, { $locked } locked, { $outside } outside their folder, { $size_only } size only
+findings-show = Show findings
+findings-title = Findings
+findings-hint = Select findings with click, Ctrl-click, and Shift-click. Double-click a file to find its type in the results table.
+findings-none = Every listed file checked out.
+findings-group = { $category } ({ $shown_finding_count })
+findings-metadata-drift = Modified after being listed
+findings-ignored = Ignored
+findings-accepted = Accepted as the new baseline
+findings-ignore = Ignore
+findings-ignore-hover = Put the selected files on the ignore list, so their findings are kept out of the way from now on
+findings-accept = Accept
+findings-accept-hover = Accept the selected files as they are now. If they change again, they'll be flagged again
+findings-undo = Undo decision
+findings-reverify = Verify again
+findings-reverify-hover = Check the selected files against their checksum files again, like after closing the program that locked them
+findings-reverify-in-progress = Verifying again...
+findings-reverify-finished = Verified again
+findings-reverify-failed = Couldn't verify again: { $reason }
+findings-export = Export selection
+export-findings-title = Export findings to CSV file
+audit-content-passed = Content integrity: passed
+audit-content-failed = Content integrity: failed
+audit-content-unconfirmed = Content integrity: couldn't be confirmed, because some files couldn't be read
+audit-report = Checksum files audited: { $checksum_files }, finished { $finished_at }, took { $duration }
+audit-metadata-unchanged = Metadata drift: none
+audit-metadata-drift = Metadata drift: { $drift_count ->
+        [one] { $shown_drift_count } file was
+       *[other] { $shown_drift_count } files were
+    } modified after being listed, but their content held up
+checksum-mismatch = Mismatch
+checksum-missing = Missing
+checksum-unreadable = Unreadable
+checksum-locked = Locked or in use
+checksum-outside-directory = Outside of its folder
+checksum-file = Checksum file
+checksum-listed-hash = Listed hash
+checksum-actual-hash = Actual hash
+checksum-file-size = Size
+checksum-modified = Modified
+checksum-listed-at = Checksum file written
+checksum-error = Error
+extra-streams = Hidden data streams ({ $stream_count })
+read-only-mode = Read-only mode
+read-only-mode-hover = Never write anything inside of the chosen directory
+choose-reports-directory = Choose reports directory...
+choose-reports-directory-title = Choose a directory for exports
+reports-directory = Reports directory:
+no-reports-directory-selected = No reports directory selected
+case-id = Case ID:
+operator = Operator:
+os-user = OS user:
+notes = Notes:
+export-csv = Export to CSV
+export-csv-title = Export extension counts to CSV file
+export-previewing = Counting the files to export...
+export-in-progress = Exporting...
+export-finished = Export finished
+export-failed = Export failed: { $reason }
+export-reveal-hover = Show the export in the file manager
+export-open-folder = Open containing folder
+export-preview-title = Write this export?
+export-preview-description = Destination: { $export_path }
+    Rows: { $counted ->
+        [all] { $row_count }
+       *[some] more than { $row_count }
+    }
+    Total size: { $total_size }
+    Filters: { $filters }
+export-preview-first-rows = First { $shown_count } rows:
+export-filter-none = none
+export-filter-selected = only the { $selected_count } selected extensions
+export-filter-depth = at most { $max_depth } levels deep
+export-filter-same-file-system = staying on one filesystem
+export-filter-ignored-metadata = ignoring { $platforms } metadata
+export-selected = Export selected rows
+export-selected-hover = Select rows of the results table with click, Ctrl-click, and Shift-click
+export-spdx = Export SPDX file listing
+export-spdx-hover = Hash every file in the chosen directory and list them, with their sizes, in an SPDX 2.3 document for SBOM tools
+export-spdx-title = Export file listing to SPDX document
+export-stats = Export statistics
+export-stats-hover = Save extension counts and sizes, how many files are at each depth, and the largest files to a CSV file
+export-stats-title = Export statistics to CSV file
+export-exists-title = Export file already exists
+export-exists-description = { $chosen_path } already exists.
+
+    Overwrite it? Choose "No" to save the export as { $versioned_path } instead.
+written-with-love = written with love by{" "}
+
+## Quick hash window.
+
+quick-hash-title = Quick Hash
+quick-hash-instructions = Drop a file onto FolSum or choose one to see its MD5 and SHA-256 hashes.
+quick-hash-choose-file = Choose file...
+quick-hash-file = File:
+quick-hash-hashing = Hashing...
+quick-hash-copy = Copy
+copy-full-hash = Copy full hash
+quick-hash-compare = Compare with:
+hash-match = Match
+hash-mismatch = Mismatch
+hash-unrecognized = That isn't an MD5 or SHA-256 hash
+
+## Walkthrough.
+
+onboarding-title = Getting Started ({ $step } of { $step_count })
+onboarding-choose-directory = Start by clicking "Open directory..." and choosing the folder that you'd like to summarize.
+onboarding-summarize = Click "Summarize" to count the files in the chosen folder by extension.
+onboarding-review-results = Results show up here, with the most common extensions first. Group them by top-level folder, pick columns, or select rows from the toolbar.
+onboarding-export = Fill in the case ID, operator, and notes, then click "Export to CSV" to save the results.
+onboarding-back = Back
+onboarding-next = Next
+onboarding-done = Done
+onboarding-skip = Skip
+
+## Results.
+
+results-heading = Summarization by File Extension
+group-by-folder = Group by top-level folder
+columns-menu = Columns
+copy-tsv = Copy as TSV
+copy-tsv-hover = Copy the shown rows and columns for pasting into a spreadsheet
+folder-total = { $folder_name } ({ $folder_total ->
+        [one] { $shown_folder_total } file
+       *[other] { $shown_folder_total } files
+    })
+column-file-extension = File Extension
+column-occurrences = Occurrences
+column-bytes = Bytes
+
+## Crash report window.
+
+crash-report-title = FolSum Closed Unexpectedly
+crash-report-description = FolSum crashed last time and saved a crash report. Attaching it to a bug report helps get the problem fixed.
+crash-report-open = Open report
+crash-report-copy = Copy report
+crash-report-dismiss = Dismiss
+
+## Window shown while FolSum waits for background work to stop.
+
+closing-title = Closing FolSum
+closing-description = Waiting for { $running } background tasks to stop…
+
+## Errors from summarizing, hashing, and exporting.
+
+error-stopped = Stopped before it was done
+error-file-in-use = The file is locked or in use by another program
+error-worker-panicked = A background task crashed
+error-already-summarizing = A summarization is already running
+error-hash-folder = Only files can be hashed, not folders
+error-hash-open = Couldn't open the file to hash it
+error-hash-read = Couldn't read the file to hash it
+error-outside-folder = The checksum file lists a path outside of its folder, so it wasn't read
+error-not-utf8 = The file isn't valid UTF-8
+error-not-utf16 = The file isn't valid UTF-16
+error-reverify-unselected = Select findings before verifying them again
+error-export-unselected = Select findings before exporting them
+error-export-no-path = No path for export file was specified
+error-spdx-no-directory = Choose a directory before exporting an SPDX document
+error-csv-export-write = Failed to create CSV export file
+error-findings-export-write = Failed to write findings export file
+error-spdx-export-write = Failed to write SPDX export file
+error-stats-export-write = Failed to write statistics export file
+error-read-only-export = Read-only mode forbids writing exports inside of the chosen directory
+error-template-placeholder = Export filename template contains an unknown placeholder
+error-template-case-id = Export filename template needs a case ID, but none was given
+error-date-format-empty = Date format can't be empty
+error-date-format-specifier = Date format contains an unknown specifier
+error-filename-empty = Export filename can't be empty
+error-filename-characters = Export filename contains characters that aren't allowed in filenames
+error-filename-ending = Export filename can't end with a period or a space on Windows
+error-filename-reserved = Export filename is a device name that's reserved by Windows
+error-no-folder = The file isn't inside of a folder
+error-open-path = Couldn't open the path with the default app
+error-reveal-path = Couldn't show the file in the file manager
+error-action-log-empty = Nothing's been recorded in the action log yet
+error-diagnostics-write = Couldn't write the diagnostics bundle
+error-profile-unnamed = Name the profile before saving it
+error-profile-missing = There's no profile with that name
+error-project-file-read = Couldn't read .folsum.toml
+error-project-file-parse = Couldn't understand .folsum.toml
+error-api-token = Set an API token before turning on the results API
+error-api-port = Couldn't start the results API. Is another program using its port?
+error-api-start = Couldn't start the results API
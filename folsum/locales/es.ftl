@@ -0,0 +1,325 @@
+## Barra de menú superior.
+
+menu-file = Archivo
+menu-new-session = Nueva sesión
+menu-quit = Salir
+menu-view = Ver
+menu-tools = Herramientas
+menu-quick-hash = Hash rápido...
+menu-settings = Ajustes
+menu-help = Ayuda
+menu-walkthrough = Mostrar recorrido guiado
+menu-about = Acerca de FolSum
+menu-validate-tool = Validar herramienta
+menu-validate-tool-hover = Comprobar MD5, SHA-1 y SHA-256 con vectores de prueba publicados, por ejemplo al validar FolSum para su uso en el laboratorio
+
+## Ventana Acerca de.
+
+about-title = Acerca de FolSum
+about-version = Versión
+about-commit = Commit
+about-build-date = Compilado
+about-os = Sistema operativo
+about-copy = Copiar información de versión
+about-save-diagnostics = Guardar paquete de diagnóstico...
+about-save-diagnostics-hover = Comprime tus ajustes, el registro reciente, el último resumen y el último informe de fallo para adjuntarlos a un informe de error. Revisa su contenido antes de compartirlo.
+about-open-action-log = Abrir registro de acciones
+about-open-action-log-hover = Muestra el registro, al que solo se añaden líneas, de cada resumen, exportación y paquete de diagnóstico hecho en este equipo, con cuándo y qué usuario del sistema lo hizo
+about-diagnostics-saved = Paquete de diagnóstico guardado en { $bundle_path }
+
+## Ventana de autoprueba.
+
+self-test-title = Autoprueba de la herramienta
+self-test-algorithm-passed = ✔ Dio las respuestas publicadas
+self-test-algorithm-failed = ✖ Dio respuestas incorrectas
+self-test-passed = La autoprueba de la herramienta se superó. El resultado se guarda en el registro y en el encabezado de cada exportación.
+self-test-failed = La autoprueba de la herramienta falló. No se puede confiar en los hashes de esta copia de FolSum, así que no te fíes de sus sumas de verificación ni de sus exportaciones.
+
+## Ventana de ajustes.
+
+settings-language = Idioma
+settings-theme = Tema
+theme-default = Predeterminado
+theme-high-contrast = Alto contraste
+theme-colorblind-safe = Apto para daltonismo
+settings-summarization = Resumen
+settings-profile = Perfil
+settings-profile-custom = (personalizado)
+settings-profile-delete = Eliminar perfil
+settings-profile-name = Nombre del perfil
+settings-profile-save = Guardar como perfil
+settings-profile-save-hover = Guarda la configuración de resumen de abajo con este nombre y reemplaza el perfil con el mismo nombre si existe.
+settings-same-file-system = Permanecer en un solo sistema de archivos
+settings-same-file-system-hover = No entrar en otros volúmenes montados dentro del directorio elegido
+settings-limit-depth = Limitar la profundidad a
+settings-depth-levels = niveles
+settings-ignore-metadata = Ignorar metadatos del sistema de:
+settings-ignore-metadata-hover = Omitir los archivos que dejan los sistemas operativos, como .DS_Store, Thumbs.db y las carpetas .Trash, al resumir, exportar y verificar archivos de sumas de verificación
+settings-ignore-macos = MacOS
+settings-ignore-windows = Windows
+settings-ignore-linux = Linux
+settings-verify-checksums = Verificar archivos de sumas de comprobación
+settings-verify-checksums-hover = Comprobar los archivos que aparecen en los archivos de sumas de comprobación (como checksums.md5 o SHA256SUMS) encontrados al resumir
+settings-extra-streams = Buscar flujos de datos ocultos
+settings-extra-streams-hover = Listar los flujos de datos alternativos (en Windows) y los atributos extendidos (en MacOS y Linux) de los archivos, con sus tamaños y hashes
+settings-retry-locked = Reintentar los archivos bloqueados al final
+settings-retry-locked-hover = Volver a comprobar los archivos listados que otro programa tenía bloqueados, después de comprobar todos los demás
+settings-size-only-extensions = Solo registrar el tamaño de las extensiones:
+settings-size-only-extensions-hover = Extensiones separadas por comas (como mov, braw) de los archivos listados a los que solo se les registra el tamaño en lugar de calcular su hash, porque tarda demasiado
+settings-size-only-larger = Solo registrar el tamaño de archivos mayores de
+settings-size-only-mib = MiB
+settings-severity = Gravedad de los hallazgos
+settings-severity-hint = Los hallazgos críticos hacen fallar la auditoría y las advertencias la dejan sin confirmar. Los cambios se aplican desde el próximo resumen.
+settings-severity-info-extensions = Solo anotar hallazgos de las extensiones:
+settings-severity-info-extensions-hover = Extensiones separadas por comas, como "tmp, log", de archivos temporales que se espera que cambien. Sus hallazgos nunca cuentan en contra de la auditoría.
+severity-info = Información
+severity-warning = Advertencia
+severity-critical = Crítico
+settings-read-buffer = Búfer de lectura
+settings-read-buffer-hover = Los archivos se leen por partes mientras se calculan sus hashes. El tamaño de cada parte se ajusta entre estos límites a lo que lea más rápido, que suele ser mayor en recursos compartidos de red.
+settings-read-buffer-min = de
+settings-read-buffer-max = a
+settings-read-buffer-kib = KiB
+settings-readers-per-device = Archivos leídos a la vez de cada disco
+settings-readers-per-device-hover = Los resúmenes, las exportaciones y los hashes rápidos se turnan para leer del mismo disco. Los discos duros son más rápidos con un solo lector, mientras que los SSD y los recursos compartidos de red admiten más.
+settings-shutdown-timeout = Esperar a que el trabajo se detenga al cerrar
+settings-shutdown-timeout-hover = Si FolSum se cierra durante un resumen o una exportación, se le pide que se detenga y se le da este tiempo para hacerlo. Las exportaciones que no hayan terminado para entonces se descartan en lugar de quedar escritas a medias.
+settings-shutdown-timeout-seconds = segundos
+settings-system-log = Registrar eventos en el registro del sistema
+settings-system-log-hover = Escribe cuándo empiezan y terminan los resúmenes, y cada discrepancia de sumas de verificación, en syslog (en MacOS y Linux) o en el Visor de eventos de Windows para que lleguen al registro central
+settings-export-filenames = Nombres de las exportaciones
+settings-filename-template = Plantilla de nombre de archivo:
+settings-placeholders = Marcadores: { $placeholders }
+settings-date-format = Formato de fecha:
+settings-date-format-hover = Cómo se escribe { "{date}" }, con especificadores de strftime como %Y (año), %m (mes) y %d (día)
+settings-date-iso = ISO 8601
+settings-date-legacy = AA_MM_DD
+settings-dates-in-utc = Fechar las exportaciones en UTC
+settings-dates-in-utc-hover = Usa UTC en lugar de la zona horaria de este equipo para las fechas de los nombres de archivo y las marcas de tiempo de las exportaciones
+settings-preview = Vista previa:
+settings-reset-template = Restablecer valor predeterminado
+settings-results-api = API de resultados
+settings-api-enabled = Compartir resultados con otros programas
+settings-api-enabled-hover = Permite que los programas de este equipo (como los sistemas de gestión de casos) lean el resumen y los resultados de las sumas de verificación en JSON desde GET /summary y GET /checksums
+settings-api-port = Puerto:
+settings-api-token = Token:
+settings-api-token-hover = Secreto que los programas deben enviar como "Authorization: Bearer <token>". La API no se inicia sin uno
+settings-api-listening = Escuchando en { $address }
+
+## Panel izquierdo.
+
+choose-directory = Elige un directorio para resumir
+open-directory = Abrir directorio...
+chosen-directory = Directorio elegido:
+no-directory-selected = Ningún directorio seleccionado
+summarize = Resumir
+summarization-estimating = Calculando el tamaño del directorio...
+summarization-in-progress = Resumiendo...
+summarization-failed = El resumen falló: { $reason }
+large-directory-title = ¿Resumir este directorio?
+large-directory-description = { $directory } contiene { $counted ->
+        [all] unos
+       *[some] más de
+    } { $file_count } archivos ({ $total_size }). Resumirlo podría tardar mucho y ralentizar este equipo. ¿Continuar?
+system-directory-warning = { $directory } es la raíz de una unidad o contiene el sistema operativo, y normalmente no es lo que hay que resumir.
+summarized-files = { $file_count ->
+        [one] Se resumió { $shown_file_count } archivo
+       *[other] Se resumieron { $shown_file_count } archivos
+    } en { $duration }
+pinned-options = Algunas opciones fueron fijadas por el { $project_file } de esta carpeta
+summarized-bytes = Tamaño total: { $total_size } ({ $allocated_size } en disco)
+sparse-files = { $sparse_count ->
+        [one] { $shown_sparse_count } archivo disperso ocupa menos espacio en disco que su longitud
+       *[other] { $shown_sparse_count } archivos dispersos ocupan menos espacio en disco que su longitud
+    }
+skipped-hardlinks = Se omitieron { $hardlink_count } enlaces duros adicionales a archivos que ya se habían contado
+performance = Rendimiento
+performance-walk = Recorrer el directorio
+performance-checksum-verification = Verificar archivos de sumas de verificación
+performance-export-write = Escribir la exportación
+performance-not-run = Aún no se ha ejecutado
+checksum-progress = Comprobados { $checked } de { $listed } archivos indicados
+checksum-summary = Sumas de comprobación: { $verified } verificados, { $mismatched } no coinciden, { $missing } faltan, { $unreadable } ilegibles, { $locked } bloqueados, { $outside } fuera de su carpeta, { $size_only } solo tamaño
+findings-show = Mostrar hallazgos
+findings-title = Hallazgos
+findings-hint = Selecciona hallazgos con clic, Ctrl-clic y Mayús-clic. Haz doble clic en un archivo para encontrar su tipo en la tabla de resultados.
+findings-none = Todos los archivos listados se verificaron correctamente.
+findings-group = { $category } ({ $shown_finding_count })
+findings-metadata-drift = Modificado después de ser listado
+findings-ignored = Ignorados
+findings-accepted = Aceptados como nueva referencia
+findings-ignore = Ignorar
+findings-ignore-hover = Añadir los archivos seleccionados a la lista de ignorados, para que sus hallazgos no estorben a partir de ahora
+findings-accept = Aceptar
+findings-accept-hover = Aceptar los archivos seleccionados tal como están ahora. Si vuelven a cambiar, se marcarán de nuevo
+findings-undo = Deshacer decisión
+findings-reverify = Verificar de nuevo
+findings-reverify-hover = Comprobar de nuevo los archivos seleccionados con sus archivos de sumas de verificación, por ejemplo después de cerrar el programa que los bloqueaba
+findings-reverify-in-progress = Verificando de nuevo...
+findings-reverify-finished = Verificados de nuevo
+findings-reverify-failed = No se pudo verificar de nuevo: { $reason }
+findings-export = Exportar selección
+export-findings-title = Exportar hallazgos a un archivo CSV
+audit-content-passed = Integridad del contenido: correcta
+audit-content-failed = Integridad del contenido: fallida
+audit-content-unconfirmed = Integridad del contenido: no se pudo confirmar porque algunos archivos no se pudieron leer
+audit-report = Archivos de sumas auditados: { $checksum_files }, terminado { $finished_at }, duró { $duration }
+audit-metadata-unchanged = Cambios en metadatos: ninguno
+audit-metadata-drift = Cambios en metadatos: { $drift_count ->
+        [one] { $shown_drift_count } archivo se modificó
+       *[other] { $shown_drift_count } archivos se modificaron
+    } después de ser listados, pero su contenido se mantuvo
+checksum-mismatch = No coincide
+checksum-missing = Falta
+checksum-unreadable = Ilegible
+checksum-locked = Bloqueado o en uso
+checksum-outside-directory = Fuera de su carpeta
+checksum-file = Archivo de sumas de verificación
+checksum-listed-hash = Hash indicado
+checksum-actual-hash = Hash real
+checksum-file-size = Tamaño
+checksum-modified = Modificado
+checksum-listed-at = Archivo de sumas escrito
+checksum-error = Error
+extra-streams = Flujos de datos ocultos ({ $stream_count })
+read-only-mode = Modo de solo lectura
+read-only-mode-hover = Nunca escribir nada dentro del directorio elegido
+choose-reports-directory = Elegir directorio de informes...
+choose-reports-directory-title = Elige un directorio para las exportaciones
+reports-directory = Directorio de informes:
+no-reports-directory-selected = Ningún directorio de informes seleccionado
+case-id = ID del caso:
+operator = Operador:
+os-user = Usuario del sistema:
+notes = Notas:
+export-csv = Exportar a CSV
+export-csv-title = Exportar el recuento de extensiones a un archivo CSV
+export-previewing = Contando los archivos que se exportarán...
+export-in-progress = Exportando...
+export-finished = Exportación terminada
+export-failed = La exportación falló: { $reason }
+export-reveal-hover = Mostrar la exportación en el administrador de archivos
+export-open-folder = Abrir carpeta contenedora
+export-preview-title = ¿Escribir esta exportación?
+export-preview-description = Destino: { $export_path }
+    Filas: { $counted ->
+        [all] { $row_count }
+       *[some] más de { $row_count }
+    }
+    Tamaño total: { $total_size }
+    Filtros: { $filters }
+export-preview-first-rows = Primeras { $shown_count } filas:
+export-filter-none = ninguno
+export-filter-selected = solo las { $selected_count } extensiones seleccionadas
+export-filter-depth = como máximo { $max_depth } niveles de profundidad
+export-filter-same-file-system = sin salir de un sistema de archivos
+export-filter-ignored-metadata = ignorando los metadatos de { $platforms }
+export-selected = Exportar filas seleccionadas
+export-selected-hover = Selecciona filas de la tabla de resultados con clic, Ctrl+clic y Mayús+clic
+export-spdx = Exportar lista de archivos SPDX
+export-spdx-hover = Calcula el hash de cada archivo del directorio elegido y los enumera, con sus tamaños, en un documento SPDX 2.3 para herramientas SBOM
+export-spdx-title = Exportar lista de archivos a un documento SPDX
+export-stats = Exportar estadísticas
+export-stats-hover = Guardar en un archivo CSV los recuentos y tamaños por extensión, cuántos archivos hay en cada nivel de profundidad y los archivos más grandes
+export-stats-title = Exportar las estadísticas a un archivo CSV
+export-exists-title = El archivo de exportación ya existe
+export-exists-description = { $chosen_path } ya existe.
+
+    ¿Sobrescribirlo? Elige "No" para guardar la exportación como { $versioned_path }.
+written-with-love = escrito con cariño por{" "}
+
+## Ventana de hash rápido.
+
+quick-hash-title = Hash rápido
+quick-hash-instructions = Suelte un archivo sobre FolSum o elija uno para ver sus hashes MD5 y SHA-256.
+quick-hash-choose-file = Elegir archivo...
+quick-hash-file = Archivo:
+quick-hash-hashing = Calculando hash...
+quick-hash-copy = Copiar
+copy-full-hash = Copiar hash completo
+quick-hash-compare = Comparar con:
+hash-match = Coincide
+hash-mismatch = No coincide
+hash-unrecognized = Eso no es un hash MD5 ni SHA-256
+
+## Recorrido guiado.
+
+onboarding-title = Primeros pasos ({ $step } de { $step_count })
+onboarding-choose-directory = Empiece haciendo clic en "Abrir directorio..." y eligiendo la carpeta que quiere resumir.
+onboarding-summarize = Haga clic en "Resumir" para contar los archivos de la carpeta elegida por extensión.
+onboarding-review-results = Los resultados aparecen aquí, con las extensiones más comunes primero. Agrúpelos por carpeta de primer nivel, elija columnas o seleccione filas desde la barra de herramientas.
+onboarding-export = Rellene el ID del caso, el operador y las notas, y luego haga clic en "Exportar a CSV" para guardar los resultados.
+onboarding-back = Atrás
+onboarding-next = Siguiente
+onboarding-done = Listo
+onboarding-skip = Omitir
+
+## Resultados.
+
+results-heading = Resumen por extensión de archivo
+group-by-folder = Agrupar por carpeta de primer nivel
+columns-menu = Columnas
+copy-tsv = Copiar como TSV
+copy-tsv-hover = Copiar las filas y columnas mostradas para pegarlas en una hoja de cálculo
+folder-total = { $folder_name } ({ $folder_total ->
+        [one] { $shown_folder_total } archivo
+       *[other] { $shown_folder_total } archivos
+    })
+column-file-extension = Extensión de archivo
+column-occurrences = Apariciones
+column-bytes = Bytes
+
+## Ventana de informe de fallo.
+
+crash-report-title = FolSum se cerró inesperadamente
+crash-report-description = FolSum falló la última vez y guardó un informe de fallo. Adjuntarlo a un informe de error ayuda a resolver el problema.
+crash-report-open = Abrir informe
+crash-report-copy = Copiar informe
+crash-report-dismiss = Descartar
+
+## Ventana que se muestra mientras FolSum espera a que se detenga el trabajo en segundo plano.
+
+closing-title = Cerrando FolSum
+closing-description = Esperando a que se detengan { $running } tareas en segundo plano…
+
+## Errores al resumir, calcular hashes y exportar.
+
+error-stopped = Se detuvo antes de terminar
+error-file-in-use = El archivo está bloqueado o lo está usando otro programa
+error-worker-panicked = Una tarea en segundo plano falló de forma inesperada
+error-already-summarizing = Ya se está realizando un resumen
+error-hash-folder = Solo se pueden calcular hashes de archivos, no de carpetas
+error-hash-open = No se pudo abrir el archivo para calcular su hash
+error-hash-read = No se pudo leer el archivo para calcular su hash
+error-outside-folder = El archivo de sumas de verificación incluye una ruta fuera de su carpeta, así que no se leyó
+error-not-utf8 = El archivo no es UTF-8 válido
+error-not-utf16 = El archivo no es UTF-16 válido
+error-reverify-unselected = Selecciona hallazgos antes de volver a verificarlos
+error-export-unselected = Selecciona hallazgos antes de exportarlos
+error-export-no-path = No se indicó una ruta para el archivo de exportación
+error-spdx-no-directory = Elige un directorio antes de exportar un documento SPDX
+error-csv-export-write = No se pudo crear el archivo de exportación CSV
+error-findings-export-write = No se pudo escribir el archivo de exportación de hallazgos
+error-spdx-export-write = No se pudo escribir el archivo de exportación SPDX
+error-stats-export-write = No se pudo escribir el archivo de exportación de estadísticas
+error-read-only-export = El modo de solo lectura no permite escribir exportaciones dentro del directorio elegido
+error-template-placeholder = La plantilla del nombre de exportación contiene un marcador desconocido
+error-template-case-id = La plantilla del nombre de exportación necesita un ID del caso, pero no se indicó ninguno
+error-date-format-empty = El formato de fecha no puede estar vacío
+error-date-format-specifier = El formato de fecha contiene un especificador desconocido
+error-filename-empty = El nombre de exportación no puede estar vacío
+error-filename-characters = El nombre de exportación contiene caracteres que no se permiten en nombres de archivo
+error-filename-ending = En Windows, el nombre de exportación no puede terminar en punto ni en espacio
+error-filename-reserved = El nombre de exportación es un nombre de dispositivo reservado por Windows
+error-no-folder = El archivo no está dentro de una carpeta
+error-open-path = No se pudo abrir la ruta con la aplicación predeterminada
+error-reveal-path = No se pudo mostrar el archivo en el gestor de archivos
+error-action-log-empty = Todavía no se ha registrado nada en el registro de acciones
+error-diagnostics-write = No se pudo escribir el paquete de diagnóstico
+error-profile-unnamed = Pon un nombre al perfil antes de guardarlo
+error-profile-missing = No hay ningún perfil con ese nombre
+error-project-file-read = No se pudo leer .folsum.toml
+error-project-file-parse = No se pudo entender .folsum.toml
+error-api-token = Configura un token de API antes de activar la API de resultados
+error-api-port = No se pudo iniciar la API de resultados. ¿Otro programa está usando su puerto?
+error-api-start = No se pudo iniciar la API de resultados
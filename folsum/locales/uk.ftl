@@ -0,0 +1,329 @@
+## Верхнє меню.
+
+menu-file = Файл
+menu-new-session = Новий сеанс
+menu-quit = Вийти
+menu-view = Вигляд
+menu-tools = Інструменти
+menu-quick-hash = Швидкий хеш...
+menu-settings = Налаштування
+menu-help = Довідка
+menu-walkthrough = Показати інструкцію
+menu-about = Про FolSum
+menu-validate-tool = Перевірити інструмент
+menu-validate-tool-hover = Перевірити MD5, SHA-1 і SHA-256 на опублікованих тестових векторах, наприклад під час валідації FolSum для лабораторії
+
+## Вікно «Про програму».
+
+about-title = Про FolSum
+about-version = Версія
+about-commit = Коміт
+about-build-date = Зібрано
+about-os = Операційна система
+about-copy = Копіювати дані про версію
+about-save-diagnostics = Зберегти діагностичний пакет...
+about-save-diagnostics-hover = Запакувати ваші налаштування, нещодавній журнал, останній підсумок і останній звіт про збій, щоб додати їх до повідомлення про помилку. Перевірте вміст перед тим, як ним ділитися.
+about-open-action-log = Відкрити журнал дій
+about-open-action-log-hover = Показати журнал, до якого лише додаються записи, з усіма підсумовуваннями, експортами й діагностичними пакетами на цьому комп'ютері, із часом і користувачем системи
+about-diagnostics-saved = Діагностичний пакет збережено в { $bundle_path }
+
+## Вікно самоперевірки.
+
+self-test-title = Самоперевірка інструмента
+self-test-algorithm-passed = ✔ Дав опубліковані відповіді
+self-test-algorithm-failed = ✖ Дав неправильні відповіді
+self-test-passed = Самоперевірку інструмента пройдено. Результат записано в журнал і в заголовок кожного експорту.
+self-test-failed = Самоперевірку інструмента не пройдено. Хешам цієї копії FolSum не можна довіряти, тож не покладайтеся на її контрольні суми й експорти.
+
+## Вікно налаштувань.
+
+settings-language = Мова
+settings-theme = Тема
+theme-default = Типова
+theme-high-contrast = Висока контрастність
+theme-colorblind-safe = Для людей з дальтонізмом
+settings-summarization = Підсумовування
+settings-profile = Профіль
+settings-profile-custom = (власний)
+settings-profile-delete = Видалити профіль
+settings-profile-name = Назва профілю
+settings-profile-save = Зберегти як профіль
+settings-profile-save-hover = Зберегти наведені нижче налаштування підсумовування під цією назвою, замінивши профіль з такою ж назвою, якщо він є.
+settings-same-file-system = Залишатися в одній файловій системі
+settings-same-file-system-hover = Не заходити в інші томи, змонтовані всередині вибраного каталогу
+settings-limit-depth = Обмежити глибину до
+settings-depth-levels = рівнів
+settings-ignore-metadata = Ігнорувати службові файли ОС:
+settings-ignore-metadata-hover = Пропускати файли, які залишають операційні системи, як-от .DS_Store, Thumbs.db і теки .Trash, під час підсумовування, експорту та перевірки файлів контрольних сум
+settings-ignore-macos = MacOS
+settings-ignore-windows = Windows
+settings-ignore-linux = Linux
+settings-verify-checksums = Перевіряти файли контрольних сум
+settings-verify-checksums-hover = Перевіряти файли, перелічені у файлах контрольних сум (як-от checksums.md5 або SHA256SUMS), знайдених під час підсумовування
+settings-extra-streams = Шукати приховані потоки даних
+settings-extra-streams-hover = Перелічувати альтернативні потоки даних (у Windows) і розширені атрибути (у MacOS і Linux) файлів, з їхніми розмірами та хешами
+settings-retry-locked = Повторити заблоковані файли наприкінці
+settings-retry-locked-hover = Ще раз перевірити перелічені файли, які заблокувала інша програма, після перевірки всіх інших файлів
+settings-size-only-extensions = Лише розмір для розширень:
+settings-size-only-extensions-hover = Розширення через кому (як-от mov, braw) перелічених файлів, для яких записується лише розмір замість хешування, бо хешування триває надто довго
+settings-size-only-larger = Лише розмір для файлів, більших за
+settings-size-only-mib = МіБ
+settings-severity = Серйозність знахідок
+settings-severity-hint = Критичні знахідки провалюють аудит, а попередження залишають його непідтвердженим. Зміни застосовуються з наступного підсумовування.
+settings-severity-info-extensions = Лише зазначати знахідки для розширень:
+settings-severity-info-extensions-hover = Розширення через кому, як-от "tmp, log", для тимчасових файлів, які мають змінюватися. Їхні знахідки ніколи не зараховуються проти аудиту.
+severity-info = Інформація
+severity-warning = Попередження
+severity-critical = Критично
+settings-read-buffer = Буфер читання
+settings-read-buffer-hover = Під час обчислення хешів файли читаються частинами. Розмір частини підлаштовується в цих межах під найшвидше читання, яке зазвичай більше на мережевих ресурсах.
+settings-read-buffer-min = від
+settings-read-buffer-max = до
+settings-read-buffer-kib = КіБ
+settings-readers-per-device = Файлів, що читаються одночасно з кожного диска
+settings-readers-per-device-hover = Підсумовування, експорт і швидкі хеші читають з одного диска по черзі. Жорсткі диски найшвидші з одним читачем, а SSD і мережеві ресурси витримують більше.
+settings-shutdown-timeout = Чекати на зупинку роботи під час закриття
+settings-shutdown-timeout-hover = Якщо FolSum закривають під час підсумовування чи експорту, роботу просять зупинитися й дають на це стільки часу. Незавершені до того експорти відкидаються, а не залишаються записаними наполовину.
+settings-shutdown-timeout-seconds = секунд
+settings-system-log = Записувати події до системного журналу
+settings-system-log-hover = Записувати початок і завершення підсумовувань та кожну розбіжність контрольних сум до syslog (у MacOS і Linux) або журналу подій Windows, щоб вони потрапляли до централізованого журналювання
+settings-export-filenames = Імена файлів експорту
+settings-filename-template = Шаблон імені файлу:
+settings-placeholders = Заповнювачі: { $placeholders }
+settings-date-format = Формат дати:
+settings-date-format-hover = Як записується { "{date}" }, зі специфікаторами strftime, як-от %Y (рік), %m (місяць) і %d (день)
+settings-date-iso = ISO 8601
+settings-date-legacy = РР_ММ_ДД
+settings-dates-in-utc = Датувати експорти в UTC
+settings-dates-in-utc-hover = Використовувати UTC замість часового поясу цього комп'ютера для дат у назвах файлів і часових позначок експортів
+settings-preview = Попередній перегляд:
+settings-reset-template = Скинути до типового
+settings-results-api = API результатів
+settings-api-enabled = Надавати результати іншим програмам
+settings-api-enabled-hover = Дозволити програмам на цьому комп'ютері (наприклад, системам керування справами) читати підсумок і результати перевірки контрольних сум у форматі JSON з GET /summary і GET /checksums
+settings-api-port = Порт:
+settings-api-token = Токен:
+settings-api-token-hover = Секрет, який програми мають надсилати як "Authorization: Bearer <token>". Без нього API не запуститься
+settings-api-listening = Слухає на { $address }
+
+## Ліва панель.
+
+choose-directory = Виберіть каталог для підсумовування
+open-directory = Відкрити каталог...
+chosen-directory = Вибраний каталог:
+no-directory-selected = Каталог не вибрано
+summarize = Підсумувати
+summarization-estimating = Оцінювання розміру каталогу...
+summarization-in-progress = Підсумовування...
+summarization-failed = Не вдалося підсумувати: { $reason }
+large-directory-title = Підсумувати цей каталог?
+large-directory-description = { $directory } містить { $counted ->
+        [all] близько
+       *[some] понад
+    } { $file_count } файлів ({ $total_size }). Підсумовування може тривати довго й сповільнити цей комп'ютер. Продовжити?
+system-directory-warning = { $directory } є коренем диска або містить операційну систему, а це зазвичай не те, що треба підсумовувати.
+summarized-files = Підсумовано { $file_count ->
+        [one] { $shown_file_count } файл
+        [few] { $shown_file_count } файли
+       *[other] { $shown_file_count } файлів
+    } за { $duration }
+pinned-options = Деякі параметри закріплено файлом { $project_file } цієї папки
+summarized-bytes = Загальний розмір: { $total_size } ({ $allocated_size } на диску)
+sparse-files = { $sparse_count ->
+        [one] { $shown_sparse_count } розріджений файл займає на диску менше місця, ніж його довжина
+        [few] { $shown_sparse_count } розріджені файли займають на диску менше місця, ніж їхня довжина
+       *[other] { $shown_sparse_count } розріджених файлів займають на диску менше місця, ніж їхня довжина
+    }
+skipped-hardlinks = Пропущено зайвих жорстких посилань на вже пораховані файли: { $hardlink_count }
+performance = Продуктивність
+performance-walk = Обхід каталогу
+performance-checksum-verification = Перевірка файлів контрольних сум
+performance-export-write = Запис експорту
+performance-not-run = Ще не запускалося
+checksum-progress = Перевірено { $checked } з { $listed } зазначених файлів
+checksum-summary = Контрольні суми: перевірено { $verified }, не збігається { $mismatched }, відсутні { $missing }, не читаються { $unreadable }, заблоковано { $locked }, поза своєю текою { $outside }, лише розмір { $size_only }
+findings-show = Показати знахідки
+findings-title = Знахідки
+findings-hint = Вибирайте знахідки клацанням, Ctrl-клацанням і Shift-клацанням. Двічі клацніть файл, щоб знайти його тип у таблиці результатів.
+findings-none = Усі файли зі списку пройшли перевірку.
+findings-group = { $category } ({ $shown_finding_count })
+findings-metadata-drift = Змінено після внесення до списку
+findings-ignored = Ігноровані
+findings-accepted = Прийняті як новий еталон
+findings-ignore = Ігнорувати
+findings-ignore-hover = Додати вибрані файли до списку ігнорованих, щоб їхні знахідки більше не заважали
+findings-accept = Прийняти
+findings-accept-hover = Прийняти вибрані файли такими, як вони є зараз. Якщо вони знову зміняться, їх буде позначено знову
+findings-undo = Скасувати рішення
+findings-reverify = Перевірити ще раз
+findings-reverify-hover = Знову перевірити вибрані файли за їхніми файлами контрольних сум, наприклад після закриття програми, що їх блокувала
+findings-reverify-in-progress = Повторна перевірка...
+findings-reverify-finished = Перевірено ще раз
+findings-reverify-failed = Не вдалося перевірити ще раз: { $reason }
+findings-export = Експортувати вибране
+export-findings-title = Експортувати знахідки у файл CSV
+audit-content-passed = Цілісність вмісту: перевірено
+audit-content-failed = Цілісність вмісту: порушено
+audit-content-unconfirmed = Цілісність вмісту: не вдалося підтвердити, бо деякі файли не вдалося прочитати
+audit-report = Перевірено файлів контрольних сум: { $checksum_files }, завершено { $finished_at }, тривало { $duration }
+audit-metadata-unchanged = Зміни метаданих: немає
+audit-metadata-drift = Зміни метаданих: { $drift_count ->
+        [one] { $shown_drift_count } файл змінено
+        [few] { $shown_drift_count } файли змінено
+       *[other] { $shown_drift_count } файлів змінено
+    } після внесення до списку, але їхній вміст не змінився
+checksum-mismatch = Не збігається
+checksum-missing = Відсутній
+checksum-unreadable = Не читається
+checksum-locked = Заблоковано або використовується
+checksum-outside-directory = Поза своєю текою
+checksum-file = Файл контрольних сум
+checksum-listed-hash = Зазначений хеш
+checksum-actual-hash = Фактичний хеш
+checksum-file-size = Розмір
+checksum-modified = Змінено
+checksum-listed-at = Файл контрольних сум записано
+checksum-error = Помилка
+extra-streams = Приховані потоки даних ({ $stream_count })
+read-only-mode = Режим лише для читання
+read-only-mode-hover = Ніколи нічого не записувати всередині вибраного каталогу
+choose-reports-directory = Вибрати каталог звітів...
+choose-reports-directory-title = Виберіть каталог для експорту
+reports-directory = Каталог звітів:
+no-reports-directory-selected = Каталог звітів не вибрано
+case-id = Номер справи:
+operator = Оператор:
+os-user = Користувач системи:
+notes = Примітки:
+export-csv = Експортувати в CSV
+export-csv-title = Експортувати кількість розширень у файл CSV
+export-previewing = Підрахунок файлів для експорту...
+export-in-progress = Експортування...
+export-finished = Експорт завершено
+export-failed = Не вдалося експортувати: { $reason }
+export-reveal-hover = Показати експорт у файловому менеджері
+export-open-folder = Відкрити теку з файлом
+export-preview-title = Записати цей експорт?
+export-preview-description = Призначення: { $export_path }
+    Рядків: { $counted ->
+        [all] { $row_count }
+       *[some] понад { $row_count }
+    }
+    Загальний розмір: { $total_size }
+    Фільтри: { $filters }
+export-preview-first-rows = Перші рядки ({ $shown_count }):
+export-filter-none = немає
+export-filter-selected = лише вибрані розширення ({ $selected_count })
+export-filter-depth = не глибше { $max_depth } рівнів
+export-filter-same-file-system = в межах однієї файлової системи
+export-filter-ignored-metadata = без службових файлів { $platforms }
+export-selected = Експортувати вибрані рядки
+export-selected-hover = Вибирайте рядки таблиці результатів клацанням, Ctrl+клацанням і Shift+клацанням
+export-spdx = Експортувати список файлів SPDX
+export-spdx-hover = Обчислити хеші всіх файлів вибраного каталогу й перелічити їх із розмірами в документі SPDX 2.3 для інструментів SBOM
+export-spdx-title = Експортувати список файлів у документ SPDX
+export-stats = Експортувати статистику
+export-stats-hover = Зберегти у файл CSV кількість і розміри файлів за розширеннями, кількість файлів на кожному рівні вкладеності та найбільші файли
+export-stats-title = Експортувати статистику у файл CSV
+export-exists-title = Файл експорту вже існує
+export-exists-description = { $chosen_path } вже існує.
+
+    Перезаписати його? Виберіть «Ні», щоб зберегти експорт як { $versioned_path }.
+written-with-love = з любов'ю від{" "}
+
+## Вікно швидкого хешу.
+
+quick-hash-title = Швидкий хеш
+quick-hash-instructions = Перетягніть файл на FolSum або виберіть його, щоб побачити хеші MD5 і SHA-256.
+quick-hash-choose-file = Вибрати файл...
+quick-hash-file = Файл:
+quick-hash-hashing = Обчислення хешу...
+quick-hash-copy = Копіювати
+copy-full-hash = Копіювати повний хеш
+quick-hash-compare = Порівняти з:
+hash-match = Збігається
+hash-mismatch = Не збігається
+hash-unrecognized = Це не хеш MD5 чи SHA-256
+
+## Покрокова інструкція.
+
+onboarding-title = Початок роботи ({ $step } з { $step_count })
+onboarding-choose-directory = Почніть із натискання «Відкрити каталог...» і виберіть папку, яку хочете підсумувати.
+onboarding-summarize = Натисніть «Підсумувати», щоб порахувати файли у вибраній папці за розширеннями.
+onboarding-review-results = Результати з'являються тут, найпоширеніші розширення — першими. Групуйте їх за папками верхнього рівня, вибирайте стовпці чи рядки на панелі інструментів.
+onboarding-export = Заповніть номер справи, оператора й примітки, а потім натисніть «Експортувати в CSV», щоб зберегти результати.
+onboarding-back = Назад
+onboarding-next = Далі
+onboarding-done = Готово
+onboarding-skip = Пропустити
+
+## Результати.
+
+results-heading = Підсумок за розширеннями файлів
+group-by-folder = Групувати за папками верхнього рівня
+columns-menu = Стовпці
+copy-tsv = Копіювати як TSV
+copy-tsv-hover = Копіювати показані рядки та стовпці, щоб вставити їх у електронну таблицю
+folder-total = { $folder_name } ({ $folder_total ->
+        [one] { $shown_folder_total } файл
+        [few] { $shown_folder_total } файли
+       *[other] { $shown_folder_total } файлів
+    })
+column-file-extension = Розширення файлу
+column-occurrences = Кількість
+column-bytes = Байти
+
+## Вікно звіту про збій.
+
+crash-report-title = FolSum неочікувано закрився
+crash-report-description = Минулого разу FolSum аварійно завершився і зберіг звіт про збій. Додайте його до повідомлення про помилку, щоб проблему було легше виправити.
+crash-report-open = Відкрити звіт
+crash-report-copy = Копіювати звіт
+crash-report-dismiss = Закрити
+
+## Вікно, яке показується, поки FolSum чекає на зупинку фонової роботи.
+
+closing-title = Закриття FolSum
+closing-description = Очікування зупинки фонових завдань: { $running }…
+
+## Помилки під час підсумовування, хешування та експорту.
+
+error-stopped = Зупинено до завершення
+error-file-in-use = Файл заблоковано або він використовується іншою програмою
+error-worker-panicked = Фонове завдання аварійно завершилося
+error-already-summarizing = Підсумовування вже виконується
+error-hash-folder = Гешувати можна лише файли, а не теки
+error-hash-open = Не вдалося відкрити файл, щоб обчислити його хеш
+error-hash-read = Не вдалося прочитати файл, щоб обчислити його хеш
+error-outside-folder = Файл контрольних сум містить шлях поза своєю текою, тому його не прочитано
+error-not-utf8 = Файл не є коректним UTF-8
+error-not-utf16 = Файл не є коректним UTF-16
+error-reverify-unselected = Виберіть знахідки, перш ніж перевіряти їх знову
+error-export-unselected = Виберіть знахідки, перш ніж експортувати їх
+error-export-no-path = Не вказано шлях до файлу експорту
+error-spdx-no-directory = Виберіть каталог, перш ніж експортувати документ SPDX
+error-csv-export-write = Не вдалося створити файл експорту CSV
+error-findings-export-write = Не вдалося записати файл експорту знахідок
+error-spdx-export-write = Не вдалося записати файл експорту SPDX
+error-stats-export-write = Не вдалося записати файл експорту статистики
+error-read-only-export = Режим лише для читання забороняє записувати експорт усередині вибраного каталогу
+error-template-placeholder = Шаблон назви файлу експорту містить невідомий заповнювач
+error-template-case-id = Шаблон назви файлу експорту потребує номера справи, але його не вказано
+error-date-format-empty = Формат дати не може бути порожнім
+error-date-format-specifier = Формат дати містить невідомий специфікатор
+error-filename-empty = Назва файлу експорту не може бути порожньою
+error-filename-characters = Назва файлу експорту містить символи, недозволені в назвах файлів
+error-filename-ending = У Windows назва файлу експорту не може закінчуватися крапкою або пробілом
+error-filename-reserved = Назва файлу експорту є назвою пристрою, зарезервованою Windows
+error-no-folder = Файл не знаходиться в теці
+error-open-path = Не вдалося відкрити шлях у програмі за замовчуванням
+error-reveal-path = Не вдалося показати файл у файловому менеджері
+error-action-log-empty = У журналі дій ще нічого не записано
+error-diagnostics-write = Не вдалося записати пакет діагностики
+error-profile-unnamed = Назвіть профіль, перш ніж зберегти його
+error-profile-missing = Профілю з такою назвою немає
+error-project-file-read = Не вдалося прочитати .folsum.toml
+error-project-file-parse = Не вдалося розібрати .folsum.toml
+error-api-token = Задайте токен API, перш ніж вмикати API результатів
+error-api-port = Не вдалося запустити API результатів. Можливо, його порт використовує інша програма?
+error-api-start = Не вдалося запустити API результатів
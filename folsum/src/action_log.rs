@@ -0,0 +1,56 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+
+use crate::os_user_name;
+
+/// Where the action log's kept, like `~/.local/share/FolSum/action_log.tsv` on Linux.
+pub fn action_log_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|data_directory| data_directory.join("FolSum/action_log.tsv"))
+}
+
+/// Add a line to the end of an action log, saying when, by which OS user, what was done, and to which path.
+///
+/// Lines are only ever appended, never changed or removed, so the log shows everything that was done.
+pub fn append_action(log_path: &Path, action: &str, target: &Path) -> io::Result<()> {
+    if let Some(log_directory) = log_path.parent() {
+        fs::create_dir_all(log_directory)?;
+    }
+    let mut action_log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)?;
+    // Tabs and newlines would break the log's columns, so swap them out of paths.
+    let target: String = target
+        .display()
+        .to_string()
+        .replace(['\t', '\n', '\r'], " ");
+    writeln!(
+        action_log,
+        "{}\t{}\t{}\t{}",
+        Local::now().to_rfc3339(),
+        os_user_name(),
+        action,
+        target
+    )
+}
+
+/// Record an action in the user's action log, carrying on if it can't be written.
+pub fn record_action(action: &str, target: &Path) {
+    let append_result = match action_log_path() {
+        Some(log_path) => append_action(&log_path, action, target),
+        None => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no app data folder to keep the action log in",
+        )),
+    };
+    if let Err(log_error) = append_result {
+        log::warn!(
+            "Couldn't record {} in the action log: {}",
+            action,
+            log_error
+        );
+    }
+}
@@ -0,0 +1,775 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Local};
+use web_time::Duration;
+
+use crate::{
+    format_duration, hash_file_with_buffer, is_within_directory, lock_shared, spawn_worker,
+    stop_requested, ReadBuffer, SummarizationOptions, WorkerStatus, FILE_IN_USE_ERROR,
+    STOPPED_REASON,
+};
+
+/// Names of checksum files that don't have a telltale extension, compared case-insensitively.
+const CHECKSUM_FILENAMES: [&str; 2] = ["md5sums", "sha256sums"];
+/// Extensions of checksum files, like vendors' `checksums.md5`.
+const CHECKSUM_EXTENSIONS: [&str; 2] = ["md5", "sha256"];
+/// Why a listed file that leads out of its checksum file's folder wasn't read.
+const OUTSIDE_DIRECTORY_ERROR: &str =
+    "The checksum file lists a path outside of its folder, so it wasn't read";
+
+/// What happened when a file that's listed in a checksum file was checked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumOutcome {
+    // The file's hash matches the one in the checksum file.
+    Verified,
+    // The file's hash doesn't match the one in the checksum file.
+    Mismatch,
+    // The file isn't where the checksum file says it is.
+    Missing,
+    // The file (or the checksum file itself) couldn't be read.
+    Unreadable,
+    // Another program had the file open without letting anyone else read it, like Outlook with its mailbox on Windows.
+    Locked,
+    // The file's there, but a size-only rule says it's too slow to hash, so only its size was recorded.
+    SizeOnly { file_size: u64 },
+    // The checksum file lists a path that leads out of its folder (like `../outside` or `/etc/passwd`), so it wasn't
+    // read.
+    OutsideDirectory,
+}
+
+impl ChecksumOutcome {
+    /// Name of the outcome in exports and the results API, which stays the same whatever language the GUI's in.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChecksumOutcome::Verified => "verified",
+            ChecksumOutcome::Mismatch => "mismatch",
+            ChecksumOutcome::Missing => "missing",
+            ChecksumOutcome::Unreadable => "unreadable",
+            ChecksumOutcome::Locked => "locked",
+            ChecksumOutcome::SizeOnly { .. } => "size_only",
+            ChecksumOutcome::OutsideDirectory => "outside_directory",
+        }
+    }
+}
+
+/// Files that are only checked for existence and size, instead of being hashed, because hashing them takes too long.
+///
+/// Useful for things like raw camera footage, where a size record is usually enough.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)] // Define default fields when deserializing old state.
+pub struct SizeOnlyRules {
+    // Comma-separated extensions (without dots) that are never hashed, like `mov, braw, r3d`.
+    pub extensions: String,
+    // Files that are bigger than this many mebibytes are never hashed, whatever their extension.
+    pub larger_than_mib: Option<u64>,
+}
+
+impl SizeOnlyRules {
+    /// Check if a file should only have its size recorded instead of being hashed.
+    pub fn applies_to(&self, file_path: &Path, file_size: u64) -> bool {
+        let extension_matches: bool = has_listed_extension(file_path, &self.extensions);
+        let size_matches: bool = self.larger_than_mib.map_or(false, |larger_than_mib| {
+            file_size > larger_than_mib.saturating_mul(1024 * 1024)
+        });
+        extension_matches || size_matches
+    }
+}
+
+/// How much a finding matters, from something that's only worth noting to something that fails the audit.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    /// Every severity, from least to most serious.
+    pub const ALL: [Severity; 3] = [Severity::Info, Severity::Warning, Severity::Critical];
+
+    /// Name of the severity in exports and the results API, which stays the same whatever language the GUI's in.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+/// User-configurable rules for how much each kind of finding matters, which decide the audit's verdict.
+///
+/// Critical findings fail the audit and warnings leave it unconfirmed, while informational ones don't count against
+/// it. Files whose content held up are always informational.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)] // Define default fields when deserializing old state.
+pub struct SeverityRules {
+    pub mismatch: Severity,
+    pub missing: Severity,
+    pub unreadable: Severity,
+    pub locked: Severity,
+    pub outside_directory: Severity,
+    // Comma-separated extensions (without dots) whose findings are only informational, like `tmp, log` for scratch
+    // files that are expected to change.
+    pub info_extensions: String,
+}
+
+impl Default for SeverityRules {
+    fn default() -> Self {
+        Self {
+            // Evidence that changed or disappeared is what audits are for.
+            mismatch: Severity::Critical,
+            missing: Severity::Critical,
+            // Files that couldn't be checked might be fine, but nobody can say for sure.
+            unreadable: Severity::Warning,
+            locked: Severity::Warning,
+            outside_directory: Severity::Warning,
+            info_extensions: String::new(),
+        }
+    }
+}
+
+impl SeverityRules {
+    /// Rule for a kind of finding, for changing it in settings, or `None` for outcomes whose content held up.
+    pub fn rule_mut(&mut self, outcome: ChecksumOutcome) -> Option<&mut Severity> {
+        match outcome {
+            ChecksumOutcome::Mismatch => Some(&mut self.mismatch),
+            ChecksumOutcome::Missing => Some(&mut self.missing),
+            ChecksumOutcome::Unreadable => Some(&mut self.unreadable),
+            ChecksumOutcome::Locked => Some(&mut self.locked),
+            ChecksumOutcome::OutsideDirectory => Some(&mut self.outside_directory),
+            ChecksumOutcome::Verified | ChecksumOutcome::SizeOnly { .. } => None,
+        }
+    }
+
+    /// Decide how much a finding matters.
+    pub fn severity(&self, finding: &ChecksumFinding) -> Severity {
+        if has_listed_extension(&finding.listed_file, &self.info_extensions) {
+            return Severity::Info;
+        }
+        match finding.outcome {
+            ChecksumOutcome::Mismatch => self.mismatch,
+            ChecksumOutcome::Missing => self.missing,
+            ChecksumOutcome::Unreadable => self.unreadable,
+            ChecksumOutcome::Locked => self.locked,
+            ChecksumOutcome::OutsideDirectory => self.outside_directory,
+            ChecksumOutcome::Verified | ChecksumOutcome::SizeOnly { .. } => Severity::Info,
+        }
+    }
+}
+
+/// What was found on disk when a listed file was checked, for explaining why it didn't hold up.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListedFileDetails {
+    // Hash of the file as it is now, with the same algorithm as the listed hash.
+    pub actual_hash: Option<String>,
+    pub file_size: Option<u64>,
+    pub modified: Option<DateTime<Local>>,
+    // Why the file couldn't be read, if it couldn't.
+    pub error: Option<String>,
+}
+
+/// Result of checking one file that's listed in a checksum file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChecksumFinding {
+    pub checksum_file: PathBuf,
+    pub listed_file: PathBuf,
+    // Hash that the checksum file lists for the file, in lowercase hex.
+    pub listed_hash: String,
+    pub outcome: ChecksumOutcome,
+    pub details: ListedFileDetails,
+    // When the checksum file was last modified, which is the last time the listed file was known to be good.
+    pub listed_at: Option<DateTime<Local>>,
+}
+
+impl ChecksumFinding {
+    /// Check if the file's content held up but it was modified after its checksum file was written.
+    ///
+    /// This is usually harmless, like a sync tool touching timestamps, but it's worth knowing about.
+    pub fn has_metadata_drift(&self) -> bool {
+        let content_held_up: bool = matches!(
+            self.outcome,
+            ChecksumOutcome::Verified | ChecksumOutcome::SizeOnly { .. }
+        );
+        match (self.details.modified, self.listed_at) {
+            (Some(modified), Some(listed_at)) => content_held_up && modified > listed_at,
+            _ => false,
+        }
+    }
+}
+
+/// What the user decided about findings that they've looked into, so the ones they've dealt with stop standing out.
+///
+/// Decisions are only remembered; they don't change findings or the audit verdict, so nothing is hidden from exports.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)] // Define default fields when deserializing old state.
+pub struct FindingDecisions {
+    // Listed files whose findings are expected, like scratch files that are always changing.
+    pub ignored: Vec<PathBuf>,
+    // Listed files whose content was accepted as the new baseline, with the hash that they had when it was accepted.
+    // Files that couldn't be hashed (like missing ones) are accepted with an empty hash.
+    pub accepted: Vec<(PathBuf, String)>,
+}
+
+impl FindingDecisions {
+    /// Check if a finding's file is on the ignore list.
+    pub fn ignores(&self, finding: &ChecksumFinding) -> bool {
+        self.ignored.contains(&finding.listed_file)
+    }
+
+    /// Check if a finding's file was accepted as it is now.
+    ///
+    /// Acceptance lapses as soon as the file changes again, so a re-baselined file can't hide later tampering.
+    pub fn accepts(&self, finding: &ChecksumFinding) -> bool {
+        let current_hash: &str = finding.details.actual_hash.as_deref().unwrap_or_default();
+        self.accepted.iter().any(|(accepted_file, accepted_hash)| {
+            *accepted_file == finding.listed_file && accepted_hash == current_hash
+        })
+    }
+
+    /// Put findings' files on the ignore list.
+    pub fn ignore(&mut self, findings: &[&ChecksumFinding]) {
+        for finding in findings {
+            if !self.ignores(finding) {
+                self.ignored.push(finding.listed_file.clone());
+            }
+        }
+    }
+
+    /// Accept findings' files as they are now, replacing what was accepted for them before.
+    pub fn accept(&mut self, findings: &[&ChecksumFinding]) {
+        for finding in findings {
+            self.accepted
+                .retain(|(accepted_file, _)| *accepted_file != finding.listed_file);
+            let current_hash: String = finding.details.actual_hash.clone().unwrap_or_default();
+            self.accepted
+                .push((finding.listed_file.clone(), current_hash));
+        }
+    }
+
+    /// Take back every decision about findings' files.
+    pub fn undo(&mut self, findings: &[&ChecksumFinding]) {
+        let is_undone = |decided_file: &PathBuf| {
+            findings
+                .iter()
+                .any(|finding| finding.listed_file == *decided_file)
+        };
+        self.ignored.retain(|ignored_file| !is_undone(ignored_file));
+        self.accepted
+            .retain(|(accepted_file, _)| !is_undone(accepted_file));
+    }
+
+    /// Name of the decision about a finding in exports, or an empty string if there isn't one.
+    pub fn describe(&self, finding: &ChecksumFinding) -> &'static str {
+        match (self.ignores(finding), self.accepts(finding)) {
+            (true, _) => "ignored",
+            (false, true) => "accepted",
+            (false, false) => "",
+        }
+    }
+}
+
+/// How the content of the files that checksum files list held up, as a whole.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContentVerdict {
+    // No findings are warnings or critical, like when every listed file matches its listed hash.
+    Passed,
+    // At least one finding is critical, like a listed file that's missing or doesn't match its listed hash.
+    Failed,
+    // Nothing's critical, but some findings are warnings, like listed files that couldn't be read to make sure.
+    Unconfirmed,
+}
+
+impl ContentVerdict {
+    /// Name of the verdict in exports and the results API, which stays the same whatever language the GUI's in.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContentVerdict::Passed => "passed",
+            ContentVerdict::Failed => "failed",
+            ContentVerdict::Unconfirmed => "unconfirmed",
+        }
+    }
+}
+
+/// Overall result of checking files against their checksum files, judged separately for content and for metadata.
+///
+/// A folder passes on content as long as its files' hashes hold up, even if their metadata has drifted, so that
+/// changed timestamps are surfaced without failing the whole run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditVerdict {
+    pub content: ContentVerdict,
+    // Files whose content held up but that were modified after they were listed.
+    pub metadata_drift: Vec<PathBuf>,
+}
+
+/// Kinds of findings that are discrepancies, in the order that they're worth looking into.
+pub const DISCREPANCY_OUTCOMES: [ChecksumOutcome; 5] = [
+    ChecksumOutcome::Mismatch,
+    ChecksumOutcome::Missing,
+    ChecksumOutcome::OutsideDirectory,
+    ChecksumOutcome::Unreadable,
+    ChecksumOutcome::Locked,
+];
+
+/// Group the findings that are discrepancies by what went wrong, leaving out kinds that weren't found.
+pub fn group_discrepancies(
+    checksum_findings: &[ChecksumFinding],
+) -> Vec<(ChecksumOutcome, Vec<&ChecksumFinding>)> {
+    DISCREPANCY_OUTCOMES
+        .iter()
+        .map(|discrepancy_outcome| {
+            let grouped_findings: Vec<&ChecksumFinding> = checksum_findings
+                .iter()
+                .filter(|finding| finding.outcome == *discrepancy_outcome)
+                .collect();
+            (*discrepancy_outcome, grouped_findings)
+        })
+        .filter(|(_, grouped_findings)| !grouped_findings.is_empty())
+        .collect()
+}
+
+/// Judge checksum findings on content (hashes) and on metadata (modification times) separately.
+///
+/// Content is judged by the most serious finding, according to `severity_rules`.
+pub fn audit_verdict(
+    checksum_findings: &[ChecksumFinding],
+    severity_rules: &SeverityRules,
+) -> AuditVerdict {
+    let worst_severity: Option<Severity> = checksum_findings
+        .iter()
+        .map(|finding| severity_rules.severity(finding))
+        .max();
+    let content: ContentVerdict = match worst_severity {
+        Some(Severity::Critical) => ContentVerdict::Failed,
+        Some(Severity::Warning) => ContentVerdict::Unconfirmed,
+        Some(Severity::Info) | None => ContentVerdict::Passed,
+    };
+    let metadata_drift: Vec<PathBuf> = checksum_findings
+        .iter()
+        .filter(|finding| finding.has_metadata_drift())
+        .map(|finding| finding.listed_file.clone())
+        .collect();
+    AuditVerdict {
+        content,
+        metadata_drift,
+    }
+}
+
+/// Everything that checking checksum files turned up, gathered into one report when a summarization finishes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditReport {
+    // Directory whose checksum files were checked.
+    pub directory: PathBuf,
+    pub finished_at: DateTime<Local>,
+    // How long checking listed files took, including second tries of locked files.
+    pub duration: Duration,
+    // Checksum files that were found, including ones that couldn't be read.
+    pub checksum_file_count: usize,
+    // How many findings there were of each severity, from least to most serious.
+    pub severity_counts: Vec<(Severity, usize)>,
+    pub findings: Vec<ChecksumFinding>,
+    pub verdict: AuditVerdict,
+}
+
+impl AuditReport {
+    /// Gather checksum findings into a report, judging them by `severity_rules`.
+    pub fn new(
+        directory: &Path,
+        checksum_file_count: usize,
+        findings: Vec<ChecksumFinding>,
+        severity_rules: &SeverityRules,
+        duration: Duration,
+    ) -> Self {
+        let severity_counts: Vec<(Severity, usize)> = Severity::ALL
+            .iter()
+            .map(|counted_severity| {
+                let severity_count: usize = findings
+                    .iter()
+                    .filter(|finding| severity_rules.severity(finding) == *counted_severity)
+                    .count();
+                (*counted_severity, severity_count)
+            })
+            .collect();
+        Self {
+            directory: directory.to_path_buf(),
+            finished_at: Local::now(),
+            duration,
+            checksum_file_count,
+            severity_counts,
+            verdict: audit_verdict(&findings, severity_rules),
+            findings,
+        }
+    }
+
+    /// Sum up the report in one line for export headers, like
+    /// `failed (1 critical, 0 warning, 2 info) in 3 checksum files, checked in 2s`.
+    pub fn summary(&self) -> String {
+        let severity_counts: Vec<String> = self
+            .severity_counts
+            .iter()
+            .rev()
+            .map(|(severity, severity_count)| format!("{severity_count} {}", severity.name()))
+            .collect();
+        format!(
+            "{} ({}) in {} checksum files, checked in {}",
+            self.verdict.content.name(),
+            severity_counts.join(", "),
+            self.checksum_file_count,
+            format_duration(&self.duration)
+        )
+    }
+}
+
+/// Check if a file looks like a list of checksums, like `checksums.md5` or `SHA256SUMS`.
+pub fn is_checksum_file(file_path: &Path) -> bool {
+    let has_checksum_extension: bool = file_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .map_or(false, |extension| {
+            CHECKSUM_EXTENSIONS.contains(&extension.as_str())
+        });
+    let has_checksum_name: bool = file_path
+        .file_name()
+        .map(|filename| filename.to_string_lossy().to_lowercase())
+        .map_or(false, |filename| {
+            CHECKSUM_FILENAMES.contains(&filename.as_str())
+        });
+    has_checksum_extension || has_checksum_name
+}
+
+/// Read the hashes and filenames out of a checksum file's contents.
+///
+/// Understands GNU-style lines (`<hash>  <filename>`, or `<hash> *<filename>` for binary mode) and BSD-style lines
+/// (`MD5 (<filename>) = <hash>`). Lines that aren't MD5 or SHA-256 checksums, like comments, are skipped.
+pub fn parse_checksum_file(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .filter_map(|checksum_line| {
+            let checksum_line: &str = checksum_line.trim_end();
+            // Try BSD-style lines first because their filenames come before their hashes.
+            if let Some((algorithm_and_filename, listed_hash)) = checksum_line.rsplit_once(") = ") {
+                let (_, listed_filename) = algorithm_and_filename.split_once(" (")?;
+                return is_hex_hash(listed_hash)
+                    .then(|| (listed_hash.to_lowercase(), listed_filename.to_string()));
+            }
+            let (listed_hash, listed_filename) = checksum_line.split_once(' ')?;
+            // Drop the second space of text mode lines or the asterisk of binary mode lines.
+            let listed_filename: &str = listed_filename
+                .strip_prefix(' ')
+                .or_else(|| listed_filename.strip_prefix('*'))
+                .unwrap_or(listed_filename);
+            (is_hex_hash(listed_hash) && !listed_filename.is_empty())
+                .then(|| (listed_hash.to_lowercase(), listed_filename.to_string()))
+        })
+        .collect()
+}
+
+/// Hashes and filenames that a checksum file lists, read before they're checked so they can be counted up front.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChecksumListing {
+    pub checksum_file: PathBuf,
+    // Listed hashes and filenames (relative to the checksum file's folder), in the order that they're listed.
+    pub entries: Vec<(String, String)>,
+    // When the checksum file was last modified, which is the last time the listed files were known to be good.
+    pub listed_at: Option<DateTime<Local>>,
+}
+
+impl ChecksumListing {
+    /// Check one of the listing's entries against the file that it names.
+    ///
+    /// Files that `size_only_rules` apply to aren't hashed, so they're reported as [`ChecksumOutcome::SizeOnly`]
+    /// instead of verified or mismatched. Listed paths that lead out of the checksum file's folder are never opened,
+    /// and are reported as [`ChecksumOutcome::OutsideDirectory`].
+    pub fn verify_entry(
+        &self,
+        listed_hash: &str,
+        listed_filename: &str,
+        size_only_rules: &SizeOnlyRules,
+        read_buffer: &mut ReadBuffer,
+    ) -> ChecksumFinding {
+        let checksum_directory: &Path = self
+            .checksum_file
+            .parent()
+            .unwrap_or_else(|| Path::new("."));
+        let listed_file: PathBuf = checksum_directory.join(listed_filename);
+        // Checksum files come from the evidence, so they mustn't be able to make FolSum hash files elsewhere.
+        let (outcome, details) =
+            match stays_in_directory(Path::new(listed_filename), checksum_directory) {
+                true => verify_listed_file(&listed_file, listed_hash, size_only_rules, read_buffer),
+                false => (
+                    ChecksumOutcome::OutsideDirectory,
+                    ListedFileDetails {
+                        error: Some(String::from(OUTSIDE_DIRECTORY_ERROR)),
+                        ..Default::default()
+                    },
+                ),
+            };
+        ChecksumFinding {
+            checksum_file: self.checksum_file.clone(),
+            listed_file,
+            listed_hash: listed_hash.to_string(),
+            outcome,
+            details,
+            listed_at: self.listed_at,
+        }
+    }
+}
+
+/// How far checking the files that checksum files list has gotten, for showing a progress bar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VerificationProgress {
+    // Listed files that have been checked so far, including second tries of locked files.
+    pub checked: usize,
+    // Listed files that will be checked in all, which grows when locked files are tried again.
+    pub listed: usize,
+}
+
+impl VerificationProgress {
+    /// Share of the listed files that have been checked, from 0 to 1.
+    pub fn fraction(&self) -> f32 {
+        match self.listed {
+            0 => 0.0,
+            listed => self.checked as f32 / listed as f32,
+        }
+    }
+}
+
+/// Read the hashes and filenames that a checksum file lists, without checking any of them yet.
+///
+/// If the checksum file itself can't be read, then a finding that reports it is returned instead, so it doesn't
+/// silently go unchecked.
+pub fn read_checksum_listing(
+    checksum_file: &Path,
+) -> Result<ChecksumListing, Box<ChecksumFinding>> {
+    let checksum_contents: Result<String, String> = fs::read(checksum_file)
+        .map_err(|read_error| read_error.to_string())
+        .and_then(|checksum_bytes| decode_text(&checksum_bytes).map_err(String::from));
+    let listed_at: Option<DateTime<Local>> = fs::metadata(checksum_file)
+        .and_then(|checksum_metadata| checksum_metadata.modified())
+        .ok()
+        .map(DateTime::<Local>::from);
+    match checksum_contents {
+        Ok(checksum_contents) => Ok(ChecksumListing {
+            checksum_file: checksum_file.to_path_buf(),
+            entries: parse_checksum_file(&checksum_contents),
+            listed_at,
+        }),
+        Err(read_error) => Err(Box::new(ChecksumFinding {
+            checksum_file: checksum_file.to_path_buf(),
+            listed_file: checksum_file.to_path_buf(),
+            listed_hash: String::new(),
+            outcome: ChecksumOutcome::Unreadable,
+            details: ListedFileDetails {
+                error: Some(read_error),
+                ..Default::default()
+            },
+            listed_at,
+        })),
+    }
+}
+
+/// Check every file that's listed in a checksum file against its listed hash.
+///
+/// Listed filenames are relative to the directory that the checksum file is in. Each one's checked like
+/// [`ChecksumListing::verify_entry`] does.
+pub fn verify_checksum_file(
+    checksum_file: &Path,
+    size_only_rules: &SizeOnlyRules,
+    read_buffer: &mut ReadBuffer,
+) -> Vec<ChecksumFinding> {
+    match read_checksum_listing(checksum_file) {
+        Ok(checksum_listing) => checksum_listing
+            .entries
+            .iter()
+            .map(|(listed_hash, listed_filename)| {
+                checksum_listing.verify_entry(
+                    listed_hash,
+                    listed_filename,
+                    size_only_rules,
+                    read_buffer,
+                )
+            })
+            .collect(),
+        Err(unreadable_finding) => vec![*unreadable_finding],
+    }
+}
+
+/// Check if a file's extension is in a comma-separated list of extensions, like `mov, .braw`, ignoring case.
+fn has_listed_extension(file_path: &Path, listed_extensions: &str) -> bool {
+    file_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .map_or(false, |extension| {
+            listed_extensions
+                .split(',')
+                .map(|listed_extension| listed_extension.trim().trim_start_matches('.'))
+                .any(|listed_extension| listed_extension.eq_ignore_ascii_case(&extension))
+        })
+}
+
+/// Check if a path that's relative to a directory lands inside of it, even after following symlinks.
+fn stays_in_directory(relative_path: &Path, directory: &Path) -> bool {
+    match directory.join(relative_path).canonicalize() {
+        // Resolve the file itself as well as its folder, since a symlink inside of the directory could point anywhere.
+        Ok(resolved_path) => is_within_directory(&resolved_path, directory),
+        // Paths that can't be resolved can't be opened either, so just make sure they don't have `..`, roots, or drive
+        // prefixes.
+        Err(_) => relative_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir)),
+    }
+}
+
+/// Check listed files against their listed hashes again in a separate thread, updating their findings in place.
+///
+/// Handy after fixing a problem, like closing the program that locked a file, without summarizing everything again.
+pub fn reverify_findings(
+    checksum_findings: &Arc<Mutex<Vec<ChecksumFinding>>>,
+    listed_files: &[PathBuf],
+    summarization_options: &SummarizationOptions,
+    reverify_status: &Arc<Mutex<WorkerStatus>>,
+) -> Result<(), &'static str> {
+    if listed_files.is_empty() {
+        return Err("Select findings before verifying them again");
+    }
+    let checksum_findings: Arc<Mutex<Vec<ChecksumFinding>>> = Arc::clone(checksum_findings);
+    let listed_files: Vec<PathBuf> = listed_files.to_vec();
+    let options_copy: SummarizationOptions = summarization_options.clone();
+    spawn_worker(reverify_status, move || {
+        // Copy the findings out so the GUI can keep showing them while files are hashed.
+        let reverified_findings: Vec<ChecksumFinding> = lock_shared(&checksum_findings)
+            .iter()
+            .filter(|finding| listed_files.contains(&finding.listed_file))
+            // Paths that lead out of their checksum file's folder stay unread, however many times they're checked.
+            .filter(|finding| finding.outcome != ChecksumOutcome::OutsideDirectory)
+            .cloned()
+            .collect();
+        let mut read_buffer = ReadBuffer::new(&options_copy.read_buffer_limits);
+        for mut finding in reverified_findings {
+            if stop_requested() {
+                return Err(String::from(STOPPED_REASON));
+            }
+            (finding.outcome, finding.details) = verify_listed_file(
+                &finding.listed_file,
+                &finding.listed_hash,
+                &options_copy.size_only_rules,
+                &mut read_buffer,
+            );
+            // Findings may have been cleared by a new summarization in the meantime, so only update ones that are left.
+            if let Some(stale_finding) =
+                lock_shared(&checksum_findings)
+                    .iter_mut()
+                    .find(|stale_finding| {
+                        stale_finding.listed_file == finding.listed_file
+                            && stale_finding.checksum_file == finding.checksum_file
+                    })
+            {
+                *stale_finding = finding;
+            }
+        }
+        Ok(())
+    });
+    Ok(())
+}
+
+/// Check one file that's listed in a checksum file against its listed hash.
+///
+/// Along with the outcome, returns what was found on disk (the file's actual hash, size, modification time, or why
+/// it couldn't be read) so that findings can be explained instead of just labeled.
+pub fn verify_listed_file(
+    listed_file: &Path,
+    listed_hash: &str,
+    size_only_rules: &SizeOnlyRules,
+    read_buffer: &mut ReadBuffer,
+) -> (ChecksumOutcome, ListedFileDetails) {
+    let file_metadata = match fs::metadata(listed_file) {
+        Err(_) if !listed_file.exists() => {
+            return (ChecksumOutcome::Missing, ListedFileDetails::default())
+        }
+        Err(metadata_error) => {
+            return (
+                ChecksumOutcome::Unreadable,
+                ListedFileDetails {
+                    error: Some(metadata_error.to_string()),
+                    ..Default::default()
+                },
+            )
+        }
+        Ok(file_metadata) => file_metadata,
+    };
+    let mut details = ListedFileDetails {
+        file_size: Some(file_metadata.len()),
+        modified: file_metadata.modified().ok().map(DateTime::<Local>::from),
+        ..Default::default()
+    };
+    if size_only_rules.applies_to(listed_file, file_metadata.len()) {
+        let outcome = ChecksumOutcome::SizeOnly {
+            file_size: file_metadata.len(),
+        };
+        return (outcome, details);
+    }
+    let outcome: ChecksumOutcome = match hash_file_with_buffer(listed_file, read_buffer) {
+        Ok(file_hashes) => {
+            // Compare like with like, so MD5 listings are shown next to the file's MD5.
+            details.actual_hash = Some(match listed_hash.len() {
+                32 => file_hashes.md5.clone(),
+                _ => file_hashes.sha256.clone(),
+            });
+            match file_hashes.matches(listed_hash) == Some(true) {
+                true => ChecksumOutcome::Verified,
+                false => ChecksumOutcome::Mismatch,
+            }
+        }
+        // Tell locked files apart from unreadable ones because they're usually fine once they're closed.
+        Err(FILE_IN_USE_ERROR) => {
+            details.error = Some(String::from(FILE_IN_USE_ERROR));
+            ChecksumOutcome::Locked
+        }
+        Err(hash_error) => {
+            details.error = Some(String::from(hash_error));
+            ChecksumOutcome::Unreadable
+        }
+    };
+    (outcome, details)
+}
+
+/// Read text that may have been re-saved by Notepad or Excel, which add byte order marks or switch to UTF-16.
+///
+/// UTF-8 (with or without a byte order mark) and UTF-16 with a byte order mark are understood. Anything else, like
+/// Windows-1252 text with accented filenames, is refused instead of being guessed at.
+pub fn decode_text(text_bytes: &[u8]) -> Result<String, &'static str> {
+    let utf16_units = |text_bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| -> Vec<u16> {
+        text_bytes
+            .chunks(2)
+            .map(|unit_bytes| from_bytes([unit_bytes[0], *unit_bytes.get(1).unwrap_or(&0)]))
+            .collect()
+    };
+    match text_bytes {
+        [0xEF, 0xBB, 0xBF, utf8_bytes @ ..] => {
+            String::from_utf8(utf8_bytes.to_vec()).map_err(|_| "The file isn't valid UTF-8")
+        }
+        [0xFF, 0xFE, utf16_bytes @ ..] => {
+            String::from_utf16(&utf16_units(utf16_bytes, u16::from_le_bytes))
+                .map_err(|_| "The file isn't valid UTF-16")
+        }
+        [0xFE, 0xFF, utf16_bytes @ ..] => {
+            String::from_utf16(&utf16_units(utf16_bytes, u16::from_be_bytes))
+                .map_err(|_| "The file isn't valid UTF-16")
+        }
+        utf8_bytes => String::from_utf8(utf8_bytes.to_vec()).map_err(|_| {
+            "The file's text encoding isn't supported. Save it as UTF-8 or UTF-16 and try again"
+        }),
+    }
+}
+
+/// Check if a string is as long as an MD5 or SHA-256 hash and made of hex digits.
+fn is_hex_hash(possible_hash: &str) -> bool {
+    matches!(possible_hash.len(), 32 | 64)
+        && possible_hash
+            .chars()
+            .all(|character| character.is_ascii_hexdigit())
+}
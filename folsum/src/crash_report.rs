@@ -0,0 +1,145 @@
+use std::backtrace::Backtrace;
+use std::env;
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+
+use chrono::Local;
+use log::{Level, Log, Metadata, Record};
+
+use crate::write_atomically;
+
+/// Number of recent log lines that are kept in memory for crash reports.
+const LOG_TAIL_LENGTH: usize = 200;
+/// Least severe log level that's kept for crash reports, whether or not it's printed to stderr.
+const LOG_TAIL_LEVEL: Level = Level::Info;
+
+// Most recent log lines, oldest first.
+static RECENT_LOG_LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Log to stderr (if you run with `RUST_LOG=debug`) and save a crash report if the GUI panics.
+///
+/// Reports are written to FolSum's folder in the user's local app data directory, like
+/// `~/.local/share/FolSum/crash_reports/` on Linux, so they can be offered to the user on next launch.
+pub fn install_crash_reporter() {
+    let stderr_logger = env_logger::Builder::from_default_env().build();
+    log::set_max_level(stderr_logger.filter().max(LOG_TAIL_LEVEL.to_level_filter()));
+    // Keep going if a logger was already set up because crash reports are still useful without a log tail.
+    let _set_result = log::set_boxed_logger(Box::new(TailLogger { stderr_logger }));
+
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |panic_info| {
+        // Only report panics on the GUI's thread. Workers' panics are already shown in the GUI as failures.
+        if thread::current().name() == Some("main") {
+            match write_crash_report(&panic_info.to_string()) {
+                Some(report_path) => eprintln!("Saved crash report to {}", report_path.display()),
+                None => eprintln!("Failed to save crash report"),
+            }
+        }
+        // Still print the panic to stderr like usual.
+        default_hook(panic_info);
+    }));
+}
+
+/// Most recent crash report, if FolSum has ever crashed.
+pub fn latest_crash_report() -> Option<PathBuf> {
+    fs::read_dir(crash_reports_directory()?)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|report_path| {
+            report_path
+                .file_name()
+                .map(|filename| filename.to_string_lossy())
+                .map_or(false, |filename| {
+                    filename.starts_with("crash_") && filename.ends_with(".txt")
+                })
+        })
+        // Report names start with their timestamp, so the alphabetically last one is the newest.
+        .max()
+}
+
+/// Recent log lines, oldest first, like the ones that go into crash reports.
+pub fn recent_log_lines() -> Vec<String> {
+    RECENT_LOG_LINES
+        .lock()
+        .map(|recent_log_lines| recent_log_lines.clone())
+        .unwrap_or_default()
+}
+
+// Folder that crash reports are saved in.
+fn crash_reports_directory() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|data_directory| data_directory.join("FolSum/crash_reports"))
+}
+
+fn write_crash_report(panic_description: &str) -> Option<PathBuf> {
+    let reports_directory: PathBuf = crash_reports_directory()?;
+    fs::create_dir_all(&reports_directory).ok()?;
+    let crash_time = Local::now();
+    let report_path: PathBuf = reports_directory.join(format!(
+        "crash_{}.txt",
+        crash_time.format("%Y-%m-%d_%H-%M-%S")
+    ));
+    // Don't wait on the log if the panic happened while it was locked, or the hook would deadlock.
+    let log_tail: String = match RECENT_LOG_LINES.try_lock() {
+        Ok(recent_log_lines) => recent_log_lines.join("\n"),
+        Err(_) => String::from("(log was unavailable)"),
+    };
+    let crash_report = format!(
+        "FolSum crash report\n\
+         Version: {}\n\
+         OS: {} ({})\n\
+         Time: {}\n\n\
+         Panic:\n{}\n\n\
+         Backtrace:\n{}\n\n\
+         Recent log lines:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        env::consts::OS,
+        env::consts::ARCH,
+        crash_time.to_rfc3339(),
+        panic_description,
+        // Capture a backtrace even if `RUST_BACKTRACE` isn't set because users won't know to set it.
+        Backtrace::force_capture(),
+        log_tail,
+    );
+    write_atomically(&report_path, crash_report.as_bytes()).ok()?;
+    Some(report_path)
+}
+
+// Pass log records on to `env_logger` and remember recent ones for crash reports.
+struct TailLogger {
+    stderr_logger: env_logger::Logger,
+}
+
+impl Log for TailLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= LOG_TAIL_LEVEL || self.stderr_logger.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if record.level() <= LOG_TAIL_LEVEL {
+            if let Ok(mut recent_log_lines) = RECENT_LOG_LINES.lock() {
+                recent_log_lines.push(format!(
+                    "{} {} {}: {}",
+                    Local::now().format("%H:%M:%S%.3f"),
+                    record.level(),
+                    record.target(),
+                    record.args()
+                ));
+                // Forget the oldest line once there are too many.
+                if recent_log_lines.len() > LOG_TAIL_LENGTH {
+                    recent_log_lines.remove(0);
+                }
+            }
+        }
+        if self.stderr_logger.matches(record) {
+            self.stderr_logger.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.stderr_logger.flush();
+    }
+}
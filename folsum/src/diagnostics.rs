@@ -0,0 +1,64 @@
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+use std::fs;
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+use std::io::{self, Write};
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+use std::path::Path;
+
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+use zip::{write::FileOptions, ZipWriter};
+
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+use crate::{latest_crash_report, recent_log_lines, write_atomically_with};
+
+/// Version of FolSum, from `Cargo.toml`.
+pub const FOLSUM_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Short hash of the commit that FolSum was built from, or `unknown` if it wasn't built from a Git checkout.
+pub const GIT_COMMIT: &str = env!("FOLSUM_GIT_COMMIT");
+/// Day that FolSum was built, like `2023-10-31`.
+pub const BUILD_DATE: &str = env!("FOLSUM_BUILD_DATE");
+
+/// Describe this build of FolSum and the system that it's running on, for bug reports.
+pub fn build_description() -> String {
+    format!(
+        "FolSum {} (commit {}, built {})\nOS: {} ({})",
+        FOLSUM_VERSION,
+        GIT_COMMIT,
+        BUILD_DATE,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    )
+}
+
+/// Zip up everything that's useful for a bug report into one file that can be attached to it.
+///
+/// The bundle has the build description, the user's settings, the last summarization's summary, recent log
+/// lines, and the latest crash report (if there is one).
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+pub fn save_diagnostics_bundle(
+    bundle_path: &Path,
+    settings: &str,
+    last_summary: &str,
+) -> Result<(), &'static str> {
+    let mut bundle_entries: Vec<(&str, String)> = vec![
+        ("about.txt", build_description()),
+        ("settings.ron", settings.to_string()),
+        ("last_summary.txt", last_summary.to_string()),
+        ("recent_log.txt", recent_log_lines().join("\n")),
+    ];
+    if let Some(crash_report) =
+        latest_crash_report().and_then(|report_path| fs::read_to_string(report_path).ok())
+    {
+        bundle_entries.push(("latest_crash_report.txt", crash_report));
+    }
+    write_atomically_with(bundle_path, |bundle_file| {
+        let mut bundle = ZipWriter::new(bundle_file);
+        for (entry_name, entry_contents) in bundle_entries {
+            bundle.start_file(entry_name, FileOptions::default())?;
+            bundle.write_all(entry_contents.as_bytes())?;
+        }
+        bundle.finish()?;
+        Ok(())
+    })
+    .map_err(|_: io::Error| "Couldn't write the diagnostics bundle")
+}
@@ -0,0 +1,106 @@
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+use web_time::{Duration, Instant};
+
+use crate::SummarizationOptions;
+
+/// Number of files that's worth a warning before summarizing, because counting them takes a long time.
+pub const ENORMOUS_FILE_COUNT: u64 = 1_000_000;
+/// Total size that's worth a warning before summarizing (1 TiB), because hashing it takes a long time.
+pub const ENORMOUS_TOTAL_BYTES: u64 = 1024 * 1024 * 1024 * 1024;
+/// How long the estimate's allowed to take before it gives up and reports what it's found so far.
+pub const ESTIMATE_TIME_BUDGET: Duration = Duration::from_secs(2);
+
+/// Rough count of the files in a directory and their total size, taken before summarizing it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DirectoryEstimate {
+    pub file_count: u64,
+    pub total_bytes: u64,
+    // Whether the whole directory was counted. If not, then it has at least this many files.
+    pub finished: bool,
+}
+
+impl DirectoryEstimate {
+    /// Check if the directory's big enough that summarizing it could tie up the computer for a long time.
+    ///
+    /// Directories that can't even be counted within the time budget are assumed to be enormous.
+    pub fn is_enormous(&self) -> bool {
+        !self.finished
+            || self.file_count >= ENORMOUS_FILE_COUNT
+            || self.total_bytes >= ENORMOUS_TOTAL_BYTES
+    }
+}
+
+/// Quickly count the files in a directory and add up their sizes, stopping early if it takes too long.
+///
+/// Only file metadata is read, so this is much faster than summarizing. The user's summarization options are
+/// respected so the estimate covers the same files that the summarization would.
+pub fn estimate_directory(
+    directory: &Path,
+    summarization_options: &SummarizationOptions,
+    time_budget: Duration,
+) -> DirectoryEstimate {
+    let estimate_start = Instant::now();
+    let mut directory_walker = WalkDir::new(directory)
+        .min_depth(1)
+        .same_file_system(summarization_options.same_file_system);
+    if let Some(max_depth) = summarization_options.max_depth {
+        directory_walker = directory_walker.max_depth(max_depth);
+    }
+    let mut directory_estimate = DirectoryEstimate::default();
+    for entry in directory_walker
+        .into_iter()
+        .filter_entry(|entry| {
+            !summarization_options
+                .ignore_presets
+                .ignores(entry.file_name())
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| !entry.file_type().is_dir())
+    {
+        directory_estimate.file_count += 1;
+        directory_estimate.total_bytes += entry.metadata().map_or(0, |metadata| metadata.len());
+        // Checking the clock for every file would slow down the count, so only check every so often.
+        if directory_estimate.file_count % 1000 == 0 && estimate_start.elapsed() > time_budget {
+            return directory_estimate;
+        }
+    }
+    directory_estimate.finished = true;
+    directory_estimate
+}
+
+/// Check if a directory is a filesystem root or holds the operating system, like `/`, `C:\`, or `C:\Windows`.
+///
+/// Summarizing these locks up the computer for a long time and rarely is what the user meant to do.
+pub fn is_system_directory(directory: &Path) -> bool {
+    // Resolve `..` and symlinks so `/usr/../` is recognized as `/`.
+    let directory: PathBuf = directory
+        .canonicalize()
+        .unwrap_or_else(|_| directory.to_path_buf());
+    // Roots of drives and filesystems don't have parents.
+    if directory.parent().is_none() {
+        return true;
+    }
+    #[cfg(windows)]
+    let system_directories: [&str; 4] = [
+        "C:\\Windows",
+        "C:\\Program Files",
+        "C:\\Program Files (x86)",
+        "C:\\ProgramData",
+    ];
+    #[cfg(target_os = "macos")]
+    let system_directories: [&str; 5] = ["/System", "/Library", "/usr", "/bin", "/private"];
+    #[cfg(not(any(windows, target_os = "macos")))]
+    let system_directories: [&str; 8] = [
+        "/usr", "/bin", "/etc", "/lib", "/proc", "/sys", "/dev", "/var",
+    ];
+    // Windows paths are case-insensitive, and canonical ones start with `\\?\`.
+    let directory_name: String = directory
+        .to_string_lossy()
+        .trim_start_matches(r"\\?\")
+        .to_lowercase();
+    system_directories
+        .iter()
+        .any(|system_directory| directory_name == system_directory.to_lowercase())
+}
@@ -0,0 +1,69 @@
+use std::fs::Metadata;
+use std::path::Path;
+
+/// How much room the summarized files take up on disk, as opposed to how long they are.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageTotals {
+    // Bytes that the filesystem actually set aside for the files.
+    pub allocated_bytes: u64,
+    // Number of files with holes in them (like virtual machine disks) that take up less room than their length.
+    pub sparse_files: u32,
+}
+
+/// Find how many bytes a file takes up on disk, which is less than its length if it's sparse.
+#[cfg(unix)]
+pub fn allocated_size(_file_path: &Path, file_metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    // Unix counts blocks in 512-byte units, whatever the filesystem's real block size is.
+    Some(file_metadata.blocks() * 512)
+}
+
+/// Find how many bytes a file takes up on disk, which is less than its length if it's sparse or compressed.
+#[cfg(windows)]
+pub fn allocated_size(file_path: &Path, _file_metadata: &Metadata) -> Option<u64> {
+    use std::iter;
+    use std::os::windows::ffi::OsStrExt;
+
+    use windows_sys::Win32::Foundation::{GetLastError, NO_ERROR};
+    use windows_sys::Win32::Storage::FileSystem::{GetCompressedFileSizeW, INVALID_FILE_SIZE};
+
+    let wide_path: Vec<u16> = file_path
+        .as_os_str()
+        .encode_wide()
+        .chain(iter::once(0))
+        .collect();
+    let mut size_high: u32 = 0;
+    // Safety: `wide_path` is null-terminated and `size_high` outlives the call.
+    let size_low: u32 = unsafe { GetCompressedFileSizeW(wide_path.as_ptr(), &mut size_high) };
+    // `INVALID_FILE_SIZE` is also a real low half of some sizes, so only the last error tells failures apart.
+    // Safety: `GetLastError` only reads this thread's last error code.
+    if size_low == INVALID_FILE_SIZE && unsafe { GetLastError() } != NO_ERROR {
+        return None;
+    }
+    Some((u64::from(size_high) << 32) | u64::from(size_low))
+}
+
+/// Assume that files take up their whole length on platforms that don't say otherwise, like the web.
+#[cfg(not(any(unix, windows)))]
+pub fn allocated_size(_file_path: &Path, _file_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+/// Check if a file has holes that weren't written to disk, like a virtual machine's disk image.
+#[cfg(windows)]
+pub fn is_sparse(file_metadata: &Metadata, _allocated_size: u64) -> bool {
+    use std::os::windows::fs::MetadataExt;
+
+    use windows_sys::Win32::Storage::FileSystem::FILE_ATTRIBUTE_SPARSE_FILE;
+    // Windows marks sparse files as such, so there's no need to guess from their size.
+    file_metadata.file_attributes() & FILE_ATTRIBUTE_SPARSE_FILE != 0
+}
+
+/// Check if a file has holes that weren't written to disk, like a virtual machine's disk image.
+///
+/// Files that are at least a block shorter on disk than their length must have holes. Tiny files that the
+/// filesystem tucks in with its own records take up no blocks, so they're not counted.
+#[cfg(not(windows))]
+pub fn is_sparse(file_metadata: &Metadata, allocated_size: u64) -> bool {
+    file_metadata.len().saturating_sub(allocated_size) >= 4096
+}
@@ -1,48 +1,142 @@
 use std::collections::HashMap;
-#[cfg(not(target_arch = "wasm32"))]
-use std::fs::File;
-#[cfg(not(target_arch = "wasm32"))]
-use std::io::Write;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex, MutexGuard};
-use std::thread;
 
-use crate::sort_counts;
+use chrono::{DateTime, FixedOffset, SecondsFormat};
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::write_atomically;
+use crate::{
+    check_read_only, lock_shared, sort_counts, spawn_worker, WorkerStatus, PROJECT_FILE_NAME,
+};
+
+/// Details about who made an export and why, for chain of custody.
+#[derive(Clone, Debug, Default)]
+pub struct ExportMetadata {
+    // When the export was made, with the timezone's offset from UTC so the time can't be misread.
+    pub exported_at: Option<DateTime<FixedOffset>>,
+    // Name of the person who ran the summarization.
+    pub operator: String,
+    // Account on the computer that the export was made from, which can differ from who the operator says they are.
+    pub os_user: String,
+    // Case number that the export belongs to.
+    pub case_id: String,
+    // Free-text explanation of why the summarization was run.
+    pub note: String,
+    // Settings that the summarized directory's `.folsum.toml` pinned, like `max_depth = 3`.
+    pub pinned_settings: Vec<String>,
+    // Result of the hash algorithms' self-test, like `passed (MD5, SHA-1, SHA-256)`.
+    pub self_test: Option<String>,
+    // Summary of the latest audit of checksum files, if there was one, like `passed (0 critical, ...)`.
+    pub audit: Option<String>,
+}
+
+impl ExportMetadata {
+    /// Render the metadata as `#`-prefixed lines that go above the CSV's column headers.
+    ///
+    /// Empty fields are left out, so exports without metadata start directly with the column headers.
+    pub fn header_lines(&self) -> String {
+        let mut header_lines = String::new();
+        if let Some(exported_at) = &self.exported_at {
+            header_lines.push_str(&format!(
+                "# Exported: {}\n",
+                exported_at.to_rfc3339_opts(SecondsFormat::Secs, false)
+            ));
+        }
+        for (field_name, field_value) in [
+            ("Operator", &self.operator),
+            ("OS User", &self.os_user),
+            ("Case ID", &self.case_id),
+            ("Note", &self.note),
+        ] {
+            // Give each line of multi-line notes its own prefix so none of it is mistaken for CSV rows.
+            for value_line in field_value.trim().lines() {
+                header_lines.push_str(&format!("# {field_name}: {value_line}\n"));
+            }
+        }
+        // Show that the options came from the directory, not the operator, so the export can be reproduced.
+        for pinned_setting in &self.pinned_settings {
+            header_lines.push_str(&format!(
+                "# Pinned by {PROJECT_FILE_NAME}: {pinned_setting}\n"
+            ));
+        }
+        // Show that the hashes were made by algorithms that gave known answers, for tool validation.
+        if let Some(self_test) = &self.self_test {
+            header_lines.push_str(&format!("# Tool self-test: {self_test}\n"));
+        }
+        if let Some(audit) = &self.audit {
+            header_lines.push_str(&format!("# Checksum audit: {audit}\n"));
+        }
+        header_lines
+    }
+}
+
+/// Write extension counts to a CSV file in a separate thread.
+///
+/// When `read_only_root` is given, the export is refused if it would be written anywhere inside of that
+/// directory, so audited folders are never modified.
 pub fn export_csv(
     export_file: &Arc<Mutex<Option<PathBuf>>>,
     extension_counts: &Arc<Mutex<HashMap<String, u32>>>,
+    read_only_root: &Option<PathBuf>,
+    export_metadata: &ExportMetadata,
+    export_status: &Arc<Mutex<WorkerStatus>>,
 ) -> Result<(), &'static str> {
+    // If read-only mode is on, then make sure that the export won't be written into the protected directory.
+    if let Some(export_path) = &*lock_shared(export_file) {
+        check_read_only(export_path, read_only_root)?;
+    }
     // Copy extension counts so we can access them in a separate thread that's dedicated to this CSV dump.
     let extension_counts_copy: Arc<Mutex<HashMap<String, u32>>> = extension_counts.clone();
     // Copy the export file path's `Arc` so we can access it in a separate thread for CSV dumping.
     let export_file: Arc<Mutex<Option<PathBuf>>> = export_file.clone();
-    thread::spawn(move || {
-        // Make a place to put extension counts that'll be written to the CSV file and include column headers.
-        let mut csv_rows = String::from("File Extension, Occurrences\n");
+    // Render operator and note metadata now so the export thread doesn't need to borrow it.
+    let metadata_lines: String = export_metadata.header_lines();
+    spawn_worker(export_status, move || {
+        // Make a place to put extension counts that'll be written to the CSV file, starting with any metadata.
+        let mut csv_rows = metadata_lines;
+        // Add column headers after the metadata.
+        csv_rows.push_str("File Extension, Occurrences\n");
         // Lock extension counts so we can read them into CSV format.
         let locked_extension_counts: MutexGuard<'_, HashMap<String, u32>> =
-            extension_counts_copy.lock().unwrap();
-        // Sort extension counts by the number of occurrences (descending), then alphabetically (for extensions with the same count).
-        let sorted_counts: Vec<(&String, &u32)> = sort_counts(&locked_extension_counts);
-        for (extension_type, extension_count) in sorted_counts.iter() {
-            // Ensure that there are no commas or newlines in this extension's name that would disrupt the output format.
-            assert!(!extension_type.contains('\n') && !extension_type.contains(','));
-            let csv_row = format!("{extension_type},{extension_count}\n");
-            csv_rows.push_str(&csv_row)
+            lock_shared(&extension_counts_copy);
+        for csv_row in extension_rows(&locked_extension_counts) {
+            csv_rows.push_str(&csv_row);
+            csv_rows.push('\n');
         }
         // Lock the export file path so we can use it to create the CSV dump.
-        let locked_export_file = export_file.lock().unwrap();
+        let locked_export_file = lock_shared(&export_file);
         let export_filename = locked_export_file
             .as_ref()
-            .expect("No path for export file was specified");
-        // Create a CSV file to write the extension types and their counts to, overwriting it if it already exists.
-        let mut csv_export =
-            File::create(export_filename).expect("Failed to create CSV export file");
-        // Write the CSV's content to the export file.
-        csv_export
-            .write_all(csv_rows.as_bytes())
-            .expect("Failed to write contents to CSV export file")
+            .ok_or("No path for export file was specified")?;
+        // Write the extension types and their counts to the CSV file, replacing it if it already exists.
+        write_atomically(export_filename, csv_rows.as_bytes())
+            .map_err(|write_error| format!("Failed to create CSV export file: {write_error}"))
     });
     Ok(())
 }
+
+/// Render extension counts as CSV rows (without line endings), in the order that they're exported.
+///
+/// Rows are sorted by the number of occurrences (descending), then alphabetically for extensions with the same count.
+pub fn extension_rows(extension_counts: &HashMap<String, u32>) -> Vec<String> {
+    sort_counts(extension_counts)
+        .iter()
+        // Quote extensions with commas, quotes, or newlines in them so they don't break the row apart.
+        .map(|(extension_type, extension_count)| {
+            format!("{},{extension_count}", csv_field(extension_type))
+        })
+        .collect()
+}
+
+/// Quote a CSV field the way RFC 4180 says to, if it has commas, quotes, or line breaks in it.
+///
+/// Spreadsheets and CSV libraries read quoted fields back as the original text, so filenames like
+/// `report, final.pdf` survive the round trip.
+pub fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        // Double up quotes inside of the field so they aren't mistaken for the closing quote.
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
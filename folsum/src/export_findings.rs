@@ -0,0 +1,77 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::{Arc, Mutex};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    check_read_only, lock_shared, spawn_worker, write_atomically, ExportMetadata, WorkerStatus,
+};
+use crate::{csv_field, ChecksumFinding, FindingDecisions, SeverityRules};
+
+/// Render checksum findings as CSV rows (without line endings) under
+/// `Listed File, Checksum File, Outcome, Severity, Listed Hash, Actual Hash, Decision` headers.
+///
+/// Rows stay in the order that they're given in. Decisions are recorded next to findings instead of leaving the
+/// decided ones out, so whoever reads the export can see what was waved through.
+pub fn findings_rows(
+    findings: &[ChecksumFinding],
+    decisions: &FindingDecisions,
+    severity_rules: &SeverityRules,
+) -> Vec<String> {
+    findings
+        .iter()
+        .map(|finding| {
+            format!(
+                "{},{},{},{},{},{},{}",
+                csv_field(&finding.listed_file.display().to_string()),
+                csv_field(&finding.checksum_file.display().to_string()),
+                finding.outcome.name(),
+                severity_rules.severity(finding).name(),
+                finding.listed_hash,
+                finding.details.actual_hash.as_deref().unwrap_or_default(),
+                decisions.describe(finding)
+            )
+        })
+        .collect()
+}
+
+/// Write checksum findings (usually the ones that are selected in the findings pane) to a CSV file in a separate
+/// thread.
+///
+/// Like `export_csv`, exports are refused inside of `read_only_root` when it's given.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export_findings(
+    export_file: &Arc<Mutex<Option<PathBuf>>>,
+    findings: &[ChecksumFinding],
+    decisions: &FindingDecisions,
+    severity_rules: &SeverityRules,
+    read_only_root: &Option<PathBuf>,
+    export_metadata: &ExportMetadata,
+    export_status: &Arc<Mutex<WorkerStatus>>,
+) -> Result<(), &'static str> {
+    let export_path: PathBuf = lock_shared(export_file)
+        .clone()
+        .ok_or("No path for export file was specified")?;
+    // If read-only mode is on, then make sure that the export won't be written into the protected directory.
+    check_read_only(&export_path, read_only_root)?;
+    if findings.is_empty() {
+        return Err("Select findings before exporting them");
+    }
+    // Render the rows now so the export thread doesn't need to borrow the findings.
+    let exported_rows: Vec<String> = findings_rows(findings, decisions, severity_rules);
+    let metadata_lines: String = export_metadata.header_lines();
+    spawn_worker(export_status, move || {
+        let mut csv_rows = metadata_lines;
+        csv_rows.push_str(
+            "Listed File, Checksum File, Outcome, Severity, Listed Hash, Actual Hash, Decision\n",
+        );
+        for findings_row in exported_rows {
+            csv_rows.push_str(&findings_row);
+            csv_rows.push('\n');
+        }
+        write_atomically(&export_path, csv_rows.as_bytes())
+            .map_err(|write_error| format!("Failed to write findings export file: {write_error}"))
+    });
+    Ok(())
+}
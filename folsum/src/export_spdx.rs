@@ -0,0 +1,258 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::{
+    check_read_only, hash_file_into, lock_shared, natural_cmp, spawn_worker, stop_requested,
+    write_atomically, ExportMetadata, ReadBuffer, SummarizationOptions, WorkerStatus,
+    FOLSUM_VERSION, STOPPED_REASON,
+};
+
+/// One file's entry in an SPDX document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpdxFile {
+    // Path relative to the summarized directory, with forward slashes like SPDX expects.
+    pub relative_path: String,
+    pub file_size: u64,
+    pub sha1: String,
+    pub sha256: String,
+    pub md5: String,
+}
+
+/// List every file in the chosen directory, with its hashes and size, in an SPDX 2.3 (tag-value) document.
+///
+/// SPDX is the format that SBOM tools ingest. The directory becomes one package that contains all of the
+/// files. Like `export_csv`, this runs in a separate thread and respects read-only mode.
+pub fn export_spdx(
+    export_file: &Arc<Mutex<Option<PathBuf>>>,
+    summarization_path: &Arc<Mutex<Option<PathBuf>>>,
+    read_only_root: &Option<PathBuf>,
+    export_metadata: &ExportMetadata,
+    summarization_options: &SummarizationOptions,
+    export_status: &Arc<Mutex<WorkerStatus>>,
+) -> Result<(), &'static str> {
+    let export_path: PathBuf = lock_shared(export_file)
+        .clone()
+        .ok_or("No path for export file was specified")?;
+    let summarized_directory: PathBuf = lock_shared(summarization_path)
+        .clone()
+        .ok_or("Choose a directory before exporting an SPDX document")?;
+    // If read-only mode is on, then make sure that the export won't be written into the protected directory.
+    check_read_only(&export_path, read_only_root)?;
+    let export_metadata: ExportMetadata = export_metadata.clone();
+    let options_copy: SummarizationOptions = summarization_options.clone();
+    spawn_worker(export_status, move || {
+        let mut spdx_files: Vec<SpdxFile> = Vec::new();
+        let mut unreadable_files: Vec<String> = Vec::new();
+        let mut read_buffer = ReadBuffer::new(&options_copy.read_buffer_limits);
+        // Don't list the document in itself if it's being saved inside of the directory.
+        for (file_path, relative_path) in spdx_file_paths(&summarized_directory, &options_copy)
+            .filter(|(file_path, _)| *file_path != export_path)
+        {
+            // Leave out the document altogether if FolSum's closing, instead of writing one that's missing files.
+            if stop_requested() {
+                return Err(String::from(STOPPED_REASON));
+            }
+            // SPDX requires a SHA-1 for every file, so files that can't be read are left out and named instead, along
+            // with why, so locked files can be told apart from unreadable ones.
+            match spdx_file(&file_path, relative_path.clone(), &mut read_buffer) {
+                Ok(spdx_file) => spdx_files.push(spdx_file),
+                Err(STOPPED_REASON) => return Err(String::from(STOPPED_REASON)),
+                Err(hash_error) => unreadable_files.push(format!("{relative_path} ({hash_error})")),
+            }
+        }
+        let spdx_document: String = spdx_document(
+            &summarized_directory,
+            &spdx_files,
+            &unreadable_files,
+            &export_metadata,
+            &Utc::now(),
+        );
+        write_atomically(&export_path, spdx_document.as_bytes())
+            .map_err(|write_error| format!("Failed to write SPDX export file: {write_error}"))
+    });
+    Ok(())
+}
+
+/// List the files that an SPDX document of the directory describes, with their SPDX-style relative paths.
+///
+/// Files are found lazily, so previews can take the first few without walking the whole directory.
+pub fn spdx_file_paths<'a>(
+    summarized_directory: &'a Path,
+    summarization_options: &'a SummarizationOptions,
+) -> impl Iterator<Item = (PathBuf, String)> + 'a {
+    // Walk the directory the same way summarizations do, so the document lists the files that were counted.
+    let mut directory_walker = WalkDir::new(summarized_directory)
+        .min_depth(1)
+        .same_file_system(summarization_options.same_file_system)
+        // Sort by name so the same directory always makes the same document, with `IMG_2` before `IMG_10`.
+        .sort_by(|a_entry, b_entry| {
+            natural_cmp(
+                &a_entry.file_name().to_string_lossy(),
+                &b_entry.file_name().to_string_lossy(),
+            )
+        });
+    if let Some(max_depth) = summarization_options.max_depth {
+        directory_walker = directory_walker.max_depth(max_depth);
+    }
+    directory_walker
+        .into_iter()
+        .filter_entry(|entry| {
+            !summarization_options
+                .ignore_presets
+                .ignores(entry.file_name())
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| !entry.file_type().is_dir())
+        .map(move |entry| {
+            let relative_path: String = spdx_path(
+                entry
+                    .path()
+                    .strip_prefix(summarized_directory)
+                    .unwrap_or(entry.path()),
+            );
+            (entry.into_path(), relative_path)
+        })
+}
+
+/// Hash a file with the algorithms that SPDX tools look for, or say why it couldn't be read.
+fn spdx_file(
+    file_path: &Path,
+    relative_path: String,
+    read_buffer: &mut ReadBuffer,
+) -> Result<SpdxFile, &'static str> {
+    let mut sha1_hasher = Sha1::new();
+    let mut sha256_hasher = Sha256::new();
+    let mut md5_hasher = Md5::new();
+    let file_size: u64 = hash_file_into(
+        file_path,
+        read_buffer,
+        &mut [&mut sha1_hasher, &mut sha256_hasher, &mut md5_hasher],
+    )?;
+    Ok(SpdxFile {
+        relative_path,
+        file_size,
+        sha1: format!("{:x}", sha1_hasher.finalize()),
+        sha256: format!("{:x}", sha256_hasher.finalize()),
+        md5: format!("{:x}", md5_hasher.finalize()),
+    })
+}
+
+/// Write a relative path the way SPDX file names look, like `./photos/IMG_0001.jpg`.
+fn spdx_path(relative_path: &Path) -> String {
+    let path_parts: Vec<String> = relative_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect();
+    format!("./{}", path_parts.join("/"))
+}
+
+/// Render an SPDX 2.3 tag-value document that describes the summarized directory as a package of files.
+pub fn spdx_document(
+    summarized_directory: &Path,
+    spdx_files: &[SpdxFile],
+    unreadable_files: &[String],
+    export_metadata: &ExportMetadata,
+    created: &DateTime<Utc>,
+) -> String {
+    let directory_name: String = summarized_directory
+        .file_name()
+        .map(|directory_name| directory_name.to_string_lossy().to_string())
+        .unwrap_or_else(|| summarized_directory.display().to_string());
+    // Namespaces have to be unique for each document, so include when this one was made.
+    let namespace_name: String = directory_name
+        .chars()
+        .map(|character| match character.is_ascii_alphanumeric() {
+            true => character,
+            false => '-',
+        })
+        .collect();
+    let mut document = format!(
+        "SPDXVersion: SPDX-2.3\n\
+         DataLicense: CC0-1.0\n\
+         SPDXID: SPDXRef-DOCUMENT\n\
+         DocumentName: {directory_name}\n\
+         DocumentNamespace: https://spdx.org/spdxdocs/folsum-{namespace_name}-{}\n\
+         Creator: Tool: FolSum-{FOLSUM_VERSION}\n",
+        created.format("%Y%m%dT%H%M%S%.fZ")
+    );
+    if !export_metadata.operator.trim().is_empty() {
+        document.push_str(&format!(
+            "Creator: Person: {}\n",
+            export_metadata.operator.trim()
+        ));
+    }
+    document.push_str(&format!(
+        "Created: {}\n",
+        created.format("%Y-%m-%dT%H:%M:%SZ")
+    ));
+    // Keep the rest of the export's metadata, like in CSV headers, in the document's comment.
+    let mut document_comment: String = export_metadata
+        .header_lines()
+        .lines()
+        .map(|header_line| format!("{}\n", header_line.trim_start_matches("# ")))
+        .collect();
+    if !unreadable_files.is_empty() {
+        document_comment.push_str("Left out because they couldn't be read:\n");
+        for unreadable_file in unreadable_files {
+            document_comment.push_str(&format!("{unreadable_file}\n"));
+        }
+    }
+    if !document_comment.is_empty() {
+        document.push_str(&format!(
+            "DocumentComment: <text>{}</text>\n",
+            document_comment.trim_end()
+        ));
+    }
+
+    // Describe the directory as a package so the files belong to something, like SBOM tools expect.
+    document.push_str(&format!(
+        "\nPackageName: {directory_name}\n\
+         SPDXID: SPDXRef-Package\n\
+         PackageDownloadLocation: NOASSERTION\n\
+         FilesAnalyzed: true\n\
+         PackageVerificationCode: {}\n\
+         PackageLicenseConcluded: NOASSERTION\n\
+         PackageLicenseDeclared: NOASSERTION\n\
+         PackageCopyrightText: NOASSERTION\n\
+         Relationship: SPDXRef-DOCUMENT DESCRIBES SPDXRef-Package\n",
+        verification_code(spdx_files)
+    ));
+
+    for (file_index, spdx_file) in spdx_files.iter().enumerate() {
+        let file_number: usize = file_index + 1;
+        // SPDX doesn't have a field for file sizes, so they go in the comment.
+        document.push_str(&format!(
+            "\nFileName: {}\n\
+             SPDXID: SPDXRef-File-{file_number}\n\
+             FileChecksum: SHA1: {}\n\
+             FileChecksum: SHA256: {}\n\
+             FileChecksum: MD5: {}\n\
+             LicenseConcluded: NOASSERTION\n\
+             FileCopyrightText: NOASSERTION\n\
+             FileComment: <text>Size: {} bytes</text>\n\
+             Relationship: SPDXRef-Package CONTAINS SPDXRef-File-{file_number}\n",
+            spdx_file.relative_path,
+            spdx_file.sha1,
+            spdx_file.sha256,
+            spdx_file.md5,
+            spdx_file.file_size,
+        ));
+    }
+    document
+}
+
+/// Fingerprint a package's files the way SPDX does: the SHA-1 of all of their sorted SHA-1s, joined together.
+fn verification_code(spdx_files: &[SpdxFile]) -> String {
+    let mut file_hashes: Vec<&str> = spdx_files
+        .iter()
+        .map(|spdx_file| spdx_file.sha1.as_str())
+        .collect();
+    file_hashes.sort_unstable();
+    format!("{:x}", Sha1::digest(file_hashes.concat().as_bytes()))
+}
@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::{Arc, Mutex};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    check_read_only, lock_shared, spawn_worker, write_atomically, ExportMetadata, WorkerStatus,
+};
+use crate::{csv_field, natural_cmp, sort_counts};
+
+/// Number of the biggest files that are kept track of while summarizing.
+pub const LARGEST_FILE_COUNT: usize = 10;
+
+/// Statistics about a summarized directory's shape, gathered in the same pass that counts extensions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InventoryStats {
+    // Number of files at each depth, where files directly inside of the chosen directory are depth 1.
+    pub depth_counts: BTreeMap<usize, u32>,
+    // Biggest files that were found (relative to the chosen directory) with their sizes, from biggest to smallest.
+    pub largest_files: Vec<(PathBuf, u64)>,
+}
+
+impl InventoryStats {
+    /// Tally a summarized file under its depth, and remember it if it's one of the biggest so far.
+    pub fn record_file(&mut self, depth: usize, relative_path: &Path, file_size: u64) {
+        *self.depth_counts.entry(depth).or_insert(0) += 1;
+        // Skip the sort for the (usual) files that are smaller than every one that's already been kept.
+        let is_big_enough: bool = self.largest_files.len() < LARGEST_FILE_COUNT
+            || self
+                .largest_files
+                .last()
+                .map_or(true, |(_, smallest_size)| file_size > *smallest_size);
+        if is_big_enough {
+            self.largest_files
+                .push((relative_path.to_path_buf(), file_size));
+            // Break ties by path so the same directory always has the same largest files.
+            self.largest_files
+                .sort_by(|(a_path, a_size), (b_path, b_size)| {
+                    b_size.cmp(a_size).then_with(|| {
+                        natural_cmp(&a_path.to_string_lossy(), &b_path.to_string_lossy())
+                    })
+                });
+            self.largest_files.truncate(LARGEST_FILE_COUNT);
+        }
+    }
+}
+
+/// Render inventory statistics as CSV rows (without line endings) under `Section, Name, Files, Bytes` headers.
+///
+/// Extensions come first (in the same order as extension exports), then the number of files at each depth, then
+/// the largest files. Cells that don't apply to a section are left empty.
+pub fn stats_rows(
+    extension_counts: &HashMap<String, u32>,
+    extension_bytes: &HashMap<String, u64>,
+    inventory_stats: &InventoryStats,
+) -> Vec<String> {
+    let extension_rows =
+        sort_counts(extension_counts)
+            .into_iter()
+            .map(|(extension_name, extension_count)| {
+                format!(
+                    "extension,{},{},{}",
+                    csv_field(extension_name),
+                    extension_count,
+                    extension_bytes.get(extension_name).unwrap_or(&0)
+                )
+            });
+    let depth_rows = inventory_stats
+        .depth_counts
+        .iter()
+        .map(|(depth, file_count)| format!("depth,{depth},{file_count},"));
+    let largest_rows = inventory_stats
+        .largest_files
+        .iter()
+        .map(|(relative_path, file_size)| {
+            format!(
+                "largest file,{},,{}",
+                csv_field(&relative_path.display().to_string()),
+                file_size
+            )
+        });
+    extension_rows
+        .chain(depth_rows)
+        .chain(largest_rows)
+        .collect()
+}
+
+/// Write inventory statistics to a CSV file in a separate thread, as a companion to the extension counts export.
+///
+/// Like `export_csv`, exports are refused inside of `read_only_root` when it's given.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export_stats(
+    export_file: &Arc<Mutex<Option<PathBuf>>>,
+    extension_counts: &Arc<Mutex<HashMap<String, u32>>>,
+    extension_bytes: &Arc<Mutex<HashMap<String, u64>>>,
+    inventory_stats: &Arc<Mutex<InventoryStats>>,
+    read_only_root: &Option<PathBuf>,
+    export_metadata: &ExportMetadata,
+    export_status: &Arc<Mutex<WorkerStatus>>,
+) -> Result<(), &'static str> {
+    let export_path: PathBuf = lock_shared(export_file)
+        .clone()
+        .ok_or("No path for export file was specified")?;
+    // If read-only mode is on, then make sure that the export won't be written into the protected directory.
+    check_read_only(&export_path, read_only_root)?;
+    let extension_counts_copy = Arc::clone(extension_counts);
+    let extension_bytes_copy = Arc::clone(extension_bytes);
+    let inventory_stats_copy = Arc::clone(inventory_stats);
+    let metadata_lines: String = export_metadata.header_lines();
+    spawn_worker(export_status, move || {
+        let mut csv_rows = metadata_lines;
+        csv_rows.push_str("Section, Name, Files, Bytes\n");
+        for stats_row in stats_rows(
+            &lock_shared(&extension_counts_copy),
+            &lock_shared(&extension_bytes_copy),
+            &lock_shared(&inventory_stats_copy),
+        ) {
+            csv_rows.push_str(&stats_row);
+            csv_rows.push('\n');
+        }
+        write_atomically(&export_path, csv_rows.as_bytes())
+            .map_err(|write_error| format!("Failed to write statistics export file: {write_error}"))
+    });
+    Ok(())
+}
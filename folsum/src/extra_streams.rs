@@ -0,0 +1,128 @@
+#[cfg(windows)]
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+#[cfg(unix)]
+use crate::hash_bytes;
+#[cfg(windows)]
+use crate::hash_file;
+use crate::FileHashes;
+
+/// Data that's attached to a file outside of its main contents, where it's easy to miss.
+///
+/// These are alternate data streams on Windows (NTFS) and extended attributes on MacOS and Linux.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtraStream {
+    // File that the stream is attached to.
+    pub file: PathBuf,
+    // Name of the stream, like `Zone.Identifier` on Windows or `com.apple.quarantine` on MacOS.
+    pub stream_name: String,
+    // Size of the stream's contents, in bytes.
+    pub stream_size: u64,
+    // Hashes of the stream's contents, or `None` if they couldn't be read.
+    pub hashes: Option<FileHashes>,
+}
+
+/// Find the extended attributes that are attached to a file, along with the hashes of their values.
+#[cfg(unix)]
+pub fn find_extra_streams(file_path: &Path) -> Vec<ExtraStream> {
+    // Filesystems that don't support extended attributes can't have any, so there's nothing to report.
+    let attribute_names = match xattr::list(file_path) {
+        Ok(attribute_names) => attribute_names,
+        Err(_) => return Vec::new(),
+    };
+    attribute_names
+        .map(|attribute_name| {
+            let attribute_value: Option<Vec<u8>> =
+                xattr::get(file_path, &attribute_name).ok().flatten();
+            ExtraStream {
+                file: file_path.to_path_buf(),
+                stream_name: attribute_name.to_string_lossy().to_string(),
+                stream_size: attribute_value
+                    .as_ref()
+                    .map_or(0, |value| value.len() as u64),
+                hashes: attribute_value.map(|value| hash_bytes(&value)),
+            }
+        })
+        .collect()
+}
+
+/// Find the alternate data streams that are attached to a file, along with the hashes of their contents.
+#[cfg(windows)]
+pub fn find_extra_streams(file_path: &Path) -> Vec<ExtraStream> {
+    use std::iter;
+    use std::mem;
+    use std::os::windows::ffi::OsStrExt;
+
+    use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+    use windows_sys::Win32::Storage::FileSystem::{
+        FindClose, FindFirstStreamW, FindNextStreamW, FindStreamInfoStandard,
+        WIN32_FIND_STREAM_DATA,
+    };
+
+    let wide_path: Vec<u16> = file_path
+        .as_os_str()
+        .encode_wide()
+        .chain(iter::once(0))
+        .collect();
+    // Safety: `WIN32_FIND_STREAM_DATA` is plain data, so all zeroes is a valid (empty) value.
+    let mut stream_data: WIN32_FIND_STREAM_DATA = unsafe { mem::zeroed() };
+    // Safety: `wide_path` is null-terminated and `stream_data` is the struct that `FindStreamInfoStandard` fills.
+    let find_handle = unsafe {
+        FindFirstStreamW(
+            wide_path.as_ptr(),
+            FindStreamInfoStandard,
+            &mut stream_data as *mut WIN32_FIND_STREAM_DATA as *mut _,
+            0,
+        )
+    };
+    // Files on filesystems without streams (like FAT32) don't have any to find.
+    if find_handle == INVALID_HANDLE_VALUE {
+        return Vec::new();
+    }
+    let mut extra_streams: Vec<ExtraStream> = Vec::new();
+    loop {
+        // Stream names look like `:Zone.Identifier:$DATA`, and the file's main contents are `::$DATA`.
+        let name_length: usize = stream_data
+            .cStreamName
+            .iter()
+            .position(|character| *character == 0)
+            .unwrap_or(stream_data.cStreamName.len());
+        let raw_name: String = String::from_utf16_lossy(&stream_data.cStreamName[..name_length]);
+        let stream_name: &str = raw_name
+            .trim_start_matches(':')
+            .trim_end_matches("$DATA")
+            .trim_end_matches(':');
+        if !stream_name.is_empty() {
+            // Streams can be read like files at `<file path>:<stream name>`.
+            let mut stream_path = OsString::from(file_path.as_os_str());
+            stream_path.push(":");
+            stream_path.push(stream_name);
+            extra_streams.push(ExtraStream {
+                file: file_path.to_path_buf(),
+                stream_name: stream_name.to_string(),
+                stream_size: stream_data.StreamSize as u64,
+                hashes: hash_file(Path::new(&stream_path)).ok(),
+            });
+        }
+        // Safety: `find_handle` is still open and `stream_data` is the same kind of struct as before.
+        let found_another: bool = unsafe {
+            FindNextStreamW(
+                find_handle,
+                &mut stream_data as *mut WIN32_FIND_STREAM_DATA as *mut _,
+            )
+        } != 0;
+        if !found_another {
+            break;
+        }
+    }
+    // Safety: `find_handle` is open and isn't used after it's closed.
+    unsafe { FindClose(find_handle) };
+    extra_streams
+}
+
+/// Assume that files don't have extra streams on platforms that can't list them, like the web.
+#[cfg(not(any(unix, windows)))]
+pub fn find_extra_streams(_file_path: &Path) -> Vec<ExtraStream> {
+    Vec::new()
+}
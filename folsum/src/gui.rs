@@ -1,54 +1,511 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 #[cfg(not(target_arch = "wasm32"))]
-use chrono::{DateTime, Local};
+use chrono::{DateTime, FixedOffset, Local, Utc};
 use dirs::home_dir;
 #[cfg(not(target_arch = "wasm32"))]
 use egui_extras::{Column, TableBuilder};
+use fluent_bundle::FluentArgs;
+// Add `sorted_by()` to HashMap iterators for listing folders in natural order.
+use itertools::Itertools;
 #[cfg(not(target_arch = "wasm32"))]
-use rfd::FileDialog;
-#[cfg(not(target_arch = "wasm32"))]
-use web_time::SystemTime;
+use rfd::{FileDialog, MessageButtons, MessageDialog, MessageDialogResult, MessageLevel};
 use web_time::{Duration, Instant};
 
 use crate::export_csv;
-use crate::sort_counts;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::hash_file_in_background;
+use crate::i18n::{Language, Translations};
+use crate::lock_shared;
 use crate::summarize_directory;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::ExportMetadata;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::QuickHash;
+use crate::SummarizationOptions;
+use crate::SummarizationState;
+use crate::VerificationProgress;
+use crate::WorkerStatus;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{action_log_path, export_spdx, os_user_name, record_action};
+use crate::{
+    audit_verdict, extension_name, group_discrepancies, AuditReport, AuditVerdict, ContentVerdict,
+};
+use crate::{build_description, BUILD_DATE, FOLSUM_VERSION, GIT_COMMIT};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{create_export_path, next_versioned_path};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{discard_unfinished_writes, request_stop, running_workers, spawn_worker};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{estimate_directory, is_system_directory, DirectoryEstimate, ESTIMATE_TIME_BUDGET};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{export_findings, findings_rows};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{export_stats, extension_rows, spdx_file_paths, stats_rows};
+use crate::{find_profile, save_profile, starter_profiles, OptionsProfile};
+use crate::{format_bytes, format_count, format_duration, format_hash};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{latest_crash_report, open_path, reveal_path, save_diagnostics_bundle};
+use crate::{natural_cmp, sort_counts};
+use crate::{read_project_file, ProjectFile, PROJECT_FILE_NAME};
+use crate::{reverify_findings, FindingDecisions, DISCREPANCY_OUTCOMES};
+use crate::{run_self_test, SelfTest};
+#[cfg(feature = "rest-api")]
+use crate::{serve_results, ApiResults, ApiServer, ApiSettings};
+use crate::{ChecksumFinding, ChecksumOutcome, InventoryStats};
+use crate::{Severity, SeverityRules};
+use crate::{BIGGEST_READ_BUFFER_KIB, MOST_READERS_PER_DEVICE, SMALLEST_READ_BUFFER_KIB};
+use crate::{DEFAULT_DATE_FORMAT, DEFAULT_FILENAME_TEMPLATE, LEGACY_DATE_FORMAT};
+use crate::{FILE_IN_USE_ERROR, STOPPED_REASON};
+
+/// How often to redraw when nothing's running in the background and the user isn't doing anything.
+const IDLE_REPAINT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Kinds of exports that the user can write.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ExportKind {
+    // Every extension's count, as CSV.
+    AllExtensions,
+    // Findings that are selected in the findings pane, as CSV.
+    SelectedFindings,
+    // Counts of the extensions that are selected in the results table, as CSV.
+    SelectedExtensions,
+    // Every file with its hashes, as an SPDX document.
+    Spdx,
+    // Extension counts and sizes, how deep files are, and the largest files, as CSV.
+    Statistics,
+}
+
+/// Number of rows that export previews show, which is enough to spot the wrong directory or columns.
+#[cfg(not(target_arch = "wasm32"))]
+const PREVIEW_ROW_COUNT: usize = 20;
+
+/// Optional columns of the results table, which the user can show or hide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+enum TableColumn {
+    // Number of files that were found with the row's extension.
+    Occurrences,
+    // Total size of the files that were found with the row's extension.
+    Bytes,
+}
+
+impl TableColumn {
+    // Every optional column, in the order that they're shown in the results table.
+    const ALL: [TableColumn; 2] = [TableColumn::Occurrences, TableColumn::Bytes];
+
+    // ID of the translated column title that's shown in the table header and the column chooser.
+    fn heading_id(&self) -> &'static str {
+        match self {
+            TableColumn::Occurrences => "column-occurrences",
+            TableColumn::Bytes => "column-bytes",
+        }
+    }
+
+    // Optional columns that are shown in the results table, in order.
+    fn shown(hidden_columns: &[TableColumn]) -> Vec<TableColumn> {
+        TableColumn::ALL
+            .into_iter()
+            .filter(|table_column| !hidden_columns.contains(table_column))
+            .collect()
+    }
+
+    // Unformatted value of this column for a row of the results table, for copying into spreadsheets.
+    fn cell_value(&self, times_seen: &u32, total_bytes: u64) -> String {
+        match self {
+            TableColumn::Occurrences => times_seen.to_string(),
+            TableColumn::Bytes => total_bytes.to_string(),
+        }
+    }
+
+    // Text that's shown in this column for a row of the results table.
+    fn cell_text(&self, times_seen: &u32, total_bytes: u64, language: Language) -> String {
+        match self {
+            TableColumn::Occurrences => format_count(u64::from(*times_seen), language),
+            TableColumn::Bytes => format_bytes(total_bytes, language),
+        }
+    }
+}
+
+/// Color schemes that are applied on top of the light or dark theme.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+enum ThemePreset {
+    // egui's own colors.
+    #[default]
+    Default,
+    // Pure black and white text with strong outlines and row stripes.
+    HighContrast,
+    // Status colors from the Okabe-Ito palette, which stay distinct for people with color vision deficiencies.
+    ColorblindSafe,
+}
+
+impl ThemePreset {
+    // Every preset, in the order that they're shown in the settings window.
+    const ALL: [ThemePreset; 3] = [
+        ThemePreset::Default,
+        ThemePreset::HighContrast,
+        ThemePreset::ColorblindSafe,
+    ];
+
+    // ID of the translated preset name that's shown in the settings window.
+    fn name_id(&self) -> &'static str {
+        match self {
+            ThemePreset::Default => "theme-default",
+            ThemePreset::HighContrast => "theme-high-contrast",
+            ThemePreset::ColorblindSafe => "theme-colorblind-safe",
+        }
+    }
+
+    // Recolor egui's light or dark theme with this preset's colors.
+    fn apply(&self, visuals: &mut egui::Visuals) {
+        match self {
+            ThemePreset::Default => {}
+            ThemePreset::HighContrast => {
+                let (text_color, background_color) = match visuals.dark_mode {
+                    true => (egui::Color32::WHITE, egui::Color32::BLACK),
+                    false => (egui::Color32::BLACK, egui::Color32::WHITE),
+                };
+                visuals.override_text_color = Some(text_color);
+                visuals.panel_fill = background_color;
+                visuals.window_fill = background_color;
+                visuals.extreme_bg_color = background_color;
+                // Make striped rows easy to tell apart.
+                visuals.faint_bg_color = match visuals.dark_mode {
+                    true => egui::Color32::from_gray(45),
+                    false => egui::Color32::from_gray(210),
+                };
+                // Outline every widget so its edges are visible without relying on shading.
+                let outline = egui::Stroke::new(1.5, text_color);
+                visuals.widgets.noninteractive.bg_stroke = outline;
+                visuals.widgets.inactive.bg_stroke = outline;
+                visuals.widgets.hovered.bg_stroke = egui::Stroke::new(2.5, text_color);
+                visuals.widgets.active.bg_stroke = egui::Stroke::new(2.5, text_color);
+                visuals.error_fg_color = match visuals.dark_mode {
+                    true => egui::Color32::from_rgb(255, 110, 110),
+                    false => egui::Color32::from_rgb(170, 0, 0),
+                };
+            }
+            ThemePreset::ColorblindSafe => {
+                // Orange and vermillion for problems, blue for selections, instead of red and green.
+                let (warn_color, error_color, selection_color) = match visuals.dark_mode {
+                    true => (
+                        egui::Color32::from_rgb(240, 228, 66),
+                        egui::Color32::from_rgb(230, 159, 0),
+                        egui::Color32::from_rgb(0, 114, 178),
+                    ),
+                    false => (
+                        egui::Color32::from_rgb(230, 159, 0),
+                        egui::Color32::from_rgb(213, 94, 0),
+                        egui::Color32::from_rgb(86, 180, 233),
+                    ),
+                };
+                visuals.warn_fg_color = warn_color;
+                visuals.error_fg_color = error_color;
+                visuals.selection.bg_fill = selection_color;
+                visuals.hyperlink_color = egui::Color32::from_rgb(86, 180, 233);
+            }
+        }
+    }
+}
+
+/// Steps of the walkthrough that's shown to new users, in order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum OnboardingStep {
+    ChooseDirectory,
+    Summarize,
+    ReviewResults,
+    Export,
+}
+
+impl OnboardingStep {
+    const ALL: [OnboardingStep; 4] = [
+        OnboardingStep::ChooseDirectory,
+        OnboardingStep::Summarize,
+        OnboardingStep::ReviewResults,
+        OnboardingStep::Export,
+    ];
+
+    // ID of the translated explanation of this step.
+    fn message_id(&self) -> &'static str {
+        match self {
+            OnboardingStep::ChooseDirectory => "onboarding-choose-directory",
+            OnboardingStep::Summarize => "onboarding-summarize",
+            OnboardingStep::ReviewResults => "onboarding-review-results",
+            OnboardingStep::Export => "onboarding-export",
+        }
+    }
+
+    // Position of this step in the walkthrough, counting from one.
+    fn number(&self) -> usize {
+        OnboardingStep::ALL
+            .iter()
+            .position(|onboarding_step| onboarding_step == self)
+            .unwrap()
+            + 1
+    }
+
+    fn previous(&self) -> Option<OnboardingStep> {
+        // Step numbers count from one, so the previous step's index is two less.
+        self.number()
+            .checked_sub(2)
+            .map(|previous_index| OnboardingStep::ALL[previous_index])
+    }
+
+    fn next(&self) -> Option<OnboardingStep> {
+        OnboardingStep::ALL.get(self.number()).copied()
+    }
+}
 
 // We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // Define default fields when deserializing old state.
 pub struct FolsumGui {
-    // Unique file extensions and the number of times each one was encountered.
+    // Results of the latest summarization, shared with the thread that collects them.
     #[serde(skip)]
-    extension_counts: Arc<Mutex<HashMap<String, u32>>>,
+    summarization_state: SummarizationState,
     // Number of files summarized, which doesn't include files and directories that were skipped.
     #[serde(skip)]
-    total_files: u32,
+    total_files: u64,
     // User's chosen directory that will be recursively summarized when the "Summarize" button's clicked.
     #[serde(skip)]
     summarization_path: Arc<Mutex<Option<PathBuf>>>,
     // User's chosen directory and filename for CSV exports.
     export_file: Arc<Mutex<Option<PathBuf>>>,
-    // Time that summarization starts so it can be used to calculate the time taken.
+    // When the export that's being written was started, so it can be timed once it's done.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    export_started: Option<Instant>,
+    // What the export that's being written gets called in the action log once it's finished or failed.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    export_action: Option<&'static str>,
+    // Whether the latest export is still being written, finished, or failed.
+    #[serde(skip)]
+    export_status: Arc<Mutex<WorkerStatus>>,
+    // Whether the results table is grouped by top-level folder instead of showing one flat list.
+    group_by_folder: bool,
+    // Optional columns of the results table that the user has hidden.
+    hidden_columns: Vec<TableColumn>,
+    // Whether writing anything inside of the summarized directory is forbidden (for legal holds).
+    read_only_mode: bool,
+    // User's chosen directory for exports while in read-only mode.
+    reports_directory: Option<PathBuf>,
+    // Reason that the last export attempt was refused, if it was.
+    #[serde(skip)]
+    export_error: Option<&'static str>,
+    // Template that export filenames are rendered from, like `{date}_{dirname}_{case_id}`.
+    filename_template: String,
+    // How `{date}` is written in export filenames, like `%Y-%m-%d`.
+    date_format: String,
+    // Whether export dates are in UTC instead of the computer's timezone.
+    dates_in_utc: bool,
+    // Case number that's filled into the `{case_id}` placeholder of export filenames.
+    #[serde(skip)]
+    case_id: String,
+    // Name of the person running the summarization, recorded in exports for chain of custody.
+    operator_name: String,
+    // Free-text explanation of why this summarization was run, recorded in exports.
     #[serde(skip)]
-    summarization_start: Arc<Mutex<Instant>>,
-    // Amount of time that it takes to summarize a directory.
+    run_note: String,
+    // Limits on which parts of the chosen directory get summarized.
+    summarization_options: SummarizationOptions,
+    // Saved sets of summarization options that can be switched between, like "Quick check" and "Court-grade".
+    profiles: Vec<OptionsProfile>,
+    // Name that's being typed in for saving the current options as a profile.
     #[serde(skip)]
-    time_taken: Arc<Mutex<Duration>>,
+    profile_name: String,
+    // Reason that the last profile couldn't be saved, if it couldn't.
+    #[serde(skip)]
+    profile_error: Option<&'static str>,
+    // Options that the chosen directory pinned with its `.folsum.toml`, which win over the user's own.
+    #[serde(skip)]
+    project_file: Option<ProjectFile>,
+    // Reason that the chosen directory's `.folsum.toml` couldn't be used, which stops it from being summarized.
+    #[serde(skip)]
+    project_file_error: Option<String>,
+    // Seconds to wait for background work to stop when FolSum's closed, before unfinished exports are thrown away.
+    #[cfg(not(target_arch = "wasm32"))]
+    shutdown_timeout_secs: u64,
+    // When FolSum was closed while background work was running, so the window stays open until the work stops.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    stopping_since: Option<Instant>,
+    // Whether the settings window is showing.
+    #[serde(skip)]
+    settings_open: bool,
+    // Whether the pane that groups checksum findings by what went wrong is showing.
+    #[serde(skip)]
+    findings_open: bool,
+    // Results table row to scroll to on the next frame, like after a finding's double-clicked.
+    #[serde(skip)]
+    scroll_to_extension: Option<String>,
+    // Listed files of the findings that the user has selected in the findings pane.
+    #[serde(skip)]
+    selected_findings: HashSet<PathBuf>,
+    // Finding that Shift-click selections in the findings pane are measured from.
+    #[serde(skip)]
+    findings_anchor: Option<PathBuf>,
+    // Which findings the user has ignored or accepted, which carry over to the next summarization.
+    finding_decisions: FindingDecisions,
+    // Progress of verifying selected findings again.
+    #[serde(skip)]
+    reverify_status: Arc<Mutex<WorkerStatus>>,
+    // Reason that selected findings couldn't be verified again, if they couldn't.
+    #[serde(skip)]
+    reverify_error: Option<&'static str>,
+    // Files that are being verified again, for the action log once they're done.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    reverified_files: Vec<PathBuf>,
+    // How far the user has zoomed the GUI in or out, relative to their screen's native scale.
+    zoom_factor: f32,
+    // Language that the GUI is shown in.
+    language: Language,
+    // GUI text in the user's chosen language, which is reloaded when they pick another one.
+    #[serde(skip)]
+    translations: Translations,
+    // Color scheme that's applied on top of the light or dark theme.
+    theme_preset: ThemePreset,
+    // File extensions of the rows that the user has selected in the results table.
+    #[serde(skip)]
+    selected_extensions: HashSet<String>,
+    // Row that Shift-click selections are measured from, which is the last row that was clicked.
+    #[serde(skip)]
+    selection_anchor: Option<String>,
+    // Whether the quick hash window is showing.
+    #[serde(skip)]
+    quick_hash_open: bool,
+    // File that the quick hash window shows the hashes of, along with its hashes once they're ready.
+    #[serde(skip)]
+    quick_hash: Arc<Mutex<Option<QuickHash>>>,
+    // Status of hashing the quick hash file, which says why if hashing it panicked.
+    #[serde(skip)]
+    quick_hash_status: Arc<Mutex<WorkerStatus>>,
+    // Hash that the user pasted in to compare with the quick hash file's hashes.
+    #[serde(skip)]
+    provided_hash: String,
+    // Whether the user has finished or skipped the walkthrough, so it isn't shown on every launch.
+    onboarding_done: bool,
+    // Step of the walkthrough that's showing, if it is.
+    #[serde(skip)]
+    onboarding_step: Option<OnboardingStep>,
+    // Newest crash report that the user has already been told about, so it's only offered once.
+    acknowledged_crash_report: Option<PathBuf>,
+    // Crash report from a previous session that hasn't been acknowledged yet.
+    #[serde(skip)]
+    crash_report: Option<PathBuf>,
+    // Summarization that's waiting on an estimate of the directory's size before the user's asked to confirm it.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    pending_summarization: Option<Pending<(PathBuf, SummarizationOptions), DirectoryEstimate>>,
+    // SPDX export that's waiting on its preview before the user's asked to confirm it.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    pending_spdx_export: Option<Pending<SpdxExportRequest, ExportPreview>>,
+    // Whether the about window is showing.
+    #[serde(skip)]
+    about_open: bool,
+    // Latest result of the hash algorithms' self-test, which is run at startup.
+    #[serde(skip)]
+    self_test: Option<SelfTest>,
+    // Whether the window with the self-test's results is showing.
+    #[serde(skip)]
+    self_test_open: bool,
+    // Where the last diagnostics bundle was saved, or why it couldn't be.
+    #[serde(skip)]
+    diagnostics_result: Option<Result<PathBuf, &'static str>>,
+    // User's settings for the local API that other programs pull results from.
+    #[cfg(feature = "rest-api")]
+    api_settings: ApiSettings,
+    // Results API that's running, if it's turned on and started without trouble.
+    #[cfg(feature = "rest-api")]
+    #[serde(skip)]
+    api_server: Option<ApiServer>,
+    // Settings that the running results API was started with, so it's only restarted when they change.
+    #[cfg(feature = "rest-api")]
+    #[serde(skip)]
+    api_server_settings: Option<ApiSettings>,
+    // Reason that the results API couldn't be started, if it couldn't.
+    #[cfg(feature = "rest-api")]
+    #[serde(skip)]
+    api_error: Option<&'static str>,
 }
 
 impl Default for FolsumGui {
     fn default() -> Self {
         Self {
-            extension_counts: Arc::new(Mutex::new(HashMap::new())),
+            summarization_state: SummarizationState::default(),
             total_files: 0,
             summarization_path: Arc::new(Mutex::new(None)),
             export_file: Arc::new(Mutex::new(None)),
-            summarization_start: Arc::new(Mutex::new(Instant::now())),
-            time_taken: Arc::new(Mutex::new(Duration::ZERO)),
+            #[cfg(not(target_arch = "wasm32"))]
+            export_started: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            export_action: None,
+            export_status: Arc::new(Mutex::new(WorkerStatus::default())),
+            group_by_folder: false,
+            hidden_columns: Vec::new(),
+            read_only_mode: false,
+            reports_directory: None,
+            export_error: None,
+            filename_template: String::from(DEFAULT_FILENAME_TEMPLATE),
+            date_format: String::from(DEFAULT_DATE_FORMAT),
+            dates_in_utc: false,
+            case_id: String::new(),
+            operator_name: String::new(),
+            run_note: String::new(),
+            summarization_options: SummarizationOptions::default(),
+            profiles: starter_profiles(),
+            profile_name: String::new(),
+            profile_error: None,
+            project_file: None,
+            project_file_error: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            shutdown_timeout_secs: 10,
+            #[cfg(not(target_arch = "wasm32"))]
+            stopping_since: None,
+            settings_open: false,
+            findings_open: false,
+            scroll_to_extension: None,
+            selected_findings: HashSet::new(),
+            findings_anchor: None,
+            finding_decisions: FindingDecisions::default(),
+            reverify_status: Arc::new(Mutex::new(WorkerStatus::default())),
+            reverify_error: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            reverified_files: Vec::new(),
+            zoom_factor: 1.0,
+            language: Language::default(),
+            translations: Translations::default(),
+            theme_preset: ThemePreset::default(),
+            selected_extensions: HashSet::new(),
+            selection_anchor: None,
+            quick_hash_open: false,
+            quick_hash: Arc::new(Mutex::new(None)),
+            quick_hash_status: Arc::new(Mutex::new(WorkerStatus::default())),
+            provided_hash: String::new(),
+            onboarding_done: false,
+            onboarding_step: None,
+            acknowledged_crash_report: None,
+            crash_report: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_summarization: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_spdx_export: None,
+            about_open: false,
+            self_test: None,
+            self_test_open: false,
+            diagnostics_result: None,
+            #[cfg(feature = "rest-api")]
+            api_settings: ApiSettings::default(),
+            #[cfg(feature = "rest-api")]
+            api_server: None,
+            #[cfg(feature = "rest-api")]
+            api_server_settings: None,
+            #[cfg(feature = "rest-api")]
+            api_error: None,
         }
     }
 }
@@ -59,12 +516,204 @@ impl FolsumGui {
         // Customized the look at feel of egui using `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
 
         // Load previous app state (if any).
-        if let Some(storage) = cc.storage {
+        let previous_state: Option<FolsumGui> = cc
+            .storage
             // You must enable the `persistence` feature for this to work.
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+            .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY));
+        let mut folsum_gui: FolsumGui = previous_state.unwrap_or_default();
+        // Load the GUI text for the language that the user chose last session.
+        folsum_gui.translations = Translations::new(folsum_gui.language);
+        // Walk first-time users through FolSum.
+        if !folsum_gui.onboarding_done {
+            folsum_gui.onboarding_step = Some(OnboardingStep::ChooseDirectory);
+        }
+        // Offer the crash report from the last time that FolSum crashed, unless the user's already seen it.
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            folsum_gui.crash_report = latest_crash_report().filter(|crash_report| {
+                folsum_gui.acknowledged_crash_report.as_ref() != Some(crash_report)
+            });
+        }
+        // Make sure the hash algorithms give known answers before anything's hashed, and speak up if they don't.
+        let self_test: SelfTest = run_self_test();
+        folsum_gui.self_test_open = !self_test.passed();
+        folsum_gui.self_test = Some(self_test);
+        // Restore the zoom level from the last session. The window's size and position are restored by eframe.
+        if let Some(native_pixels_per_point) = cc.integration_info.native_pixels_per_point {
+            cc.egui_ctx
+                .set_pixels_per_point(native_pixels_per_point * folsum_gui.zoom_factor);
+        }
+        // Start the results API if the user left it on last session.
+        #[cfg(feature = "rest-api")]
+        folsum_gui.sync_api_server();
+        folsum_gui
+    }
+
+    /// Forget the current summarization and its results, like a fresh launch that keeps the user's settings.
+    ///
+    /// Shared state gets new `Arc`s, so threads that are still running write into state that's no longer shown.
+    pub fn new_session(&mut self) {
+        let fresh_session = FolsumGui::default();
+        self.summarization_state = fresh_session.summarization_state;
+        self.total_files = fresh_session.total_files;
+        self.summarization_path = fresh_session.summarization_path;
+        self.export_status = fresh_session.export_status;
+        self.export_error = fresh_session.export_error;
+        self.project_file = fresh_session.project_file;
+        self.project_file_error = fresh_session.project_file_error;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.export_started = fresh_session.export_started;
+            self.export_action = fresh_session.export_action;
+            self.reverified_files = fresh_session.reverified_files;
+        }
+        self.case_id = fresh_session.case_id;
+        self.run_note = fresh_session.run_note;
+        self.selected_extensions = fresh_session.selected_extensions;
+        self.selection_anchor = fresh_session.selection_anchor;
+        self.selected_findings = fresh_session.selected_findings;
+        self.findings_anchor = fresh_session.findings_anchor;
+        self.reverify_status = fresh_session.reverify_status;
+        self.reverify_error = fresh_session.reverify_error;
+        self.quick_hash = fresh_session.quick_hash;
+        self.quick_hash_status = fresh_session.quick_hash_status;
+        self.provided_hash = fresh_session.provided_hash;
+        // Restart the results API so it serves the new session's results instead of the old one's.
+        #[cfg(feature = "rest-api")]
+        {
+            self.api_server_settings = None;
+            self.sync_api_server();
+        }
+    }
+
+    /// Start, restart, or stop the results API so it matches the user's settings.
+    #[cfg(feature = "rest-api")]
+    fn sync_api_server(&mut self) {
+        let wanted_settings: Option<ApiSettings> =
+            self.api_settings.enabled.then(|| self.api_settings.clone());
+        if wanted_settings == self.api_server_settings {
+            return;
+        }
+        // Stop the old server first so the new one can take over its port.
+        self.api_server = None;
+        self.api_error = None;
+        if let Some(api_settings) = &wanted_settings {
+            let api_results = ApiResults {
+                summarization_path: Arc::clone(&self.summarization_path),
+                extension_counts: Arc::clone(&self.summarization_state.extension_counts),
+                extension_bytes: Arc::clone(&self.summarization_state.extension_bytes),
+                checksum_findings: Arc::clone(&self.summarization_state.checksum_findings),
+                severity_rules: Arc::clone(&self.summarization_state.severity_rules),
+                phase_timings: Arc::clone(&self.summarization_state.phase_timings),
+                summarization_status: Arc::clone(&self.summarization_state.summarization_status),
+            };
+            match serve_results(api_settings, &api_results) {
+                Ok(api_server) => self.api_server = Some(api_server),
+                Err(api_error) => self.api_error = Some(api_error),
+            }
         }
+        self.api_server_settings = wanted_settings;
+    }
+
+    /// Describe the current summarization in plain text, for diagnostics bundles.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn summary_text(&self) -> String {
+        let summarized_directory: String = match &*lock_shared(&self.summarization_path) {
+            Some(directory) => directory.display().to_string(),
+            None => String::from("(none)"),
+        };
+        let locked_bytes = lock_shared(&self.summarization_state.extension_bytes);
+        let locked_totals = lock_shared(&self.summarization_state.storage_totals);
+        let mut summary_text = format!(
+            "Directory: {}\nStatus: {:?}\nFiles: {}\nBytes: {}\nBytes on disk: {}\nSparse files: {}\nTime taken: {}\nHardlinks skipped: {}\n",
+            summarized_directory,
+            lock_shared(&self.summarization_state.summarization_status),
+            self.total_files,
+            locked_bytes.values().sum::<u64>(),
+            locked_totals.allocated_bytes,
+            locked_totals.sparse_files,
+            format_duration(&lock_shared(&self.summarization_state.time_taken)),
+            lock_shared(&self.summarization_state.hardlink_count),
+        );
+        let locked_timings = lock_shared(&self.summarization_state.phase_timings);
+        summary_text.push_str(&format!(
+            "Walk time: {}\nChecksum verification time: {}\nExport write time: {}\n",
+            format_duration(&locked_timings.walk),
+            format_duration(&locked_timings.checksum_verification),
+            locked_timings
+                .export_write
+                .as_ref()
+                .map_or_else(|| String::from("(none)"), format_duration),
+        ));
+        drop(locked_timings);
+        summary_text.push_str("\nExtensions:\n");
+        for (extension, occurrences) in
+            sort_counts(&lock_shared(&self.summarization_state.extension_counts))
+        {
+            let total_bytes: u64 = locked_bytes.get(extension).copied().unwrap_or(0);
+            summary_text.push_str(&format!(
+                "  {extension}: {occurrences} ({total_bytes} bytes)\n"
+            ));
+        }
+        summary_text.push_str("\nChecksum findings:\n");
+        for checksum_finding in lock_shared(&self.summarization_state.checksum_findings).iter() {
+            summary_text.push_str(&format!(
+                "  {:?}: {}\n",
+                checksum_finding.outcome,
+                checksum_finding.listed_file.display()
+            ));
+        }
+        summary_text.push_str("\nExtra streams:\n");
+        for extra_stream in lock_shared(&self.summarization_state.extra_streams).iter() {
+            summary_text.push_str(&format!(
+                "  {}:{} ({} bytes)\n",
+                extra_stream.file.display(),
+                extra_stream.stream_name,
+                extra_stream.stream_size
+            ));
+        }
+        summary_text
+    }
+
+    /// Save a diagnostics bundle with the settings as they'd be saved on quit.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_diagnostics(&self, bundle_path: PathBuf) -> Result<PathBuf, &'static str> {
+        let mut settings_storage = MemoryStorage::default();
+        eframe::set_value(&mut settings_storage, eframe::APP_KEY, self);
+        let settings: String = settings_storage
+            .values
+            .remove(eframe::APP_KEY)
+            .unwrap_or_default();
+        save_diagnostics_bundle(&bundle_path, &settings, &self.summary_text())?;
+        record_action("save diagnostics", &bundle_path);
+        Ok(bundle_path)
+    }
 
-        Default::default()
+    /// Switch to a saved profile's summarization options, like when FolSum's launched with `--profile`.
+    pub fn use_profile(&mut self, profile_name: &str) -> Result<(), &'static str> {
+        let profile: &OptionsProfile = find_profile(&self.profiles, profile_name)
+            .ok_or("There's no profile with that name")?;
+        self.summarization_options = profile.options.clone();
+        Ok(())
+    }
+
+    /// Choose a directory and start summarizing it right away, like when FolSum's launched from a file manager.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn summarize_on_launch(&mut self, directory: PathBuf) {
+        // Don't summarize without the options that the directory pinned, if they can't be read.
+        match read_project_file(&directory) {
+            Ok(project_file) => self.project_file = project_file,
+            Err(project_file_error) => {
+                self.project_file_error = Some(project_file_error);
+                return;
+            }
+        }
+        let pinned_options: SummarizationOptions = match &self.project_file {
+            Some(project_file) => project_file.pin(&self.summarization_options),
+            None => self.summarization_options.clone(),
+        };
+        // Still check with the user before tying up their computer with a huge directory, once it's been sized up.
+        self.pending_summarization = Some(estimate_before_summarizing(directory, pinned_options));
     }
 }
 
@@ -74,160 +723,2883 @@ impl eframe::App for FolsumGui {
         eframe::set_value(storage, eframe::APP_KEY, self);
     }
 
+    // Called when the window's closed or File > Quit is clicked, before FolSum exits.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn on_close_event(&mut self) -> bool {
+        let still_running: usize = running_workers();
+        match self.stopping_since {
+            None if still_running == 0 => true,
+            // Ask summarizations, exports, and hashing to wrap up, and keep the window open while they do.
+            None => {
+                log::info!("Stopping {still_running} background workers before closing");
+                request_stop();
+                self.stopping_since = Some(Instant::now());
+                false
+            }
+            Some(_) if still_running == 0 => {
+                log::info!("Stopped background work before closing");
+                true
+            }
+            Some(stopping_since)
+                if stopping_since.elapsed() >= Duration::from_secs(self.shutdown_timeout_secs) =>
+            {
+                // Don't leave half-written exports behind for work that's about to be cut off.
+                log::warn!(
+                    "Background work didn't stop within {} seconds, so its unfinished exports were discarded",
+                    self.shutdown_timeout_secs
+                );
+                discard_unfinished_writes();
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
     // Called each time the UI needs repainting, which may be many times per second.
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         let Self {
-            extension_counts,
+            summarization_state,
             total_files,
             summarization_path,
             #[cfg(not(target_arch = "wasm32"))]
             export_file,
-            summarization_start,
-            time_taken,
+            #[cfg(not(target_arch = "wasm32"))]
+            export_started,
+            #[cfg(not(target_arch = "wasm32"))]
+            export_action,
+            #[cfg(not(target_arch = "wasm32"))]
+            export_status,
+            group_by_folder,
+            hidden_columns,
+            #[cfg(not(target_arch = "wasm32"))]
+            read_only_mode,
+            #[cfg(not(target_arch = "wasm32"))]
+            reports_directory,
+            #[cfg(not(target_arch = "wasm32"))]
+            export_error,
+            #[cfg(not(target_arch = "wasm32"))]
+            filename_template,
+            #[cfg(not(target_arch = "wasm32"))]
+            date_format,
+            #[cfg(not(target_arch = "wasm32"))]
+            dates_in_utc,
+            #[cfg(not(target_arch = "wasm32"))]
+            case_id,
+            #[cfg(not(target_arch = "wasm32"))]
+            operator_name,
+            #[cfg(not(target_arch = "wasm32"))]
+            run_note,
+            summarization_options,
+            profiles,
+            profile_name,
+            profile_error,
+            project_file,
+            project_file_error,
+            #[cfg(not(target_arch = "wasm32"))]
+            shutdown_timeout_secs,
+            #[cfg(not(target_arch = "wasm32"))]
+            stopping_since,
+            settings_open,
+            findings_open,
+            scroll_to_extension,
+            selected_findings,
+            findings_anchor,
+            finding_decisions,
+            reverify_status,
+            reverify_error,
+            #[cfg(not(target_arch = "wasm32"))]
+            reverified_files,
+            zoom_factor,
+            language,
+            translations,
+            theme_preset,
+            selected_extensions,
+            selection_anchor,
+            #[cfg(not(target_arch = "wasm32"))]
+            quick_hash_open,
+            #[cfg(not(target_arch = "wasm32"))]
+            quick_hash,
+            #[cfg(not(target_arch = "wasm32"))]
+            quick_hash_status,
+            #[cfg(not(target_arch = "wasm32"))]
+            provided_hash,
+            onboarding_done,
+            onboarding_step,
+            #[cfg(not(target_arch = "wasm32"))]
+            acknowledged_crash_report,
+            #[cfg(not(target_arch = "wasm32"))]
+            crash_report,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_summarization,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_spdx_export,
+            about_open,
+            self_test,
+            self_test_open,
+            #[cfg(not(target_arch = "wasm32"))]
+            diagnostics_result,
+            #[cfg(feature = "rest-api")]
+            api_settings,
+            #[cfg(feature = "rest-api")]
+            api_server,
+            #[cfg(feature = "rest-api")]
+            api_error,
             ..
         } = self;
+        let SummarizationState {
+            extension_counts,
+            extension_bytes,
+            storage_totals,
+            time_taken,
+            hardlink_count,
+            folder_counts,
+            checksum_findings,
+            extra_streams,
+            inventory_stats,
+            phase_timings,
+            verification_progress,
+            severity_rules,
+            audit_report,
+            summarization_status,
+            ..
+        } = &*summarization_state;
+
+        // Start over once everything's been drawn so the GUI doesn't show half of each session.
+        let mut start_new_session: bool = false;
+        // Save a diagnostics bundle here once everything's been drawn, because it needs all of the settings.
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut save_diagnostics_to: Option<PathBuf> = None;
+        // Start or stop the results API once everything's been drawn if the user changed its settings.
+        #[cfg(feature = "rest-api")]
+        let mut api_settings_changed: bool = false;
+        // Remember where the parts of the GUI that the walkthrough points at were drawn.
+        let mut step_rects: HashMap<OnboardingStep, egui::Rect> = HashMap::new();
 
         // Update the count of total files summarized.
-        *total_files = extension_counts.lock().unwrap().values().sum();
-        // Update the screen on each iteration, bounded by the refresh rate of the user's screen.
-        ctx.request_repaint();
+        *total_files = lock_shared(extension_counts)
+            .values()
+            .map(|count| u64::from(*count))
+            .sum();
+        // Redraw as often as the screen refreshes while work runs in the background so its results show up live.
+        let mut work_in_progress: bool =
+            *lock_shared(summarization_status) == WorkerStatus::InProgress;
+        work_in_progress |= *lock_shared(reverify_status) == WorkerStatus::InProgress;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            work_in_progress |= *lock_shared(export_status) == WorkerStatus::InProgress;
+            work_in_progress |= *lock_shared(quick_hash_status) == WorkerStatus::InProgress;
+            work_in_progress |= pending_summarization.is_some() || pending_spdx_export.is_some();
+        }
+        match work_in_progress {
+            true => ctx.request_repaint(),
+            // Otherwise, only redraw when the user does something, with a slow heartbeat to catch anything else.
+            false => ctx.request_repaint_after(IDLE_REPAINT_INTERVAL),
+        }
+
+        // Let the user zoom with keyboard shortcuts. On web, the browser controls zooming instead.
+        #[cfg(not(target_arch = "wasm32"))]
+        egui::gui_zoom::zoom_with_keyboard_shortcuts(ctx, _frame.info().native_pixels_per_point);
+        // Remember the zoom level relative to the screen's scale so it can be restored on the next launch.
+        if let Some(native_pixels_per_point) = _frame.info().native_pixels_per_point {
+            *zoom_factor = ctx.pixels_per_point() / native_pixels_per_point;
+        }
+
+        // Recolor the light or dark theme with the user's theme preset.
+        let mut visuals = match ctx.style().visuals.dark_mode {
+            true => egui::Visuals::dark(),
+            false => egui::Visuals::light(),
+        };
+        theme_preset.apply(&mut visuals);
+        ctx.set_visuals(visuals);
 
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             // Add a menu bar to the top of the screen.
             egui::menu::bar(ui, |ui| {
-                // Don't include a File->Quit menu item when compiling for web.
-                #[cfg(not(target_arch = "wasm32"))]
-                ui.menu_button("File", |ui| {
-                    if ui.button("Quit").clicked() {
+                ui.menu_button(translations.get("menu-file"), |ui| {
+                    if ui.button(translations.get("menu-new-session")).clicked() {
+                        start_new_session = true;
+                        ui.close_menu();
+                    }
+                    // Don't include a File->Quit menu item when compiling for web.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui.button(translations.get("menu-quit")).clicked() {
                         _frame.close();
                     }
                 });
+                // Add zoom in, zoom out, and reset zoom buttons. On web, the browser controls zooming instead.
+                #[cfg(not(target_arch = "wasm32"))]
+                ui.menu_button(translations.get("menu-view"), |ui| {
+                    egui::gui_zoom::zoom_menu_buttons(ui, _frame.info().native_pixels_per_point);
+                });
+                #[cfg(not(target_arch = "wasm32"))]
+                ui.menu_button(translations.get("menu-tools"), |ui| {
+                    if ui.button(translations.get("menu-quick-hash")).clicked() {
+                        *quick_hash_open = true;
+                        ui.close_menu();
+                    }
+                });
+                if ui.button(translations.get("menu-settings")).clicked() {
+                    *settings_open = true;
+                }
+                ui.menu_button(translations.get("menu-help"), |ui| {
+                    if ui.button(translations.get("menu-walkthrough")).clicked() {
+                        *onboarding_step = Some(OnboardingStep::ChooseDirectory);
+                        ui.close_menu();
+                    }
+                    let validate_button = describe(
+                        ui.button(translations.get("menu-validate-tool")),
+                        translations.get("menu-validate-tool-hover"),
+                    );
+                    if validate_button.clicked() {
+                        *self_test = Some(run_self_test());
+                        *self_test_open = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(translations.get("menu-about")).clicked() {
+                        *about_open = true;
+                        ui.close_menu();
+                    }
+                });
                 // Add a dark/light mode toggle button to the top menu bar.
                 egui::widgets::global_dark_light_mode_switch(ui);
             });
         });
 
-        egui::SidePanel::left("left_panel")
+        // Show which hash algorithms gave their known answers.
+        egui::Window::new(translations.get("self-test-title"))
+            // Keep the window's position when its title changes language.
+            .id(egui::Id::new("self_test_window"))
+            .open(self_test_open)
             .resizable(false)
             .show(ctx, |ui| {
-                ui.heading("Choose a Directory to Summarize");
+                if let Some(self_test) = self_test {
+                    egui::Grid::new("self_test_grid").show(ui, |ui| {
+                        for (algorithm_name, passed) in &self_test.algorithms {
+                            let algorithm_label = ui.label(*algorithm_name);
+                            let result_id: &str = match passed {
+                                true => "self-test-algorithm-passed",
+                                false => "self-test-algorithm-failed",
+                            };
+                            ui.label(translations.get(result_id))
+                                .labelled_by(algorithm_label.id);
+                            ui.end_row();
+                        }
+                    });
+                    match self_test.passed() {
+                        true => announce_changes(&ui.label(translations.get("self-test-passed"))),
+                        false => announce_changes(&error_label(
+                            ui,
+                            &translations.get("self-test-failed"),
+                        )),
+                    }
+                }
+            });
 
-                // Don't add a directory picker when compiling for web.
+        egui::Window::new(translations.get("about-title"))
+            // Keep the window's position when its title changes language.
+            .id(egui::Id::new("about_window"))
+            .open(about_open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.heading("FolSum");
+                egui::Grid::new("about_grid").show(ui, |ui| {
+                    for (label_id, about_value) in [
+                        ("about-version", FOLSUM_VERSION),
+                        ("about-commit", GIT_COMMIT),
+                        ("about-build-date", BUILD_DATE),
+                        ("about-os", std::env::consts::OS),
+                    ] {
+                        let about_label = ui.label(translations.get(label_id));
+                        ui.monospace(about_value).labelled_by(about_label.id);
+                        ui.end_row();
+                    }
+                });
+                if ui.button(translations.get("about-copy")).clicked() {
+                    ui.output_mut(|output| output.copied_text = build_description());
+                }
+                // Saving files isn't possible from the browser.
                 #[cfg(not(target_arch = "wasm32"))]
-                if ui.button("Open directory...").clicked() {
-                    if let Some(path) = FileDialog::new().pick_folder() {
-                        *summarization_path = Arc::new(Mutex::new(Some(path)));
+                {
+                    ui.separator();
+                    let save_button = describe(
+                        ui.button(translations.get("about-save-diagnostics")),
+                        translations.get("about-save-diagnostics-hover"),
+                    );
+                    if save_button.clicked() {
+                        save_diagnostics_to = FileDialog::new()
+                            .set_title(translations.get("about-save-diagnostics"))
+                            .set_file_name("folsum_diagnostics.zip")
+                            .add_filter("Zip", &["zip"])
+                            .save_file();
+                    }
+                    match diagnostics_result {
+                        Some(Ok(bundle_path)) => {
+                            let mut saved_args = FluentArgs::new();
+                            saved_args.set("bundle_path", bundle_path.display().to_string());
+                            announce_changes(&ui.label(
+                                translations.get_with_args("about-diagnostics-saved", &saved_args),
+                            ));
+                        }
+                        Some(Err(diagnostics_error)) => {
+                            announce_changes(&error_label(
+                                ui,
+                                &translate_error(diagnostics_error, translations),
+                            ));
+                        }
+                        None => (),
+                    }
+                    // Show who did what on this computer, for workstations that several people share.
+                    let action_log_button = describe(
+                        ui.button(translations.get("about-open-action-log")),
+                        translations.get("about-open-action-log-hover"),
+                    );
+                    if action_log_button.clicked() {
+                        let open_result = action_log_path()
+                            .filter(|log_path| log_path.exists())
+                            .ok_or("Nothing's been recorded in the action log yet")
+                            .and_then(|log_path| open_path(&log_path));
+                        if let Err(open_error) = open_result {
+                            announce_changes(&error_label(
+                                ui,
+                                &translate_error(open_error, translations),
+                            ));
+                        }
                     }
                 }
+            });
+
+        egui::Window::new(translations.get("menu-settings"))
+            // Keep the window's position when its title changes language.
+            .id(egui::Id::new("settings_window"))
+            .open(settings_open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let language_label = ui.label(translations.get("settings-language"));
+                    let previous_language: Language = *language;
+                    egui::ComboBox::from_id_source("language_picker")
+                        .selected_text(language.native_name())
+                        .show_ui(ui, |ui| {
+                            for choice in Language::ALL {
+                                ui.selectable_value(language, choice, choice.native_name());
+                            }
+                        })
+                        .response
+                        .labelled_by(language_label.id);
+                    // Switch the GUI's text to the newly chosen language right away.
+                    if *language != previous_language {
+                        *translations = Translations::new(*language);
+                    }
+                });
 
                 ui.horizontal(|ui| {
-                    let locked_path: &Option<PathBuf> = &*summarization_path.lock().unwrap();
-                    // Check if the user has picked a directory to summarize.
-                    let shown_path: &str = match &*locked_path {
-                        Some(the_path) => the_path.as_os_str().to_str().unwrap(),
-                        None => "No directory selected",
-                    };
-                    ui.label("Chosen directory:");
-                    // Display the user's chosen directory in monospace font.
-                    ui.monospace(shown_path);
+                    let theme_label = ui.label(translations.get("settings-theme"));
+                    egui::ComboBox::from_id_source("theme_picker")
+                        .selected_text(translations.get(theme_preset.name_id()))
+                        .show_ui(ui, |ui| {
+                            for choice in ThemePreset::ALL {
+                                ui.selectable_value(
+                                    theme_preset,
+                                    choice,
+                                    translations.get(choice.name_id()),
+                                );
+                            }
+                        })
+                        .response
+                        .labelled_by(theme_label.id);
                 });
 
                 ui.separator();
 
-                if ui.button("Summarize").clicked() {
-                    let _result = summarize_directory(
-                        &summarization_path,
-                        &extension_counts,
-                        &summarization_start,
-                        &time_taken,
-                    );
-                };
-
+                ui.heading(translations.get("settings-summarization"));
+                ui.horizontal(|ui| {
+                    let profile_label = ui.label(translations.get("settings-profile"));
+                    // Name the profile that the options came from, as long as they haven't been changed since.
+                    let current_profile: Option<String> = profiles
+                        .iter()
+                        .find(|profile| profile.options == *summarization_options)
+                        .map(|profile| profile.name.clone());
+                    egui::ComboBox::from_id_source("profile_picker")
+                        .selected_text(
+                            current_profile
+                                .clone()
+                                .unwrap_or_else(|| translations.get("settings-profile-custom")),
+                        )
+                        .show_ui(ui, |ui| {
+                            for profile in profiles.iter() {
+                                let is_current: bool =
+                                    current_profile.as_ref() == Some(&profile.name);
+                                if ui.selectable_label(is_current, &profile.name).clicked() {
+                                    *summarization_options = profile.options.clone();
+                                }
+                            }
+                        })
+                        .response
+                        .labelled_by(profile_label.id);
+                    if let Some(current_profile) = current_profile {
+                        if ui
+                            .button(translations.get("settings-profile-delete"))
+                            .clicked()
+                        {
+                            profiles.retain(|profile| profile.name != current_profile);
+                        }
+                    }
+                });
                 ui.horizontal(|ui| {
-                    let locked_time_taken = time_taken.lock().unwrap();
-                    ui.label(format!(
-                        "Summarized {} files in {} milliseconds",
-                        &total_files,
-                        &locked_time_taken.as_millis()
+                    let profile_name_field = ui.add(
+                        egui::TextEdit::singleline(profile_name)
+                            .hint_text(translations.get("settings-profile-name")),
+                    );
+                    let save_button = ui
+                        .button(translations.get("settings-profile-save"))
+                        .labelled_by(profile_name_field.id);
+                    if describe(save_button, translations.get("settings-profile-save-hover"))
+                        .clicked()
+                    {
+                        *profile_error =
+                            save_profile(profiles, profile_name, summarization_options).err();
+                        if profile_error.is_none() {
+                            profile_name.clear();
+                        }
+                    }
+                });
+                if let Some(profile_error) = profile_error {
+                    announce_changes(&error_label(
+                        ui,
+                        &translate_error(profile_error, translations),
                     ));
+                }
+                let same_file_system_checkbox = ui.checkbox(
+                    &mut summarization_options.same_file_system,
+                    translations.get("settings-same-file-system"),
+                );
+                describe(
+                    same_file_system_checkbox,
+                    translations.get("settings-same-file-system-hover"),
+                );
+                ui.horizontal(|ui| {
+                    let mut limit_depth: bool = summarization_options.max_depth.is_some();
+                    let limit_depth_checkbox =
+                        ui.checkbox(&mut limit_depth, translations.get("settings-limit-depth"));
+                    if limit_depth_checkbox.changed() {
+                        // Start depth-limited summarizations at a reasonable default depth.
+                        summarization_options.max_depth = limit_depth.then_some(10);
+                    }
+                    if let Some(max_depth) = &mut summarization_options.max_depth {
+                        ui.add(egui::DragValue::new(max_depth).clamp_range(1..=1000))
+                            .labelled_by(limit_depth_checkbox.id);
+                        ui.label(translations.get("settings-depth-levels"));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    let ignore_label = describe(
+                        ui.label(translations.get("settings-ignore-metadata")),
+                        translations.get("settings-ignore-metadata-hover"),
+                    );
+                    let ignore_presets = &mut summarization_options.ignore_presets;
+                    for (preset_chosen, preset_id) in [
+                        (&mut ignore_presets.macos, "settings-ignore-macos"),
+                        (&mut ignore_presets.windows, "settings-ignore-windows"),
+                        (&mut ignore_presets.linux, "settings-ignore-linux"),
+                    ] {
+                        ui.checkbox(preset_chosen, translations.get(preset_id))
+                            .labelled_by(ignore_label.id);
+                    }
+                });
+                let streams_checkbox = ui.checkbox(
+                    &mut summarization_options.find_extra_streams,
+                    translations.get("settings-extra-streams"),
+                );
+                describe(
+                    streams_checkbox,
+                    translations.get("settings-extra-streams-hover"),
+                );
+                let verify_checkbox = ui.checkbox(
+                    &mut summarization_options.verify_checksum_files,
+                    translations.get("settings-verify-checksums"),
+                );
+                describe(
+                    verify_checkbox,
+                    translations.get("settings-verify-checksums-hover"),
+                );
+                let retry_checkbox = ui.checkbox(
+                    &mut summarization_options.retry_locked_files,
+                    translations.get("settings-retry-locked"),
+                );
+                describe(
+                    retry_checkbox,
+                    translations.get("settings-retry-locked-hover"),
+                );
+                let size_only_rules = &mut summarization_options.size_only_rules;
+                ui.horizontal(|ui| {
+                    let size_only_label =
+                        ui.label(translations.get("settings-size-only-extensions"));
+                    let size_only_field = ui
+                        .text_edit_singleline(&mut size_only_rules.extensions)
+                        .labelled_by(size_only_label.id);
+                    describe(
+                        size_only_field,
+                        translations.get("settings-size-only-extensions-hover"),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    let mut limit_size: bool = size_only_rules.larger_than_mib.is_some();
+                    let limit_size_checkbox = ui.checkbox(
+                        &mut limit_size,
+                        translations.get("settings-size-only-larger"),
+                    );
+                    if limit_size_checkbox.changed() {
+                        // Start with files bigger than a gibibyte, which take a while to hash even on fast disks.
+                        size_only_rules.larger_than_mib = limit_size.then_some(1024);
+                    }
+                    if let Some(larger_than_mib) = &mut size_only_rules.larger_than_mib {
+                        ui.add(egui::DragValue::new(larger_than_mib).clamp_range(1..=u64::MAX))
+                            .labelled_by(limit_size_checkbox.id);
+                        ui.label(translations.get("settings-size-only-mib"));
+                    }
+                });
+                // Decide which kinds of findings fail an audit, which only leave it unconfirmed, and which are noise.
+                egui::CollapsingHeader::new(translations.get("settings-severity"))
+                    .id_source("severity_rules")
+                    .show(ui, |ui| {
+                        ui.label(translations.get("settings-severity-hint"));
+                        let severity_rules = &mut summarization_options.severity_rules;
+                        egui::Grid::new("severity_grid").show(ui, |ui| {
+                            for outcome in DISCREPANCY_OUTCOMES {
+                                let outcome_label = ui.label(translations.get(outcome_id(outcome)));
+                                if let Some(severity) = severity_rules.rule_mut(outcome) {
+                                    egui::ComboBox::from_id_source(("severity", outcome.name()))
+                                        .selected_text(translations.get(severity_id(*severity)))
+                                        .show_ui(ui, |ui| {
+                                            for offered_severity in Severity::ALL {
+                                                ui.selectable_value(
+                                                    severity,
+                                                    offered_severity,
+                                                    translations.get(severity_id(offered_severity)),
+                                                );
+                                            }
+                                        })
+                                        .response
+                                        .labelled_by(outcome_label.id);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            let info_label =
+                                ui.label(translations.get("settings-severity-info-extensions"));
+                            let info_field = ui
+                                .text_edit_singleline(&mut severity_rules.info_extensions)
+                                .labelled_by(info_label.id);
+                            describe(
+                                info_field,
+                                translations.get("settings-severity-info-extensions-hover"),
+                            );
+                        });
+                    });
+                // Files are read in bigger chunks when that turns out to be faster, like on network shares.
+                ui.horizontal(|ui| {
+                    let read_buffer_label = describe(
+                        ui.label(translations.get("settings-read-buffer")),
+                        translations.get("settings-read-buffer-hover"),
+                    );
+                    let read_buffer_limits = &mut summarization_options.read_buffer_limits;
+                    for (limit_kib, limit_id) in [
+                        (&mut read_buffer_limits.min_kib, "settings-read-buffer-min"),
+                        (&mut read_buffer_limits.max_kib, "settings-read-buffer-max"),
+                    ] {
+                        ui.label(translations.get(limit_id));
+                        ui.add(
+                            egui::DragValue::new(limit_kib)
+                                .clamp_range(SMALLEST_READ_BUFFER_KIB..=BIGGEST_READ_BUFFER_KIB),
+                        )
+                        .labelled_by(read_buffer_label.id);
+                    }
+                    ui.label(translations.get("settings-read-buffer-kib"));
                 });
+                ui.horizontal(|ui| {
+                    let readers_label = describe(
+                        ui.label(translations.get("settings-readers-per-device")),
+                        translations.get("settings-readers-per-device-hover"),
+                    );
+                    ui.add(
+                        egui::DragValue::new(
+                            &mut summarization_options.read_buffer_limits.readers_per_device,
+                        )
+                        .clamp_range(1..=MOST_READERS_PER_DEVICE),
+                    )
+                    .labelled_by(readers_label.id);
+                });
+                // Browsers stop FolSum on their own when the tab's closed.
+                #[cfg(not(target_arch = "wasm32"))]
+                ui.horizontal(|ui| {
+                    let shutdown_label = describe(
+                        ui.label(translations.get("settings-shutdown-timeout")),
+                        translations.get("settings-shutdown-timeout-hover"),
+                    );
+                    ui.add(egui::DragValue::new(shutdown_timeout_secs).clamp_range(0..=120))
+                        .labelled_by(shutdown_label.id);
+                    ui.label(translations.get("settings-shutdown-timeout-seconds"));
+                });
+                // Browsers don't have a system log to write to.
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let system_log_checkbox = ui.checkbox(
+                        &mut summarization_options.write_system_log,
+                        translations.get("settings-system-log"),
+                    );
+                    describe(
+                        system_log_checkbox,
+                        translations.get("settings-system-log-hover"),
+                    );
+                }
 
                 ui.separator();
 
+                // Don't show export settings when compiling for web because there's nothing to export.
                 #[cfg(not(target_arch = "wasm32"))]
-                if ui.button("Export to CSV").clicked() {
-                    let date_today: DateTime<Local> = DateTime::from(SystemTime::now());
-                    let formatted_date = date_today.format("%y_%m_%d").to_string();
-                    // Prepend the date (YY_MM_DD) to the filename.
-                    let export_filename = format!("{formatted_date}_folsum_export");
-                    // Open the "Save export file as" dialog.
-                    let starting_directory = match export_file.lock().unwrap().clone() {
-                        // Open the export dialog in the same dir as the previous export.
-                        Some(export_file) => export_file.parent().unwrap().to_path_buf(),
-                        // Otherwise, if there was no previous export, then open the export dialog in the user's home dir.
-                        None => home_dir().expect("Failed to get user's home directory"),
+                {
+                    ui.heading(translations.get("settings-export-filenames"));
+                    ui.horizontal(|ui| {
+                        let template_label =
+                            ui.label(translations.get("settings-filename-template"));
+                        ui.text_edit_singleline(filename_template)
+                            .labelled_by(template_label.id);
+                    });
+                    let mut placeholder_args = FluentArgs::new();
+                    placeholder_args.set("placeholders", "{date}, {dirname}, {case_id}");
+                    ui.label(
+                        translations.get_with_args("settings-placeholders", &placeholder_args),
+                    );
+                    ui.horizontal(|ui| {
+                        let date_format_label = ui.label(translations.get("settings-date-format"));
+                        describe(
+                            ui.text_edit_singleline(date_format)
+                                .labelled_by(date_format_label.id),
+                            translations.get("settings-date-format-hover"),
+                        );
+                        // Offer the formats that most people want so they don't have to know strftime.
+                        for (preset_id, preset_format) in [
+                            ("settings-date-iso", DEFAULT_DATE_FORMAT),
+                            ("settings-date-legacy", LEGACY_DATE_FORMAT),
+                        ] {
+                            if ui
+                                .selectable_label(
+                                    date_format == preset_format,
+                                    translations.get(preset_id),
+                                )
+                                .clicked()
+                            {
+                                *date_format = String::from(preset_format);
+                            }
+                        }
+                    });
+                    describe(
+                        ui.checkbox(dates_in_utc, translations.get("settings-dates-in-utc")),
+                        translations.get("settings-dates-in-utc-hover"),
+                    );
+                    // Show what the next export will be called so typos are caught before exporting.
+                    match create_export_path(
+                        Path::new(""),
+                        filename_template,
+                        date_format,
+                        &lock_shared(summarization_path),
+                        case_id,
+                        &export_time(*dates_in_utc),
+                    ) {
+                        Ok(preview_path) => {
+                            ui.horizontal(|ui| {
+                                let preview_label = ui.label(translations.get("settings-preview"));
+                                ui.monospace(preview_path.display().to_string())
+                                    .labelled_by(preview_label.id);
+                            });
+                        }
+                        Err(template_error) => {
+                            announce_changes(&error_label(
+                                ui,
+                                &translate_error(template_error, translations),
+                            ));
+                        }
                     };
-                    // Ask user where they'd like to save the CSV export and what they'd like it to be called.
-                    if let Some(path) = FileDialog::new()
-                        // Add `.csv` to the end of the user's chosen name for the CSV export.
-                        .add_filter("csv", &["csv"])
-                        .set_title("Export extension counts to CSV file")
-                        // Open export dialogs in the last saved directory (if it exists), otherwise in the user's home directory.
-                        .set_directory(starting_directory)
-                        // Set the default filename for CSV exports to YY_MM_DD_folsum_export.
-                        .set_file_name(&export_filename)
-                        .save_file()
+                    if ui
+                        .button(translations.get("settings-reset-template"))
+                        .clicked()
                     {
-                        *export_file = Arc::new(Mutex::new(Some(path)));
+                        *filename_template = String::from(DEFAULT_FILENAME_TEMPLATE);
                     }
-                    let _result = export_csv(&export_file, &extension_counts);
-                };
+                }
 
-                ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
+                #[cfg(feature = "rest-api")]
+                {
+                    ui.separator();
+                    ui.heading(translations.get("settings-results-api"));
+                    let enabled_checkbox = describe(
+                        ui.checkbox(
+                            &mut api_settings.enabled,
+                            translations.get("settings-api-enabled"),
+                        ),
+                        translations.get("settings-api-enabled-hover"),
+                    );
+                    api_settings_changed |= enabled_checkbox.changed();
+                    ui.horizontal(|ui| {
+                        let port_label = ui.label(translations.get("settings-api-port"));
+                        let port_field = ui
+                            .add(
+                                egui::DragValue::new(&mut api_settings.port)
+                                    .clamp_range(1..=u16::MAX),
+                            )
+                            .labelled_by(port_label.id);
+                        // Wait until the user lets go so every port that's dragged past isn't bound.
+                        api_settings_changed |= port_field.drag_released()
+                            || (port_field.changed() && !port_field.dragged());
+                    });
+                    ui.horizontal(|ui| {
+                        let token_label = ui.label(translations.get("settings-api-token"));
+                        let token_field = describe(
+                            ui.add(
+                                egui::TextEdit::singleline(&mut api_settings.token).password(true),
+                            )
+                            .labelled_by(token_label.id),
+                            translations.get("settings-api-token-hover"),
+                        );
+                        // Wait until the user's done typing so the server isn't restarted on every key.
+                        api_settings_changed |= token_field.lost_focus();
+                    });
+                    match (&api_server, &api_error) {
+                        (Some(api_server), _) => {
+                            let mut listening_args = FluentArgs::new();
+                            listening_args
+                                .set("address", format!("http://127.0.0.1:{}", api_server.port));
+                            ui.label(
+                                translations
+                                    .get_with_args("settings-api-listening", &listening_args),
+                            );
+                        }
+                        (None, Some(api_error)) => {
+                            announce_changes(&error_label(
+                                ui,
+                                &translate_error(api_error, translations),
+                            ));
+                        }
+                        (None, None) => (),
+                    }
+                }
+            });
+
+        // Hash whichever file the user drops onto FolSum.
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(dropped_path) = ctx.input(|input| {
+            input
+                .raw
+                .dropped_files
+                .iter()
+                .find_map(|dropped_file| dropped_file.path.clone())
+        }) {
+            *quick_hash_open = true;
+            *quick_hash_status = hash_file_in_background(
+                dropped_path,
+                &summarization_options.read_buffer_limits,
+                quick_hash,
+            );
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        egui::Window::new(translations.get("quick-hash-title"))
+            // Keep the window's position when its title changes language.
+            .id(egui::Id::new("quick_hash_window"))
+            .open(quick_hash_open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(translations.get("quick-hash-instructions"));
+                if ui
+                    .button(translations.get("quick-hash-choose-file"))
+                    .clicked()
+                {
+                    if let Some(path) = FileDialog::new().pick_file() {
+                        *quick_hash_status = hash_file_in_background(
+                            path,
+                            &summarization_options.read_buffer_limits,
+                            quick_hash,
+                        );
+                    }
+                }
+                // Don't show hashes until there's a file to hash.
+                let latest_hash: Option<QuickHash> = lock_shared(quick_hash).clone();
+                if let Some(latest_hash) = latest_hash {
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        let file_label = ui.label(translations.get("quick-hash-file"));
+                        ui.monospace(latest_hash.file_path.display().to_string())
+                            .labelled_by(file_label.id);
+                    });
+                    let hash_status: WorkerStatus = lock_shared(quick_hash_status).clone();
+                    match (&latest_hash.hash_result, hash_status) {
+                        // Say why if hashing died before it could hand over any hashes.
+                        (None, WorkerStatus::Failed(failure_reason)) => {
+                            announce_changes(&error_label(
+                                ui,
+                                &translate_error(&failure_reason, translations),
+                            ));
+                        }
+                        (None, _) => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(translations.get("quick-hash-hashing"));
+                            });
+                        }
+                        (Some(Err(hash_error)), _) => {
+                            announce_changes(&error_label(
+                                ui,
+                                &translate_error(hash_error, translations),
+                            ));
+                        }
+                        (Some(Ok(file_hashes)), _) => {
+                            egui::Grid::new("quick_hash_grid").show(ui, |ui| {
+                                for (algorithm_name, file_hash) in
+                                    [("MD5", &file_hashes.md5), ("SHA-256", &file_hashes.sha256)]
+                                {
+                                    let algorithm_label = ui.label(algorithm_name);
+                                    hash_label(ui, file_hash, translations)
+                                        .labelled_by(algorithm_label.id);
+                                    if ui.button(translations.get("quick-hash-copy")).clicked() {
+                                        ui.output_mut(|output| {
+                                            output.copied_text = file_hash.to_string()
+                                        });
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                let compare_label =
+                                    ui.label(translations.get("quick-hash-compare"));
+                                ui.text_edit_singleline(provided_hash)
+                                    .labelled_by(compare_label.id);
+                            });
+                            // Say whether the pasted hash matches once there's something to compare.
+                            if !provided_hash.trim().is_empty() {
+                                let comparison_label = match file_hashes.matches(provided_hash) {
+                                    Some(true) => {
+                                        ui.label(format!("✔ {}", translations.get("hash-match")))
+                                    }
+                                    Some(false) => {
+                                        error_label(ui, &translations.get("hash-mismatch"))
+                                    }
+                                    None => error_label(ui, &translations.get("hash-unrecognized")),
+                                };
+                                announce_changes(&comparison_label);
+                            }
+                        }
+                    }
+                }
+            });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(report_path) = crash_report.clone() {
+            egui::Window::new(translations.get("crash-report-title"))
+                .id(egui::Id::new("crash_report_window"))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(translations.get("crash-report-description"));
+                    ui.monospace(report_path.display().to_string());
+                    ui.horizontal(|ui| {
+                        if ui.button(translations.get("crash-report-open")).clicked() {
+                            if let Err(open_error) = open_path(&report_path) {
+                                announce_changes(&error_label(
+                                    ui,
+                                    &translate_error(open_error, translations),
+                                ));
+                            }
+                        }
+                        if ui.button(translations.get("crash-report-copy")).clicked() {
+                            if let Ok(report_contents) = std::fs::read_to_string(&report_path) {
+                                ui.output_mut(|output| output.copied_text = report_contents);
+                            }
+                        }
+                        if ui
+                            .button(translations.get("crash-report-dismiss"))
+                            .clicked()
+                        {
+                            *acknowledged_crash_report = crash_report.take();
+                        }
+                    });
+                });
+        }
+
+        // Show that FolSum's waiting for background work to stop, and close once it has or once it's out of time.
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(stopping_since) = *stopping_since {
+            let still_running: usize = running_workers();
+            let timed_out: bool =
+                stopping_since.elapsed() >= Duration::from_secs(*shutdown_timeout_secs);
+            match still_running == 0 || timed_out {
+                true => _frame.close(),
+                false => {
+                    egui::Window::new(translations.get("closing-title"))
+                        .id(egui::Id::new("closing_window"))
+                        .collapsible(false)
+                        .resizable(false)
+                        .show(ctx, |ui| {
+                            let mut closing_args = FluentArgs::new();
+                            closing_args
+                                .set("running", format_count(still_running as u64, *language));
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(
+                                    translations
+                                        .get_with_args("closing-description", &closing_args),
+                                );
+                            });
+                        });
+                    ctx.request_repaint();
+                }
+            }
+        }
+
+        // Whether the findings pane asked for its selected findings to be exported, which the export buttons handle.
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut findings_export_clicked: bool = false;
+        // List the files that didn't check out, grouped by what went wrong, so they don't have to be hunted for.
+        egui::Window::new(translations.get("findings-title"))
+            // Keep the window's position when its title changes language.
+            .id(egui::Id::new("findings_window"))
+            .open(findings_open)
+            .show(ctx, |ui| {
+                ui.label(translations.get("findings-hint"));
+                let locked_findings = lock_shared(checksum_findings);
+                let is_undecided = |finding: &&ChecksumFinding| {
+                    !finding_decisions.ignores(finding) && !finding_decisions.accepts(finding)
+                };
+                let drifted_findings: Vec<&ChecksumFinding> = locked_findings
+                    .iter()
+                    .filter(|finding| finding.has_metadata_drift())
+                    .filter(is_undecided)
+                    .collect();
+                // Findings that the user has dealt with are kept out of the way, but can still be looked at.
+                let decided_findings = |is_decided: &dyn Fn(&ChecksumFinding) -> bool| {
+                    locked_findings
+                        .iter()
+                        .filter(|finding| {
+                            DISCREPANCY_OUTCOMES.contains(&finding.outcome)
+                                || finding.has_metadata_drift()
+                        })
+                        .filter(|finding| is_decided(finding))
+                        .collect::<Vec<&ChecksumFinding>>()
+                };
+                let ignored_findings: Vec<&ChecksumFinding> =
+                    decided_findings(&|finding| finding_decisions.ignores(finding));
+                let accepted_findings: Vec<&ChecksumFinding> = decided_findings(&|finding| {
+                    !finding_decisions.ignores(finding) && finding_decisions.accepts(finding)
+                });
+                // Metadata drift goes after discrepancies because the content of those files held up.
+                let finding_groups: Vec<(&str, Vec<&ChecksumFinding>, bool)> =
+                    group_discrepancies(&locked_findings)
+                        .into_iter()
+                        .map(|(outcome, grouped_findings)| {
+                            let undecided_findings: Vec<&ChecksumFinding> =
+                                grouped_findings.into_iter().filter(is_undecided).collect();
+                            (outcome_id(outcome), undecided_findings, true)
+                        })
+                        .chain([
+                            ("findings-metadata-drift", drifted_findings, true),
+                            ("findings-ignored", ignored_findings, false),
+                            ("findings-accepted", accepted_findings, false),
+                        ])
+                        .filter(|(_, grouped_findings, _)| !grouped_findings.is_empty())
+                        .collect();
+                if finding_groups.is_empty() {
+                    ui.label(translations.get("findings-none"));
+                }
+                // Forget selected findings that aren't around anymore, like after a new summarization.
+                selected_findings.retain(|selected_file| {
+                    locked_findings
+                        .iter()
+                        .any(|finding| finding.listed_file == *selected_file)
+                });
+                let chosen_findings: Vec<&ChecksumFinding> = locked_findings
+                    .iter()
+                    .filter(|finding| selected_findings.contains(&finding.listed_file))
+                    .collect();
+                let reverifying: bool = *lock_shared(reverify_status) == WorkerStatus::InProgress;
+                ui.horizontal_wrapped(|ui| {
+                    ui.add_enabled_ui(!chosen_findings.is_empty(), |ui| {
+                        let ignore_button = describe(
+                            ui.button(translations.get("findings-ignore")),
+                            translations.get("findings-ignore-hover"),
+                        );
+                        if ignore_button.clicked() {
+                            finding_decisions.ignore(&chosen_findings);
+                            #[cfg(not(target_arch = "wasm32"))]
+                            record_findings_action("ignore finding", &chosen_findings);
+                        }
+                        let accept_button = describe(
+                            ui.button(translations.get("findings-accept")),
+                            translations.get("findings-accept-hover"),
+                        );
+                        if accept_button.clicked() {
+                            finding_decisions.accept(&chosen_findings);
+                            #[cfg(not(target_arch = "wasm32"))]
+                            record_findings_action("accept finding", &chosen_findings);
+                        }
+                        if ui.button(translations.get("findings-undo")).clicked() {
+                            finding_decisions.undo(&chosen_findings);
+                            #[cfg(not(target_arch = "wasm32"))]
+                            record_findings_action("undo finding decision", &chosen_findings);
+                        }
+                        let reverify_button = describe(
+                            ui.add_enabled(
+                                !reverifying,
+                                egui::Button::new(translations.get("findings-reverify")),
+                            ),
+                            translations.get("findings-reverify-hover"),
+                        );
+                        if reverify_button.clicked() {
+                            let chosen_files: Vec<PathBuf> = chosen_findings
+                                .iter()
+                                .map(|finding| finding.listed_file.clone())
+                                .collect();
+                            // Check files the way the last summarization did, including what `.folsum.toml` pinned.
+                            let pinned_options: SummarizationOptions = match project_file {
+                                Some(project_file) => project_file.pin(summarization_options),
+                                None => summarization_options.clone(),
+                            };
+                            *reverify_error = reverify_findings(
+                                checksum_findings,
+                                &chosen_files,
+                                &pinned_options,
+                                reverify_status,
+                            )
+                            .err();
+                            #[cfg(not(target_arch = "wasm32"))]
+                            if reverify_error.is_none() {
+                                *reverified_files = chosen_files;
+                            }
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button(translations.get("findings-export")).clicked() {
+                            findings_export_clicked = true;
+                        }
+                    });
+                });
+                if let Some(reverify_error) = reverify_error {
+                    announce_changes(&error_label(
+                        ui,
+                        &translate_error(reverify_error, translations),
+                    ));
+                }
+                // Record files that were verified again once it's known whether that worked.
+                #[cfg(not(target_arch = "wasm32"))]
+                if !reverified_files.is_empty() {
+                    let reverify_action: Option<&str> = match *lock_shared(reverify_status) {
+                        WorkerStatus::Finished => Some("reverify"),
+                        WorkerStatus::Failed(_) => Some("reverify failed"),
+                        _ => None,
+                    };
+                    if let Some(reverify_action) = reverify_action {
+                        for reverified_file in reverified_files.drain(..) {
+                            record_action(reverify_action, &reverified_file);
+                        }
+                    }
+                }
+                show_worker_status(
+                    ui,
+                    &lock_shared(reverify_status),
+                    translations,
+                    "findings-reverify-in-progress",
+                    Some("findings-reverify-finished"),
+                    "findings-reverify-failed",
+                );
+                // Shift-click selects findings in the order that they're listed, across groups.
+                let listed_order: Vec<PathBuf> = finding_groups
+                    .iter()
+                    .flat_map(|(_, grouped_findings, _)| grouped_findings)
+                    .map(|finding| finding.listed_file.clone())
+                    .collect();
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        for (group_id, grouped_findings, open_by_default) in finding_groups {
+                            let mut group_args = FluentArgs::new();
+                            group_args.set("category", translations.get(group_id));
+                            group_args.set("finding_count", grouped_findings.len());
+                            group_args.set(
+                                "shown_finding_count",
+                                format_count(grouped_findings.len() as u64, *language),
+                            );
+                            egui::CollapsingHeader::new(
+                                translations.get_with_args("findings-group", &group_args),
+                            )
+                            .id_source(("findings_group", group_id))
+                            .default_open(open_by_default)
+                            .show(ui, |ui| {
+                                let locked_rules = lock_shared(severity_rules);
+                                for finding in grouped_findings {
+                                    let finding_label = ui
+                                        .horizontal(|ui| {
+                                            severity_label(
+                                                ui,
+                                                locked_rules.severity(finding),
+                                                translations,
+                                            );
+                                            ui.selectable_label(
+                                                selected_findings.contains(&finding.listed_file),
+                                                finding.listed_file.display().to_string(),
+                                            )
+                                        })
+                                        .inner;
+                                    if finding_label.clicked() {
+                                        select_row(
+                                            selected_findings,
+                                            findings_anchor,
+                                            &listed_order,
+                                            &finding.listed_file,
+                                            ui.input(|input| input.modifiers),
+                                        );
+                                    }
+                                    // Jump to the row that the file was counted in.
+                                    if finding_label.double_clicked() {
+                                        let finding_extension: String =
+                                            extension_name(&finding.listed_file);
+                                        selected_extensions.clear();
+                                        selected_extensions.insert(finding_extension.clone());
+                                        *selection_anchor = Some(finding_extension.clone());
+                                        *scroll_to_extension = Some(finding_extension);
+                                        // Grouped results don't have rows to jump to.
+                                        *group_by_folder = false;
+                                    }
+                                    // Show what was actually found on hover.
+                                    describe(
+                                        finding_label,
+                                        finding_details(finding, translations, *language),
+                                    );
+                                }
+                            });
+                        }
+                    });
+            });
+
+        egui::SidePanel::left("left_panel")
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.heading(translations.get("choose-directory"));
+
+                // Don't add a directory picker when compiling for web.
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let open_button = ui.button(translations.get("open-directory"));
+                    step_rects.insert(OnboardingStep::ChooseDirectory, open_button.rect);
+                    if open_button.clicked() {
+                        if let Some(path) = FileDialog::new().pick_folder() {
+                            *summarization_path = Arc::new(Mutex::new(Some(path)));
+                        }
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    let locked_path: &Option<PathBuf> = &lock_shared(summarization_path);
+                    // Check if the user has picked a directory to summarize.
+                    let shown_path: String = match locked_path {
+                        Some(the_path) => the_path.as_os_str().to_str().unwrap().to_string(),
+                        None => translations.get("no-directory-selected"),
+                    };
+                    let chosen_label = ui.label(translations.get("chosen-directory"));
+                    // Display the user's chosen directory in monospace font.
+                    ui.monospace(shown_path).labelled_by(chosen_label.id);
+                });
+
+                ui.separator();
+
+                // Don't let a second summarization start while one's running.
+                let summarize_button = ui.add_enabled(
+                    *lock_shared(summarization_status) != WorkerStatus::InProgress,
+                    egui::Button::new(translations.get("summarize")),
+                );
+                step_rects.insert(OnboardingStep::Summarize, summarize_button.rect);
+                // Pick up the options that the chosen directory pins in its `.folsum.toml` (if it has one).
+                if summarize_button.clicked() {
+                    if let Some(chosen_directory) = lock_shared(summarization_path).clone() {
+                        (*project_file, *project_file_error) =
+                            match read_project_file(&chosen_directory) {
+                                Ok(chosen_project_file) => (chosen_project_file, None),
+                                Err(read_error) => (None, Some(read_error)),
+                            };
+                    }
+                }
+                let pinned_options: SummarizationOptions = match project_file {
+                    Some(project_file) => project_file.pin(summarization_options),
+                    None => summarization_options.clone(),
+                };
+                // Check with the user before tying up their computer with a huge or system directory, once it's
+                // been sized up in the background.
+                #[cfg(not(target_arch = "wasm32"))]
+                let chosen_directory: Option<PathBuf> = lock_shared(summarization_path).clone();
+                #[cfg(not(target_arch = "wasm32"))]
+                let summarization_confirmed: bool = match (
+                    summarize_button.clicked() && project_file_error.is_none(),
+                    chosen_directory,
+                ) {
+                    (true, Some(chosen_directory)) => {
+                        *pending_summarization = Some(estimate_before_summarizing(
+                            chosen_directory,
+                            pinned_options.clone(),
+                        ));
+                        false
+                    }
+                    // Let the summarization itself say that a directory's needed.
+                    (true, None) => true,
+                    (false, _) => false,
+                };
+                #[cfg(target_arch = "wasm32")]
+                let summarization_confirmed: bool =
+                    summarize_button.clicked() && project_file_error.is_none();
+                if summarization_confirmed {
+                    let _summarization_result = summarize_directory(
+                        summarization_path,
+                        &pinned_options,
+                        summarization_state,
+                    );
+                };
+                #[cfg(not(target_arch = "wasm32"))]
+                if pending_summarization.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(translations.get("summarization-estimating"));
+                    });
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(((directory, estimated_options), directory_estimate)) =
+                    Pending::take_finished(pending_summarization)
+                {
+                    // Assume that directories which couldn't be sized up are enormous, so the user's still asked.
+                    let directory_estimate: DirectoryEstimate =
+                        directory_estimate.unwrap_or_default();
+                    if confirm_summarization(
+                        &directory,
+                        &directory_estimate,
+                        translations,
+                        *language,
+                    ) {
+                        *lock_shared(summarization_path) = Some(directory);
+                        let summarization_result = summarize_directory(
+                            summarization_path,
+                            &estimated_options,
+                            summarization_state,
+                        );
+                        if let (Ok(()), Some(directory)) =
+                            (summarization_result, &*lock_shared(summarization_path))
+                        {
+                            record_action("summarize", directory);
+                        }
+                    }
+                }
+                show_worker_status(
+                    ui,
+                    &lock_shared(summarization_status),
+                    translations,
+                    "summarization-in-progress",
+                    None,
+                    "summarization-failed",
+                );
+                if let Some(project_file_error) = project_file_error {
+                    announce_changes(&error_label(
+                        ui,
+                        &translate_error(project_file_error, translations),
+                    ));
+                }
+                // Say which options came from the directory, since they don't match what's in settings.
+                if let Some(project_file) = project_file {
+                    let pinned_settings: Vec<String> = project_file.pinned_settings();
+                    if !pinned_settings.is_empty() {
+                        let mut pinned_args = FluentArgs::new();
+                        pinned_args.set("project_file", PROJECT_FILE_NAME);
+                        describe(
+                            ui.label(translations.get_with_args("pinned-options", &pinned_args)),
+                            pinned_settings.join("\n"),
+                        );
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    let locked_time_taken = lock_shared(time_taken);
+                    let mut summary_args = FluentArgs::new();
+                    // Pass the raw count for picking plural forms, and the formatted count for showing.
+                    summary_args.set("file_count", *total_files);
+                    summary_args.set("shown_file_count", format_count(*total_files, *language));
+                    summary_args.set("duration", format_duration(&locked_time_taken));
+                    // Tell screen reader users when a summarization finishes.
+                    announce_changes(
+                        &ui.label(translations.get_with_args("summarized-files", &summary_args)),
+                    );
+                });
+                // Show how much data was inventoried, not just how many files.
+                let total_bytes: u64 = lock_shared(extension_bytes).values().sum();
+                let mut size_args = FluentArgs::new();
+                size_args.set("total_size", format_bytes(total_bytes, *language));
+                // Sparse files, like virtual machine disks, take up less room on disk than their length.
+                let locked_totals = lock_shared(storage_totals);
+                size_args.set(
+                    "allocated_size",
+                    format_bytes(locked_totals.allocated_bytes, *language),
+                );
+                ui.label(translations.get_with_args("summarized-bytes", &size_args));
+                if locked_totals.sparse_files > 0 {
+                    let mut sparse_args = FluentArgs::new();
+                    sparse_args.set("sparse_count", locked_totals.sparse_files);
+                    sparse_args.set(
+                        "shown_sparse_count",
+                        format_count(u64::from(locked_totals.sparse_files), *language),
+                    );
+                    ui.label(translations.get_with_args("sparse-files", &sparse_args));
+                }
+                drop(locked_totals);
+                // Break down where the time went, for planning how long bigger runs will take.
+                egui::CollapsingHeader::new(translations.get("performance"))
+                    .id_source("performance")
+                    .show(ui, |ui| {
+                        let locked_timings = lock_shared(phase_timings);
+                        egui::Grid::new("performance_grid").show(ui, |ui| {
+                            for (phase_id, phase_time) in [
+                                ("performance-walk", Some(locked_timings.walk)),
+                                (
+                                    "performance-checksum-verification",
+                                    Some(locked_timings.checksum_verification),
+                                ),
+                                ("performance-export-write", locked_timings.export_write),
+                            ] {
+                                let phase_label = ui.label(translations.get(phase_id));
+                                let shown_time: String = phase_time.as_ref().map_or_else(
+                                    || translations.get("performance-not-run"),
+                                    format_duration,
+                                );
+                                ui.label(shown_time).labelled_by(phase_label.id);
+                                ui.end_row();
+                            }
+                        });
+                    });
+                // Explain why the total might be lower than the number of directory entries.
+                let skipped_hardlinks: u32 = *lock_shared(hardlink_count);
+                if skipped_hardlinks > 0 {
+                    let mut hardlink_args = FluentArgs::new();
+                    hardlink_args.set(
+                        "hardlink_count",
+                        format_count(u64::from(skipped_hardlinks), *language),
+                    );
+                    announce_changes(
+                        &ui.label(translations.get_with_args("skipped-hardlinks", &hardlink_args)),
+                    );
+                }
+                // Show how far checking listed files has gotten, since hashing them takes much longer than counting.
+                let progress: VerificationProgress = *lock_shared(verification_progress);
+                if progress.checked < progress.listed {
+                    let mut progress_args = FluentArgs::new();
+                    progress_args.set("checked", format_count(progress.checked as u64, *language));
+                    progress_args.set("listed", format_count(progress.listed as u64, *language));
+                    ui.add(
+                        egui::ProgressBar::new(progress.fraction())
+                            .text(translations.get_with_args("checksum-progress", &progress_args)),
+                    );
+                }
+                // Sum up how the files listed in checksum files held up.
+                let locked_findings = lock_shared(checksum_findings);
+                if !locked_findings.is_empty() {
+                    let mut checksum_args = FluentArgs::new();
+                    for (argument_name, counted_outcome) in [
+                        ("verified", ChecksumOutcome::Verified),
+                        ("mismatched", ChecksumOutcome::Mismatch),
+                        ("missing", ChecksumOutcome::Missing),
+                        ("unreadable", ChecksumOutcome::Unreadable),
+                        ("locked", ChecksumOutcome::Locked),
+                        ("outside", ChecksumOutcome::OutsideDirectory),
+                    ] {
+                        let outcome_count: usize = locked_findings
+                            .iter()
+                            .filter(|finding| finding.outcome == counted_outcome)
+                            .count();
+                        checksum_args
+                            .set(argument_name, format_count(outcome_count as u64, *language));
+                    }
+                    let size_only_count: usize = locked_findings
+                        .iter()
+                        .filter(|finding| {
+                            matches!(finding.outcome, ChecksumOutcome::SizeOnly { .. })
+                        })
+                        .count();
+                    checksum_args.set("size_only", format_count(size_only_count as u64, *language));
+                    announce_changes(
+                        &ui.label(translations.get_with_args("checksum-summary", &checksum_args)),
+                    );
+                    // Judge content and metadata separately so touched timestamps don't fail the whole audit.
+                    let verdict: AuditVerdict =
+                        audit_verdict(&locked_findings, &lock_shared(severity_rules));
+                    let content_verdict_id: &str = match verdict.content {
+                        ContentVerdict::Passed => "audit-content-passed",
+                        ContentVerdict::Failed => "audit-content-failed",
+                        ContentVerdict::Unconfirmed => "audit-content-unconfirmed",
+                    };
+                    let content_verdict_label = match verdict.content {
+                        ContentVerdict::Passed => ui.label(translations.get(content_verdict_id)),
+                        _ => error_label(ui, &translations.get(content_verdict_id)),
+                    };
+                    announce_changes(&content_verdict_label);
+                    // Say when the audit finished and how long it took, so it's clear which run the verdict's from.
+                    if let Some(report) = &*lock_shared(audit_report) {
+                        let mut report_args = FluentArgs::new();
+                        report_args.set(
+                            "checksum_files",
+                            format_count(report.checksum_file_count as u64, *language),
+                        );
+                        report_args.set(
+                            "finished_at",
+                            report.finished_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                        );
+                        report_args.set("duration", format_duration(&report.duration));
+                        ui.label(translations.get_with_args("audit-report", &report_args));
+                    }
+                    match verdict.metadata_drift.is_empty() {
+                        true => {
+                            ui.label(translations.get("audit-metadata-unchanged"));
+                        }
+                        false => {
+                            let mut drift_args = FluentArgs::new();
+                            drift_args.set("drift_count", verdict.metadata_drift.len());
+                            drift_args.set(
+                                "shown_drift_count",
+                                format_count(verdict.metadata_drift.len() as u64, *language),
+                            );
+                            ui.label(
+                                translations.get_with_args("audit-metadata-drift", &drift_args),
+                            );
+                        }
+                    }
+                    // Open the findings pane to look into the files that didn't check out.
+                    let has_findings: bool = !group_discrepancies(&locked_findings).is_empty()
+                        || !verdict.metadata_drift.is_empty();
+                    if has_findings && ui.button(translations.get("findings-show")).clicked() {
+                        *findings_open = true;
+                    }
+                }
+                drop(locked_findings);
+                // List data that's hidden alongside files so it's not overlooked.
+                let locked_streams = lock_shared(extra_streams);
+                if !locked_streams.is_empty() {
+                    let mut stream_args = FluentArgs::new();
+                    stream_args.set(
+                        "stream_count",
+                        format_count(locked_streams.len() as u64, *language),
+                    );
+                    egui::CollapsingHeader::new(
+                        translations.get_with_args("extra-streams", &stream_args),
+                    )
+                    .id_source("extra_streams")
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .id_source("extra_streams_scroll")
+                            .max_height(200.0)
+                            .show(ui, |ui| {
+                                for extra_stream in locked_streams.iter() {
+                                    ui.horizontal(|ui| {
+                                        ui.label(format!(
+                                            "{}:{} ({})",
+                                            extra_stream.file.display(),
+                                            extra_stream.stream_name,
+                                            format_bytes(extra_stream.stream_size, *language),
+                                        ));
+                                        match &extra_stream.hashes {
+                                            Some(stream_hashes) => {
+                                                hash_label(ui, &stream_hashes.sha256, translations);
+                                            }
+                                            None => {
+                                                ui.label(translations.get("checksum-unreadable"));
+                                            }
+                                        }
+                                    });
+                                }
+                            });
+                    });
+                }
+                drop(locked_streams);
+
+                ui.separator();
+
+                // Don't offer read-only mode when compiling for web because there's nothing to export.
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    describe(
+                        ui.checkbox(read_only_mode, translations.get("read-only-mode")),
+                        translations.get("read-only-mode-hover"),
+                    );
+                    if *read_only_mode {
+                        // Ask the user where exports should go instead of the chosen directory.
+                        if ui
+                            .button(translations.get("choose-reports-directory"))
+                            .clicked()
+                        {
+                            if let Some(path) = FileDialog::new()
+                                .set_title(translations.get("choose-reports-directory-title"))
+                                .pick_folder()
+                            {
+                                *reports_directory = Some(path);
+                            }
+                        }
+                        ui.horizontal(|ui| {
+                            let shown_directory: String = match reports_directory {
+                                Some(the_path) => the_path.display().to_string(),
+                                None => translations.get("no-reports-directory-selected"),
+                            };
+                            let reports_label = ui.label(translations.get("reports-directory"));
+                            ui.monospace(shown_directory).labelled_by(reports_label.id);
+                        });
+                    }
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.horizontal(|ui| {
+                        let case_id_label = ui.label(translations.get("case-id"));
+                        ui.text_edit_singleline(case_id)
+                            .labelled_by(case_id_label.id);
+                    });
+                    ui.horizontal(|ui| {
+                        let operator_label = ui.label(translations.get("operator"));
+                        ui.text_edit_singleline(operator_name)
+                            .labelled_by(operator_label.id);
+                    });
+                    // Exports also record the OS account, whatever name is typed in above.
+                    ui.horizontal(|ui| {
+                        let os_user_label = ui.label(translations.get("os-user"));
+                        ui.monospace(os_user_name()).labelled_by(os_user_label.id);
+                    });
+                    let notes_label = ui.label(translations.get("notes"));
+                    ui.text_edit_multiline(run_note).labelled_by(notes_label.id);
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                let export_all_clicked: bool = {
+                    let export_button = ui.button(translations.get("export-csv"));
+                    step_rects.insert(OnboardingStep::Export, export_button.rect);
+                    export_button.clicked()
+                };
+                #[cfg(not(target_arch = "wasm32"))]
+                let export_selected_clicked: bool = describe(
+                    ui.add_enabled(
+                        !selected_extensions.is_empty(),
+                        egui::Button::new(translations.get("export-selected")),
+                    ),
+                    translations.get("export-selected-hover"),
+                )
+                .clicked();
+                #[cfg(not(target_arch = "wasm32"))]
+                let export_spdx_clicked: bool = describe(
+                    ui.button(translations.get("export-spdx")),
+                    translations.get("export-spdx-hover"),
+                )
+                .clicked();
+                #[cfg(not(target_arch = "wasm32"))]
+                let export_stats_clicked: bool = describe(
+                    ui.button(translations.get("export-stats")),
+                    translations.get("export-stats-hover"),
+                )
+                .clicked();
+                #[cfg(not(target_arch = "wasm32"))]
+                let clicked_export: Option<ExportKind> = [
+                    (export_all_clicked, ExportKind::AllExtensions),
+                    (findings_export_clicked, ExportKind::SelectedFindings),
+                    (export_selected_clicked, ExportKind::SelectedExtensions),
+                    (export_spdx_clicked, ExportKind::Spdx),
+                    (export_stats_clicked, ExportKind::Statistics),
+                ]
+                .into_iter()
+                .find(|(export_clicked, _)| *export_clicked)
+                .map(|(_, export_kind)| export_kind);
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(export_kind) = clicked_export {
+                    let date_today: DateTime<FixedOffset> = export_time(*dates_in_utc);
+                    // Open the "Save export file as" dialog.
+                    let starting_directory = match (
+                        *read_only_mode,
+                        reports_directory.clone(),
+                        lock_shared(export_file).clone(),
+                    ) {
+                        // In read-only mode, open the export dialog in the reports directory.
+                        (true, Some(reports_directory), _) => reports_directory,
+                        // Open the export dialog in the same dir as the previous export.
+                        (_, _, Some(export_file)) => export_file.parent().unwrap().to_path_buf(),
+                        // Otherwise, if there was no previous export, then open the export dialog in the user's home dir.
+                        (_, _, None) => home_dir().expect("Failed to get user's home directory"),
+                    };
+                    // Fill in the user's filename template to suggest a name for the export.
+                    // Name exports the way the directory's `.folsum.toml` says to, if it does.
+                    let pinned_template: &String = project_file
+                        .as_ref()
+                        .and_then(|project_file| project_file.filename_template.as_ref())
+                        .unwrap_or(filename_template);
+                    let suggested_path: Result<PathBuf, &'static str> = create_export_path(
+                        &starting_directory,
+                        pinned_template,
+                        date_format,
+                        &lock_shared(summarization_path),
+                        case_id,
+                        &date_today,
+                    );
+                    // Don't open the export dialog if the template can't make a valid filename.
+                    *export_error = suggested_path.as_ref().err().copied();
+                    // SPDX documents are saved as `.spdx` files, and everything else as CSV.
+                    let (export_extension, export_title) = match export_kind {
+                        ExportKind::Spdx => ("spdx", translations.get("export-spdx-title")),
+                        ExportKind::Statistics => ("csv", translations.get("export-stats-title")),
+                        ExportKind::SelectedFindings => {
+                            ("csv", translations.get("export-findings-title"))
+                        }
+                        _ => ("csv", translations.get("export-csv-title")),
+                    };
+                    // Keep statistics and findings from taking the name that the extension counts export would get.
+                    let suggested_path: Result<PathBuf, &'static str> =
+                        suggested_path.map(|suggested_path| {
+                            let name_suffix: Option<&str> = match export_kind {
+                                ExportKind::Statistics => Some("_stats"),
+                                ExportKind::SelectedFindings => Some("_findings"),
+                                _ => None,
+                            };
+                            match name_suffix {
+                                Some(name_suffix) => {
+                                    let mut suffixed_name = suggested_path.into_os_string();
+                                    suffixed_name.push(name_suffix);
+                                    PathBuf::from(suffixed_name)
+                                }
+                                None => suggested_path,
+                            }
+                        });
+                    // Ask user where they'd like to save the export and what they'd like it to be called.
+                    if let Some(path) = suggested_path.ok().and_then(|suggested_path| {
+                        FileDialog::new()
+                            // Add the export's extension to the end of the user's chosen name.
+                            .add_filter(export_extension, &[export_extension])
+                            .set_title(export_title)
+                            // Open export dialogs in the last saved directory (if it exists), otherwise in the user's home directory.
+                            .set_directory(starting_directory)
+                            // Suggest the filename that was rendered from the user's template.
+                            .set_file_name(suggested_path.file_name().unwrap().to_string_lossy())
+                            .save_file()
+                    }) {
+                        // Only export if the user agreed to what happens to an existing file at that path.
+                        if let Some(confirmed_path) = confirm_export_path(path, translations) {
+                            // In read-only mode, forbid the export from landing inside of the summarized directory.
+                            let read_only_root: Option<PathBuf> = match read_only_mode {
+                                true => lock_shared(summarization_path).clone(),
+                                false => None,
+                            };
+                            // Record who ran the export and why in the export's header.
+                            let export_metadata = ExportMetadata {
+                                exported_at: Some(date_today),
+                                operator: operator_name.clone(),
+                                os_user: os_user_name(),
+                                case_id: case_id.clone(),
+                                note: run_note.clone(),
+                                pinned_settings: project_file
+                                    .as_ref()
+                                    .map_or_else(Vec::new, ProjectFile::pinned_settings),
+                                self_test: self_test.as_ref().map(SelfTest::summary),
+                                audit: lock_shared(audit_report).as_ref().map(AuditReport::summary),
+                            };
+                            // Only export the selected rows if that's what the user asked for.
+                            let exported_counts: Arc<Mutex<HashMap<String, u32>>> =
+                                match export_kind == ExportKind::SelectedExtensions {
+                                    true => Arc::new(Mutex::new(
+                                        lock_shared(extension_counts)
+                                            .iter()
+                                            .filter(|(extension_name, _)| {
+                                                selected_extensions.contains(*extension_name)
+                                            })
+                                            .map(|(extension_name, times_seen)| {
+                                                (extension_name.clone(), *times_seen)
+                                            })
+                                            .collect(),
+                                    )),
+                                    false => extension_counts.clone(),
+                                };
+                            let exported_findings: Vec<ChecksumFinding> =
+                                lock_shared(checksum_findings)
+                                    .iter()
+                                    .filter(|finding| {
+                                        selected_findings.contains(&finding.listed_file)
+                                    })
+                                    .cloned()
+                                    .collect();
+                            let selected_count: Option<usize> = (export_kind
+                                == ExportKind::SelectedExtensions)
+                                .then_some(selected_extensions.len());
+                            let filters: Vec<String> =
+                                applied_filters(&pinned_options, selected_count, translations);
+                            // Show what's about to be written, and where, before anything lands in the client's folder.
+                            let export_preview: Option<ExportPreview> = match export_kind {
+                                ExportKind::SelectedFindings => Some(findings_preview(
+                                    &exported_findings,
+                                    finding_decisions,
+                                    &lock_shared(severity_rules),
+                                )),
+                                // Counting the directory's files can take seconds, so SPDX exports are previewed in
+                                // the background, and started further down once the user's confirmed them.
+                                ExportKind::Spdx => {
+                                    let summarized_directory: Option<PathBuf> =
+                                        lock_shared(summarization_path).clone();
+                                    *pending_spdx_export =
+                                        Some(preview_spdx_export(SpdxExportRequest {
+                                            export_path: confirmed_path.clone(),
+                                            summarized_directory,
+                                            read_only_root: read_only_root.clone(),
+                                            export_metadata: export_metadata.clone(),
+                                            pinned_options: pinned_options.clone(),
+                                            filters: filters.clone(),
+                                        }));
+                                    None
+                                }
+                                ExportKind::Statistics => Some(stats_preview(
+                                    extension_counts,
+                                    extension_bytes,
+                                    inventory_stats,
+                                )),
+                                ExportKind::AllExtensions | ExportKind::SelectedExtensions => {
+                                    Some(csv_preview(&exported_counts, extension_bytes))
+                                }
+                            };
+                            let preview_confirmed: bool =
+                                export_preview.map_or(false, |export_preview| {
+                                    confirm_export_preview(
+                                        &confirmed_path,
+                                        &export_preview,
+                                        &filters,
+                                        translations,
+                                        *language,
+                                    )
+                                });
+                            if preview_confirmed {
+                                *export_file = Arc::new(Mutex::new(Some(confirmed_path)));
+                                *export_error = match export_kind {
+                                    ExportKind::Statistics => export_stats(
+                                        export_file,
+                                        extension_counts,
+                                        extension_bytes,
+                                        inventory_stats,
+                                        &read_only_root,
+                                        &export_metadata,
+                                        export_status,
+                                    ),
+                                    ExportKind::SelectedFindings => export_findings(
+                                        export_file,
+                                        &exported_findings,
+                                        finding_decisions,
+                                        &lock_shared(severity_rules),
+                                        &read_only_root,
+                                        &export_metadata,
+                                        export_status,
+                                    ),
+                                    _ => export_csv(
+                                        export_file,
+                                        &exported_counts,
+                                        &read_only_root,
+                                        &export_metadata,
+                                        export_status,
+                                    ),
+                                }
+                                .err();
+                                if export_error.is_none() {
+                                    *export_started = Some(Instant::now());
+                                    *export_action = Some(match export_kind {
+                                        ExportKind::Statistics => "export statistics",
+                                        ExportKind::SelectedFindings => "export findings",
+                                        _ => "export",
+                                    });
+                                }
+                            }
+                        }
+                    }
+                };
+
+                // Ask the user to confirm an SPDX export once its preview's ready, and start it if they do.
+                #[cfg(not(target_arch = "wasm32"))]
+                if pending_spdx_export.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(translations.get("export-previewing"));
+                    });
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some((spdx_request, export_preview)) =
+                    Pending::take_finished(pending_spdx_export)
+                {
+                    let preview_confirmed: bool = confirm_export_preview(
+                        &spdx_request.export_path,
+                        &export_preview.unwrap_or_default(),
+                        &spdx_request.filters,
+                        translations,
+                        *language,
+                    );
+                    if preview_confirmed {
+                        *export_file = Arc::new(Mutex::new(Some(spdx_request.export_path)));
+                        // Export the directory that was previewed, even if another's been chosen since.
+                        *export_error = export_spdx(
+                            export_file,
+                            &Arc::new(Mutex::new(spdx_request.summarized_directory)),
+                            &spdx_request.read_only_root,
+                            &spdx_request.export_metadata,
+                            &spdx_request.pinned_options,
+                            export_status,
+                        )
+                        .err();
+                        if export_error.is_none() {
+                            *export_started = Some(Instant::now());
+                            *export_action = Some("export spdx");
+                        }
+                    }
+                }
+
+                // Tell the user why their export didn't happen.
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(error_message) = export_error {
+                    announce_changes(&error_label(
+                        ui,
+                        &translate_error(error_message, translations),
+                    ));
+                }
+                // Time the export once it's done, which is accurate to a frame because the GUI redraws while it runs.
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(started) = *export_started {
+                    match *lock_shared(export_status) {
+                        WorkerStatus::Finished => {
+                            lock_shared(phase_timings).export_write = Some(started.elapsed());
+                            *export_started = None;
+                        }
+                        WorkerStatus::Failed(_) => *export_started = None,
+                        _ => (),
+                    }
+                }
+                // Record the export once it's been written, or that it wasn't, so the log never claims a missing file.
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(action) = *export_action {
+                    let logged_action: Option<String> = match *lock_shared(export_status) {
+                        WorkerStatus::Finished => Some(action.to_string()),
+                        WorkerStatus::Failed(_) => Some(format!("{} failed", action)),
+                        _ => None,
+                    };
+                    if let Some(logged_action) = logged_action {
+                        if let Some(export_path) = &*lock_shared(export_file) {
+                            record_action(&logged_action, export_path);
+                        }
+                        *export_action = None;
+                    }
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                show_worker_status(
+                    ui,
+                    &lock_shared(export_status),
+                    translations,
+                    "export-in-progress",
+                    Some("export-finished"),
+                    "export-failed",
+                );
+                // Give the user a way to get to the export once it's been written.
+                #[cfg(not(target_arch = "wasm32"))]
+                if *lock_shared(export_status) == WorkerStatus::Finished {
+                    if let Some(export_path) = lock_shared(export_file).clone() {
+                        let export_name: String = export_path.file_name().map_or_else(
+                            || export_path.display().to_string(),
+                            |export_name| export_name.to_string_lossy().to_string(),
+                        );
+                        let reveal_link = describe(
+                            ui.link(export_name),
+                            translations.get("export-reveal-hover"),
+                        );
+                        if reveal_link.clicked() {
+                            if let Err(reveal_error) = reveal_path(&export_path) {
+                                announce_changes(&error_label(
+                                    ui,
+                                    &translate_error(reveal_error, translations),
+                                ));
+                            }
+                        }
+                        if ui.button(translations.get("export-open-folder")).clicked() {
+                            let open_result = export_path
+                                .parent()
+                                .ok_or("The export isn't inside of a folder")
+                                .and_then(open_path);
+                            if let Err(open_error) = open_result {
+                                announce_changes(&error_label(
+                                    ui,
+                                    &translate_error(open_error, translations),
+                                ));
+                            }
+                        }
+                    }
+                }
+
+                ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
                     egui::warn_if_debug_build(ui);
                     ui.horizontal(|ui| {
                         ui.spacing_mut().item_spacing.x = 0.0;
-                        ui.label("written with love by ");
+                        ui.label(translations.get("written-with-love"));
                         ui.hyperlink_to("Brooke", "https://github.com/goingforbrooke");
                     });
                 });
             });
 
-        egui::CentralPanel::default().show(ctx, |ui| {
+        let results_panel = egui::CentralPanel::default().show(ctx, |ui| {
             ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
-                ui.heading("Summarization by File Extension");
+                ui.heading(translations.get("results-heading"));
+                ui.horizontal(|ui| {
+                    ui.checkbox(group_by_folder, translations.get("group-by-folder"));
+                    // Let the user pick which of the optional columns are shown in the results table.
+                    ui.menu_button(translations.get("columns-menu"), |ui| {
+                        for table_column in TableColumn::ALL {
+                            let mut column_shown: bool = !hidden_columns.contains(&table_column);
+                            if ui
+                                .checkbox(
+                                    &mut column_shown,
+                                    translations.get(table_column.heading_id()),
+                                )
+                                .changed()
+                            {
+                                match column_shown {
+                                    true => hidden_columns.retain(|hidden| hidden != &table_column),
+                                    false => hidden_columns.push(table_column),
+                                }
+                            }
+                        }
+                    });
+                    // Copy the results table for pasting into a spreadsheet. Grouped results aren't one table.
+                    let copy_button = ui.add_enabled(
+                        !*group_by_folder,
+                        egui::Button::new(translations.get("copy-tsv")),
+                    );
+                    if describe(copy_button, translations.get("copy-tsv-hover")).clicked() {
+                        let locked_exts = lock_shared(extension_counts);
+                        let results_tsv: String = table_as_tsv(
+                            &TableColumn::shown(hidden_columns),
+                            &sort_counts(&locked_exts),
+                            &lock_shared(extension_bytes),
+                            translations,
+                        );
+                        ui.output_mut(|output| output.copied_text = results_tsv);
+                    }
+                });
                 ui.separator();
             });
-            let locked_exts = extension_counts.lock().unwrap();
+            // Show a collapsible section of extension counts for each top-level folder instead of the flat table.
+            if *group_by_folder {
+                let locked_folders = lock_shared(folder_counts);
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    // List folders in natural order so they're easy to find, with `Disk 2` before `Disk 10`.
+                    for (folder_name, folder_extensions) in locked_folders
+                        .iter()
+                        .sorted_by(|(a_name, _), (b_name, _)| natural_cmp(a_name, b_name))
+                    {
+                        let folder_total: u64 = folder_extensions
+                            .values()
+                            .map(|count| u64::from(*count))
+                            .sum();
+                        let mut folder_args = FluentArgs::new();
+                        folder_args.set("folder_name", folder_name.as_str());
+                        folder_args.set("folder_total", folder_total);
+                        folder_args
+                            .set("shown_folder_total", format_count(folder_total, *language));
+                        egui::CollapsingHeader::new(
+                            translations.get_with_args("folder-total", &folder_args),
+                        )
+                        .id_source(folder_name)
+                        .show(ui, |ui| {
+                            egui::Grid::new(("folder_grid", folder_name))
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    // Sort extension counts in descending order, then alphabetically.
+                                    for (extension_name, times_seen) in
+                                        sort_counts(folder_extensions)
+                                    {
+                                        let extension_label = ui.label(extension_name);
+                                        // Read each count along with the extension that it's for.
+                                        ui.label(format_count(u64::from(*times_seen), *language))
+                                            .labelled_by(extension_label.id);
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    }
+                });
+                return;
+            }
+            let locked_exts = lock_shared(extension_counts);
+            let locked_bytes = lock_shared(extension_bytes);
             // Sort extension counts in descending order, then alphabetically.
             let ext_info = sort_counts(&*locked_exts);
-            // todo: Optimize table display by efficiently displaying viewable rows with `show_rows()`.
-            // Create a scrollable table that (inefficiently) shows all rows, whether they're in the "viewport" or not.
-            TableBuilder::new(ui)
-                .resizable(true)
-                .striped(true)
-                .column(Column::initial(150.0).at_least(150.0))
-                .column(Column::remainder().at_least(60.0))
-                .header(20.0, |mut header| {
-                    header.col(|ui| {
-                        ui.heading("File Extension");
-                    });
-                    header.col(|ui| {
-                        ui.heading("Occurrences");
-                    });
-                })
-                .body(|mut body| {
-                    for (extension_name, times_seen) in ext_info.iter() {
-                        body.row(15.0, |mut row| {
-                            row.col(|ui| {
-                                ui.label(extension_name.to_string());
+            // Create a scrollable table that only lays out the rows that are scrolled into view.
+            // Only show the columns that the user hasn't hidden. File extensions are always shown.
+            let shown_columns: Vec<TableColumn> = TableColumn::shown(hidden_columns);
+            // Remember column widths separately for each set of shown columns so hiding one doesn't reset the rest.
+            ui.push_id(&shown_columns, |ui| {
+                let mut table_builder = TableBuilder::new(ui)
+                    .resizable(true)
+                    .striped(true)
+                    .column(Column::initial(150.0).at_least(150.0));
+                // Bring a row into view once, like when a finding's double-clicked, then let the user scroll freely.
+                if let Some(target_extension) = scroll_to_extension.take() {
+                    if let Some(target_row) = ext_info
+                        .iter()
+                        .position(|(extension_name, _)| **extension_name == target_extension)
+                    {
+                        table_builder =
+                            table_builder.scroll_to_row(target_row, Some(egui::Align::Center));
+                    }
+                }
+                for column_index in 0..shown_columns.len() {
+                    // Let the last column fill the rest of the table's width.
+                    let column_size: Column = match column_index + 1 == shown_columns.len() {
+                        true => Column::remainder().at_least(60.0),
+                        false => Column::initial(100.0).at_least(60.0),
+                    };
+                    table_builder = table_builder.column(column_size);
+                }
+                // Remember each column heading so screen readers can read it along with the column's cells.
+                let mut heading_ids: Vec<egui::Id> = Vec::new();
+                table_builder
+                    .header(20.0, |mut header| {
+                        header.col(|ui| {
+                            ui.heading(translations.get("column-file-extension"));
+                        });
+                        for table_column in shown_columns.iter() {
+                            header.col(|ui| {
+                                heading_ids.push(
+                                    ui.heading(translations.get(table_column.heading_id())).id,
+                                );
                             });
+                        }
+                    })
+                    .body(|body| {
+                        body.rows(15.0, ext_info.len(), |row_index, mut row| {
+                            let (extension_name, times_seen) = ext_info[row_index];
+                            let total_bytes: u64 =
+                                locked_bytes.get(extension_name).copied().unwrap_or(0);
+                            let mut extension_label_id: Option<egui::Id> = None;
                             row.col(|ui| {
-                                ui.label(times_seen.to_string());
+                                let extension_label = ui.selectable_label(
+                                    selected_extensions.contains(extension_name),
+                                    extension_name.to_string(),
+                                );
+                                if extension_label.clicked() {
+                                    let listed_extensions: Vec<String> = ext_info
+                                        .iter()
+                                        .map(|(listed_extension, _)| listed_extension.to_string())
+                                        .collect();
+                                    select_row(
+                                        selected_extensions,
+                                        selection_anchor,
+                                        &listed_extensions,
+                                        extension_name,
+                                        ui.input(|input| input.modifiers),
+                                    );
+                                }
+                                extension_label_id = Some(extension_label.id);
                             });
+                            for (table_column, heading_id) in
+                                shown_columns.iter().zip(heading_ids.iter())
+                            {
+                                row.col(|ui| {
+                                    let mut cell_label = ui.label(table_column.cell_text(
+                                        times_seen,
+                                        total_bytes,
+                                        *language,
+                                    ));
+                                    // Read cells like "pdf, Occurrences, 12" instead of a bare number.
+                                    if let Some(extension_label_id) = extension_label_id {
+                                        cell_label = cell_label.labelled_by(extension_label_id);
+                                    }
+                                    cell_label.labelled_by(*heading_id);
+                                });
+                            }
                         });
+                    });
+                // Let the user right-click anywhere on the table to copy it.
+                ui.interact(
+                    ui.min_rect(),
+                    ui.id().with("table_context_menu"),
+                    egui::Sense::click(),
+                )
+                .context_menu(|ui| {
+                    if ui.button(translations.get("copy-tsv")).clicked() {
+                        let results_tsv: String =
+                            table_as_tsv(&shown_columns, &ext_info, &locked_bytes, translations);
+                        ui.output_mut(|output| output.copied_text = results_tsv);
+                        ui.close_menu();
                     }
                 });
+            });
+        });
+
+        step_rects.insert(OnboardingStep::ReviewResults, results_panel.response.rect);
+
+        // Walk the user through FolSum one step at a time, outlining the part of the GUI that each step's about.
+        if let Some(current_step) = *onboarding_step {
+            if let Some(step_rect) = step_rects.get(&current_step) {
+                let highlight_color: egui::Color32 = ctx.style().visuals.selection.bg_fill;
+                ctx.layer_painter(egui::LayerId::new(
+                    egui::Order::Foreground,
+                    egui::Id::new("onboarding_highlight"),
+                ))
+                .rect_stroke(
+                    step_rect.expand(4.0),
+                    4.0,
+                    egui::Stroke::new(3.0, highlight_color),
+                );
+            }
+            let mut step_args = FluentArgs::new();
+            step_args.set("step", current_step.number());
+            step_args.set("step_count", OnboardingStep::ALL.len());
+            egui::Window::new(translations.get_with_args("onboarding-title", &step_args))
+                // Keep the window's position when its title changes.
+                .id(egui::Id::new("onboarding_window"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::RIGHT_BOTTOM, [-16.0, -16.0])
+                .show(ctx, |ui| {
+                    announce_changes(&ui.label(translations.get(current_step.message_id())));
+                    ui.horizontal(|ui| {
+                        let back_button = ui.add_enabled(
+                            current_step.previous().is_some(),
+                            egui::Button::new(translations.get("onboarding-back")),
+                        );
+                        if back_button.clicked() {
+                            *onboarding_step = current_step.previous();
+                        }
+                        let next_label: String = match current_step.next() {
+                            Some(_) => translations.get("onboarding-next"),
+                            None => translations.get("onboarding-done"),
+                        };
+                        if ui.button(next_label).clicked() {
+                            *onboarding_step = current_step.next();
+                            *onboarding_done = onboarding_step.is_none();
+                        }
+                        if ui.button(translations.get("onboarding-skip")).clicked() {
+                            *onboarding_step = None;
+                            *onboarding_done = true;
+                        }
+                    });
+                });
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(bundle_path) = save_diagnostics_to {
+            self.diagnostics_result = Some(self.save_diagnostics(bundle_path));
+        }
+        #[cfg(feature = "rest-api")]
+        if api_settings_changed {
+            self.sync_api_server();
+        }
+        if start_new_session {
+            self.new_session();
+        }
+    }
+}
+
+/// Storage that only keeps values in memory, for serializing the settings the same way eframe saves them.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct MemoryStorage {
+    values: HashMap<String, String>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl eframe::Storage for MemoryStorage {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.values.get(key).cloned()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        self.values.insert(key.to_string(), value);
+    }
+
+    fn flush(&mut self) {}
+}
+
+/// Update which rows (of the results table or the findings pane) are selected after one's clicked, the way file
+/// managers do.
+fn select_row<R: Clone + Eq + std::hash::Hash>(
+    selected_rows: &mut HashSet<R>,
+    selection_anchor: &mut Option<R>,
+    listed_rows: &[R],
+    clicked_row: &R,
+    modifiers: egui::Modifiers,
+) {
+    // Shift-click selects every row between the last clicked row and this one.
+    if modifiers.shift {
+        let row_position = |wanted_row: &R| {
+            listed_rows
+                .iter()
+                .position(|listed_row| listed_row == wanted_row)
+        };
+        if let Some((anchor_index, clicked_index)) = selection_anchor
+            .as_ref()
+            .and_then(row_position)
+            .zip(row_position(clicked_row))
+        {
+            // Shift-click replaces the selection unless Ctrl (Cmd on macOS) is held too.
+            if !modifiers.command {
+                selected_rows.clear();
+            }
+            let first_index: usize = anchor_index.min(clicked_index);
+            let last_index: usize = anchor_index.max(clicked_index);
+            selected_rows.extend(listed_rows[first_index..=last_index].iter().cloned());
+            return;
+        }
+    }
+    match modifiers.command {
+        // Ctrl-click (Cmd-click on macOS) adds or removes one row without touching the rest.
+        true => {
+            if !selected_rows.remove(clicked_row) {
+                selected_rows.insert(clicked_row.clone());
+            }
+        }
+        // A plain click selects only the clicked row.
+        false => {
+            selected_rows.clear();
+            selected_rows.insert(clicked_row.clone());
+        }
+    }
+    *selection_anchor = Some(clicked_row.clone());
+}
+
+/// Lay out rows of the results table as tab-separated values, which spreadsheets accept when pasted.
+fn table_as_tsv(
+    shown_columns: &[TableColumn],
+    table_rows: &[(&String, &u32)],
+    extension_bytes: &HashMap<String, u64>,
+    translations: &Translations,
+) -> String {
+    let mut header_cells: Vec<String> = vec![translations.get("column-file-extension")];
+    header_cells.extend(
+        shown_columns
+            .iter()
+            .map(|table_column| translations.get(table_column.heading_id())),
+    );
+    let mut tsv_lines: Vec<String> = vec![header_cells.join("\t")];
+    for (extension_name, times_seen) in table_rows {
+        // Tabs and newlines would split the extension across cells, so swap them for spaces.
+        let mut row_cells: Vec<String> = vec![extension_name.replace(['\t', '\n', '\r'], " ")];
+        // Copy raw numbers instead of formatted ones so spreadsheets can do math on them.
+        let total_bytes: u64 = extension_bytes.get(*extension_name).copied().unwrap_or(0);
+        row_cells.extend(
+            shown_columns
+                .iter()
+                .map(|table_column| table_column.cell_value(times_seen, total_bytes)),
+        );
+        tsv_lines.push(row_cells.join("\t"));
+    }
+    tsv_lines.join("\n") + "\n"
+}
+
+/// Show whether work that runs in a separate thread is still going, finished, or failed (and why).
+///
+/// Nothing's shown for finished work without a `finished_id` because its results speak for themselves.
+fn show_worker_status(
+    ui: &mut egui::Ui,
+    worker_status: &WorkerStatus,
+    translations: &Translations,
+    in_progress_id: &str,
+    finished_id: Option<&str>,
+    failed_id: &str,
+) {
+    match worker_status {
+        WorkerStatus::Idle => {}
+        WorkerStatus::InProgress => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                announce_changes(&ui.label(translations.get(in_progress_id)));
+            });
+        }
+        WorkerStatus::Finished => {
+            if let Some(finished_id) = finished_id {
+                announce_changes(&ui.label(format!("✔ {}", translations.get(finished_id))));
+            }
+        }
+        WorkerStatus::Failed(failure_reason) => {
+            let mut failure_args = FluentArgs::new();
+            failure_args.set("reason", translate_error(failure_reason, translations));
+            announce_changes(&error_label(
+                ui,
+                &translations.get_with_args(failed_id, &failure_args),
+            ));
+        }
+    }
+}
+
+/// Something that's waiting on a worker's result, like a directory estimate, before the user's asked about it.
+#[cfg(not(target_arch = "wasm32"))]
+struct Pending<C, R> {
+    // What to carry on with once the result's in, like the directory that's about to be summarized.
+    context: C,
+    result: Arc<Mutex<Option<R>>>,
+    status: Arc<Mutex<WorkerStatus>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<C, R: Send + 'static> Pending<C, R> {
+    /// Work out a result in a separate thread, so the GUI keeps drawing while it's worked out.
+    fn start<W>(context: C, work: W) -> Self
+    where
+        W: FnOnce() -> R + Send + 'static,
+    {
+        let result: Arc<Mutex<Option<R>>> = Arc::new(Mutex::new(None));
+        let status = Arc::new(Mutex::new(WorkerStatus::default()));
+        let result_copy = Arc::clone(&result);
+        spawn_worker(&status, move || {
+            *lock_shared(&result_copy) = Some(work());
+            Ok(())
+        });
+        Self {
+            context,
+            result,
+            status,
+        }
+    }
+
+    /// Take what's pending once its worker's done, with `None` for the result if the worker failed.
+    fn take_finished(pending: &mut Option<Self>) -> Option<(C, Option<R>)> {
+        let finished: bool = pending.as_ref().map_or(false, |pending| {
+            *lock_shared(&pending.status) != WorkerStatus::InProgress
+        });
+        match finished {
+            true => pending.take().map(|pending| {
+                let result: Option<R> = lock_shared(&pending.result).take();
+                (pending.context, result)
+            }),
+            false => None,
+        }
+    }
+}
+
+/// Size up a directory in the background before asking the user to confirm summarizing it.
+#[cfg(not(target_arch = "wasm32"))]
+fn estimate_before_summarizing(
+    directory: PathBuf,
+    pinned_options: SummarizationOptions,
+) -> Pending<(PathBuf, SummarizationOptions), DirectoryEstimate> {
+    let (estimated_directory, estimate_options) = (directory.clone(), pinned_options.clone());
+    Pending::start((directory, pinned_options), move || {
+        estimate_directory(
+            &estimated_directory,
+            &estimate_options,
+            ESTIMATE_TIME_BUDGET,
+        )
+    })
+}
+
+/// Ask the user to confirm before summarizing a system directory or one that's enormous.
+///
+/// Returns whether to go ahead with the summarization.
+#[cfg(not(target_arch = "wasm32"))]
+fn confirm_summarization(
+    directory: &Path,
+    directory_estimate: &DirectoryEstimate,
+    translations: &Translations,
+    language: Language,
+) -> bool {
+    let system_directory: bool = is_system_directory(directory);
+    // Don't bother the user about directories of a reasonable size.
+    if !system_directory && !directory_estimate.is_enormous() {
+        return true;
+    }
+    let mut description_args = FluentArgs::new();
+    description_args.set("directory", directory.display().to_string());
+    description_args.set(
+        "counted",
+        match directory_estimate.finished {
+            true => "all",
+            false => "some",
+        },
+    );
+    description_args.set(
+        "file_count",
+        format_count(directory_estimate.file_count, language),
+    );
+    description_args.set(
+        "total_size",
+        format_bytes(directory_estimate.total_bytes, language),
+    );
+    let mut description: String =
+        translations.get_with_args("large-directory-description", &description_args);
+    if system_directory {
+        description = format!(
+            "{}\n\n{}",
+            translations.get_with_args("system-directory-warning", &description_args),
+            description
+        );
+    }
+    let summarize_choice = MessageDialog::new()
+        .set_level(MessageLevel::Warning)
+        .set_title(translations.get("large-directory-title"))
+        .set_description(description)
+        .set_buttons(MessageButtons::YesNo)
+        .show();
+    summarize_choice == MessageDialogResult::Yes
+}
+
+/// Current time in the timezone that the user wants exports dated in.
+#[cfg(not(target_arch = "wasm32"))]
+fn export_time(dates_in_utc: bool) -> DateTime<FixedOffset> {
+    match dates_in_utc {
+        true => Utc::now().fixed_offset(),
+        false => Local::now().fixed_offset(),
+    }
+}
+
+/// Message ID of the name for how much a finding matters.
+fn severity_id(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "severity-info",
+        Severity::Warning => "severity-warning",
+        Severity::Critical => "severity-critical",
+    }
+}
+
+/// Show how much a finding matters, in the error color if it's critical so those stand out in long lists.
+fn severity_label(ui: &mut egui::Ui, severity: Severity, translations: &Translations) {
+    let shown_severity: String = translations.get(severity_id(severity));
+    match severity {
+        Severity::Critical => error_label(ui, &shown_severity),
+        _ => ui.weak(shown_severity),
+    };
+}
+
+/// Message ID of the name for what went wrong with a listed file.
+fn outcome_id(outcome: ChecksumOutcome) -> &'static str {
+    match outcome {
+        ChecksumOutcome::Mismatch => "checksum-mismatch",
+        ChecksumOutcome::Missing => "checksum-missing",
+        ChecksumOutcome::Locked => "checksum-locked",
+        ChecksumOutcome::OutsideDirectory => "checksum-outside-directory",
+        _ => "checksum-unreadable",
+    }
+}
+
+/// Record what the user decided about findings in the action log, one line per listed file.
+#[cfg(not(target_arch = "wasm32"))]
+fn record_findings_action(action: &str, findings: &[&ChecksumFinding]) {
+    for finding in findings {
+        record_action(action, &finding.listed_file);
+    }
+}
+
+/// Describe what was found when a listed file was checked, like its listed and actual hashes, one per line.
+fn finding_details(
+    finding: &ChecksumFinding,
+    translations: &Translations,
+    language: Language,
+) -> String {
+    let mut detail_lines: Vec<String> = vec![format!(
+        "{}: {}",
+        translations.get("checksum-file"),
+        finding.checksum_file.display()
+    )];
+    if !finding.listed_hash.is_empty() {
+        detail_lines.push(format!(
+            "{}: {}",
+            translations.get("checksum-listed-hash"),
+            finding.listed_hash
+        ));
+    }
+    if let Some(actual_hash) = &finding.details.actual_hash {
+        detail_lines.push(format!(
+            "{}: {}",
+            translations.get("checksum-actual-hash"),
+            actual_hash
+        ));
+    }
+    if let Some(file_size) = finding.details.file_size {
+        detail_lines.push(format!(
+            "{}: {} ({} B)",
+            translations.get("checksum-file-size"),
+            format_bytes(file_size, language),
+            format_count(file_size, language)
+        ));
+    }
+    if let Some(modified) = finding.details.modified {
+        detail_lines.push(format!(
+            "{}: {}",
+            translations.get("checksum-modified"),
+            modified.format("%Y-%m-%d %H:%M:%S %:z")
+        ));
+    }
+    if let Some(listed_at) = finding.listed_at {
+        detail_lines.push(format!(
+            "{}: {}",
+            translations.get("checksum-listed-at"),
+            listed_at.format("%Y-%m-%d %H:%M:%S %:z")
+        ));
+    }
+    if let Some(error) = &finding.details.error {
+        detail_lines.push(format!(
+            "{}: {}",
+            translations.get("checksum-error"),
+            translate_error(error, translations)
+        ));
+    }
+    detail_lines.join("\n")
+}
+
+/// Show a shortened hash in monospace, with the full hash on hover and a right-click menu for copying it.
+fn hash_label(ui: &mut egui::Ui, full_hash: &str, translations: &Translations) -> egui::Response {
+    let hash_response = ui
+        .monospace(format_hash(full_hash))
+        .on_hover_text_at_pointer(full_hash);
+    // Tell screen readers the whole hash rather than the shortened one.
+    hash_response
+        .ctx
+        .accesskit_node_builder(hash_response.id, |builder| {
+            builder.set_description(full_hash);
+        });
+    hash_response.context_menu(|ui| {
+        if ui.button(translations.get("copy-full-hash")).clicked() {
+            ui.output_mut(|output| output.copied_text = full_hash.to_string());
+            ui.close_menu();
+        }
+    })
+}
+
+/// Fluent ID of an error message from FolSum's library, so it can be shown in the user's language.
+fn error_id(error_message: &str) -> Option<&'static str> {
+    let error_id: &str = match error_message {
+        STOPPED_REASON => "error-stopped",
+        FILE_IN_USE_ERROR => "error-file-in-use",
+        "Worker thread panicked" => "error-worker-panicked",
+        "A summarization is already running" => "error-already-summarizing",
+        "Only files can be hashed, not folders" => "error-hash-folder",
+        "Couldn't open the file to hash it" => "error-hash-open",
+        "Couldn't read the file to hash it" => "error-hash-read",
+        "The checksum file lists a path outside of its folder, so it wasn't read" => {
+            "error-outside-folder"
+        }
+        "The file isn't valid UTF-8" => "error-not-utf8",
+        "The file isn't valid UTF-16" => "error-not-utf16",
+        "Select findings before verifying them again" => "error-reverify-unselected",
+        "Select findings before exporting them" => "error-export-unselected",
+        "No path for export file was specified" => "error-export-no-path",
+        "Choose a directory before exporting an SPDX document" => "error-spdx-no-directory",
+        "Failed to create CSV export file" => "error-csv-export-write",
+        "Failed to write findings export file" => "error-findings-export-write",
+        "Failed to write SPDX export file" => "error-spdx-export-write",
+        "Failed to write statistics export file" => "error-stats-export-write",
+        "Read-only mode forbids writing exports inside of the chosen directory" => {
+            "error-read-only-export"
+        }
+        "Export filename template contains an unknown placeholder" => "error-template-placeholder",
+        "Export filename template needs a case ID, but none was given" => "error-template-case-id",
+        "Date format can't be empty" => "error-date-format-empty",
+        "Date format contains an unknown specifier" => "error-date-format-specifier",
+        "Export filename can't be empty" => "error-filename-empty",
+        "Export filename contains characters that aren't allowed in filenames" => {
+            "error-filename-characters"
+        }
+        "Export filename can't end with a period or a space on Windows" => "error-filename-ending",
+        "Export filename is a device name that's reserved by Windows" => "error-filename-reserved",
+        "The export isn't inside of a folder" | "The file isn't inside of a folder" => {
+            "error-no-folder"
+        }
+        "Couldn't open the path with the default app" => "error-open-path",
+        "Couldn't show the file in the file manager" => "error-reveal-path",
+        "Nothing's been recorded in the action log yet" => "error-action-log-empty",
+        "Couldn't write the diagnostics bundle" => "error-diagnostics-write",
+        "Name the profile before saving it" => "error-profile-unnamed",
+        "There's no profile with that name" => "error-profile-missing",
+        "Couldn't read .folsum.toml" => "error-project-file-read",
+        "Couldn't understand .folsum.toml" => "error-project-file-parse",
+        "Set an API token before turning on the results API" => "error-api-token",
+        "Couldn't start the results API. Is another program using its port?" => "error-api-port",
+        "Couldn't find the results API's port" | "Couldn't start the results API" => {
+            "error-api-start"
+        }
+        _ => return None,
+    };
+    Some(error_id)
+}
+
+/// Show an error message from FolSum's library in the user's language.
+///
+/// Details after a known message's colon, like the OS's reason that a write failed, are kept as they are. Unknown
+/// messages, like panics, are shown untranslated rather than not at all.
+fn translate_error(error_message: &str, translations: &Translations) -> String {
+    if let Some(error_id) = error_id(error_message) {
+        return translations.get(error_id);
+    }
+    match error_message
+        .split_once(": ")
+        .and_then(|(known_message, details)| Some((error_id(known_message)?, details)))
+    {
+        Some((error_id, details)) => format!("{}: {}", translations.get(error_id), details),
+        None => error_message.to_string(),
+    }
+}
+
+/// Show an error message in the theme's error color, marked with an icon so it doesn't rely on color alone.
+fn error_label(ui: &mut egui::Ui, error_message: &str) -> egui::Response {
+    let error_color: egui::Color32 = ui.visuals().error_fg_color;
+    ui.colored_label(error_color, format!("⚠ {error_message}"))
+}
+
+/// Have screen readers announce a status label whenever its text changes.
+fn announce_changes(status_label: &egui::Response) {
+    status_label
+        .ctx
+        .accesskit_node_builder(status_label.id, |builder| {
+            builder.set_live(egui::accesskit::Live::Polite);
         });
+}
+
+/// Explain what a widget does in its hover text and to screen readers.
+fn describe(response: egui::Response, description: String) -> egui::Response {
+    response.ctx.accesskit_node_builder(response.id, |builder| {
+        builder.set_description(description.as_str());
+    });
+    response.on_hover_text(description)
+}
+
+/// Ask the user whether an existing export file should be overwritten or kept alongside a new version.
+///
+/// Returns the path to export to, or `None` if the user backed out.
+#[cfg(not(target_arch = "wasm32"))]
+fn confirm_export_path(chosen_path: PathBuf, translations: &Translations) -> Option<PathBuf> {
+    // Nothing can be clobbered if there's no file at the chosen path yet.
+    if !chosen_path.exists() {
+        return Some(chosen_path);
+    }
+    // Find the name that the export would get if the user doesn't want to overwrite.
+    let versioned_path: PathBuf = next_versioned_path(&chosen_path);
+    let mut description_args = FluentArgs::new();
+    description_args.set("chosen_path", chosen_path.display().to_string());
+    description_args.set("versioned_path", versioned_path.display().to_string());
+    let overwrite_choice = MessageDialog::new()
+        .set_level(MessageLevel::Warning)
+        .set_title(translations.get("export-exists-title"))
+        .set_description(translations.get_with_args("export-exists-description", &description_args))
+        .set_buttons(MessageButtons::YesNoCancel)
+        .show();
+    match overwrite_choice {
+        // Overwrite the existing file because the user said so.
+        MessageDialogResult::Yes => Some(chosen_path),
+        // Keep the existing file and save this export next to it with a version suffix.
+        MessageDialogResult::No => Some(versioned_path),
+        // Don't export anything if the user closed the dialog or cancelled.
+        _ => None,
+    }
+}
+
+/// What an export would contain, shown to the user before it's written.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct ExportPreview {
+    row_count: u64,
+    // Whether every row was counted. If not, then there are at least `row_count` rows.
+    counted_all: bool,
+    total_bytes: u64,
+    first_rows: Vec<String>,
+}
+
+/// Preview a CSV export from the extension counts that'll be written.
+#[cfg(not(target_arch = "wasm32"))]
+fn csv_preview(
+    exported_counts: &Arc<Mutex<HashMap<String, u32>>>,
+    extension_bytes: &Arc<Mutex<HashMap<String, u64>>>,
+) -> ExportPreview {
+    let locked_counts = lock_shared(exported_counts);
+    let locked_bytes = lock_shared(extension_bytes);
+    let exported_rows: Vec<String> = extension_rows(&locked_counts);
+    ExportPreview {
+        row_count: exported_rows.len() as u64,
+        counted_all: true,
+        // Only add up the sizes of the extensions that are being exported.
+        total_bytes: locked_counts
+            .keys()
+            .filter_map(|extension_name| locked_bytes.get(extension_name))
+            .sum(),
+        first_rows: exported_rows.into_iter().take(PREVIEW_ROW_COUNT).collect(),
+    }
+}
+
+/// Preview a findings export from the findings that were selected.
+#[cfg(not(target_arch = "wasm32"))]
+fn findings_preview(
+    exported_findings: &[ChecksumFinding],
+    finding_decisions: &FindingDecisions,
+    severity_rules: &SeverityRules,
+) -> ExportPreview {
+    let exported_rows: Vec<String> =
+        findings_rows(exported_findings, finding_decisions, severity_rules);
+    ExportPreview {
+        row_count: exported_rows.len() as u64,
+        counted_all: true,
+        total_bytes: exported_findings
+            .iter()
+            .filter_map(|finding| finding.details.file_size)
+            .sum(),
+        first_rows: exported_rows.into_iter().take(PREVIEW_ROW_COUNT).collect(),
+    }
+}
+
+/// Preview a statistics export from the latest summarization's inventory.
+#[cfg(not(target_arch = "wasm32"))]
+fn stats_preview(
+    extension_counts: &Arc<Mutex<HashMap<String, u32>>>,
+    extension_bytes: &Arc<Mutex<HashMap<String, u64>>>,
+    inventory_stats: &Arc<Mutex<InventoryStats>>,
+) -> ExportPreview {
+    let locked_bytes = lock_shared(extension_bytes);
+    let exported_rows: Vec<String> = stats_rows(
+        &lock_shared(extension_counts),
+        &locked_bytes,
+        &lock_shared(inventory_stats),
+    );
+    ExportPreview {
+        row_count: exported_rows.len() as u64,
+        counted_all: true,
+        total_bytes: locked_bytes.values().sum(),
+        first_rows: exported_rows.into_iter().take(PREVIEW_ROW_COUNT).collect(),
+    }
+}
+
+/// SPDX export that's waiting on its preview, with everything that's needed to start it once it's confirmed.
+#[cfg(not(target_arch = "wasm32"))]
+struct SpdxExportRequest {
+    export_path: PathBuf,
+    summarized_directory: Option<PathBuf>,
+    read_only_root: Option<PathBuf>,
+    export_metadata: ExportMetadata,
+    pinned_options: SummarizationOptions,
+    // Filters that decide which files end up in the export, for the confirmation.
+    filters: Vec<String>,
+}
+
+/// Work out an SPDX export's preview in the background before asking the user to confirm it.
+#[cfg(not(target_arch = "wasm32"))]
+fn preview_spdx_export(
+    spdx_request: SpdxExportRequest,
+) -> Pending<SpdxExportRequest, ExportPreview> {
+    let (summarized_directory, preview_options) = (
+        spdx_request.summarized_directory.clone(),
+        spdx_request.pinned_options.clone(),
+    );
+    Pending::start(spdx_request, move || {
+        spdx_preview(&summarized_directory, &preview_options)
+    })
+}
+
+/// Preview an SPDX export without hashing anything, by counting the directory's files and listing the first few.
+#[cfg(not(target_arch = "wasm32"))]
+fn spdx_preview(
+    summarized_directory: &Option<PathBuf>,
+    summarization_options: &SummarizationOptions,
+) -> ExportPreview {
+    // There's nothing to preview yet, so let the export itself say that a directory's needed.
+    let summarized_directory: &PathBuf = match summarized_directory {
+        Some(summarized_directory) => summarized_directory,
+        None => return ExportPreview::default(),
+    };
+    let directory_estimate: DirectoryEstimate = estimate_directory(
+        summarized_directory,
+        summarization_options,
+        ESTIMATE_TIME_BUDGET,
+    );
+    ExportPreview {
+        row_count: directory_estimate.file_count,
+        counted_all: directory_estimate.finished,
+        total_bytes: directory_estimate.total_bytes,
+        first_rows: spdx_file_paths(summarized_directory, summarization_options)
+            .take(PREVIEW_ROW_COUNT)
+            .map(|(_, relative_path)| relative_path)
+            .collect(),
     }
 }
+
+/// Describe the filters that decide which files and extensions end up in an export.
+#[cfg(not(target_arch = "wasm32"))]
+fn applied_filters(
+    summarization_options: &SummarizationOptions,
+    selected_count: Option<usize>,
+    translations: &Translations,
+) -> Vec<String> {
+    let mut filters: Vec<String> = Vec::new();
+    if let Some(selected_count) = selected_count {
+        let mut filter_args = FluentArgs::new();
+        filter_args.set("selected_count", selected_count);
+        filters.push(translations.get_with_args("export-filter-selected", &filter_args));
+    }
+    if let Some(max_depth) = summarization_options.max_depth {
+        let mut filter_args = FluentArgs::new();
+        filter_args.set("max_depth", max_depth);
+        filters.push(translations.get_with_args("export-filter-depth", &filter_args));
+    }
+    if summarization_options.same_file_system {
+        filters.push(translations.get("export-filter-same-file-system"));
+    }
+    let ignore_presets = &summarization_options.ignore_presets;
+    let ignored_platforms: Vec<String> = [
+        (ignore_presets.macos, "settings-ignore-macos"),
+        (ignore_presets.windows, "settings-ignore-windows"),
+        (ignore_presets.linux, "settings-ignore-linux"),
+    ]
+    .iter()
+    .filter(|(preset_chosen, _)| *preset_chosen)
+    .map(|(_, preset_id)| translations.get(preset_id))
+    .collect();
+    if !ignored_platforms.is_empty() {
+        let mut filter_args = FluentArgs::new();
+        filter_args.set("platforms", ignored_platforms.join(", "));
+        filters.push(translations.get_with_args("export-filter-ignored-metadata", &filter_args));
+    }
+    filters
+}
+
+/// Show the user where an export's going and what's in it, and ask them to confirm before it's written.
+///
+/// Returns whether to go ahead with the export.
+#[cfg(not(target_arch = "wasm32"))]
+fn confirm_export_preview(
+    export_path: &Path,
+    export_preview: &ExportPreview,
+    filters: &[String],
+    translations: &Translations,
+    language: Language,
+) -> bool {
+    let mut description_args = FluentArgs::new();
+    description_args.set("export_path", export_path.display().to_string());
+    description_args.set(
+        "counted",
+        match export_preview.counted_all {
+            true => "all",
+            false => "some",
+        },
+    );
+    description_args.set(
+        "row_count",
+        format_count(export_preview.row_count, language),
+    );
+    description_args.set(
+        "total_size",
+        format_bytes(export_preview.total_bytes, language),
+    );
+    description_args.set(
+        "filters",
+        match filters.is_empty() {
+            true => translations.get("export-filter-none"),
+            false => filters.join("; "),
+        },
+    );
+    description_args.set("shown_count", export_preview.first_rows.len());
+    let description: String = format!(
+        "{}\n\n{}\n{}",
+        translations.get_with_args("export-preview-description", &description_args),
+        translations.get_with_args("export-preview-first-rows", &description_args),
+        export_preview.first_rows.join("\n")
+    );
+    let export_choice = MessageDialog::new()
+        .set_level(MessageLevel::Info)
+        .set_title(translations.get("export-preview-title"))
+        .set_description(description)
+        .set_buttons(MessageButtons::OkCancel)
+        .show();
+    export_choice == MessageDialogResult::Ok
+}
@@ -0,0 +1,123 @@
+#[cfg(feature = "gui")]
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+#[cfg(feature = "gui")]
+use unic_langid::LanguageIdentifier;
+
+/// Languages that FolSum's GUI has been translated into.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    Ukrainian,
+}
+
+impl Language {
+    /// Every language that can be chosen in the settings window.
+    pub const ALL: [Language; 3] = [Language::English, Language::Spanish, Language::Ukrainian];
+
+    /// Name of the language written in that language, so users can find their own in the language picker.
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+            Language::Ukrainian => "Українська",
+        }
+    }
+
+    /// Character that separates groups of thousands in big numbers, like the comma in `1,250,000`.
+    pub fn digit_group_separator(&self) -> char {
+        match self {
+            Language::English => ',',
+            Language::Spanish => '.',
+            // Non-breaking space so numbers aren't split across lines.
+            Language::Ukrainian => '\u{a0}',
+        }
+    }
+
+    /// Character that separates the whole part of a number from its fraction, like the period in `1.5`.
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Language::English => '.',
+            Language::Spanish | Language::Ukrainian => ',',
+        }
+    }
+
+    // Unicode language identifier that Fluent uses to pick plural rules.
+    #[cfg(feature = "gui")]
+    fn language_id(&self) -> &'static str {
+        match self {
+            Language::English => "en-US",
+            Language::Spanish => "es",
+            Language::Ukrainian => "uk",
+        }
+    }
+
+    // Translated messages, which are embedded in the binary so there's nothing extra to ship.
+    #[cfg(feature = "gui")]
+    fn messages(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en-US.ftl"),
+            Language::Spanish => include_str!("../locales/es.ftl"),
+            Language::Ukrainian => include_str!("../locales/uk.ftl"),
+        }
+    }
+}
+
+/// User-facing GUI text in one language.
+#[cfg(feature = "gui")]
+pub struct Translations {
+    bundle: FluentBundle<FluentResource>,
+}
+
+#[cfg(feature = "gui")]
+impl Translations {
+    pub fn new(language: Language) -> Self {
+        let language_id: LanguageIdentifier = language
+            .language_id()
+            .parse()
+            .expect("Failed to parse built-in language identifier");
+        let mut bundle = FluentBundle::new(vec![language_id]);
+        // Don't wrap arguments in Unicode isolation marks because egui's fonts draw them as boxes.
+        bundle.set_use_isolating(false);
+        let resource = FluentResource::try_new(language.messages().to_string())
+            .expect("Failed to parse built-in translations");
+        bundle
+            .add_resource(resource)
+            .expect("Failed to load built-in translations");
+        Self { bundle }
+    }
+
+    /// Look up a message that doesn't take any arguments.
+    pub fn get(&self, message_id: &str) -> String {
+        self.format(message_id, None)
+    }
+
+    /// Look up a message and fill in its arguments, like the number of files that were summarized.
+    pub fn get_with_args(&self, message_id: &str, args: &FluentArgs<'_>) -> String {
+        self.format(message_id, Some(args))
+    }
+
+    fn format(&self, message_id: &str, args: Option<&FluentArgs<'_>>) -> String {
+        // Show the message's ID if it's missing so untranslated text is easy to spot instead of blank.
+        let pattern = match self
+            .bundle
+            .get_message(message_id)
+            .and_then(|message| message.value())
+        {
+            Some(pattern) => pattern,
+            None => return message_id.to_string(),
+        };
+        let mut errors = Vec::new();
+        self.bundle
+            .format_pattern(pattern, args, &mut errors)
+            .into_owned()
+    }
+}
+
+#[cfg(feature = "gui")]
+impl Default for Translations {
+    fn default() -> Self {
+        Self::new(Language::default())
+    }
+}
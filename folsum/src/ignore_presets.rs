@@ -0,0 +1,66 @@
+use std::ffi::OsStr;
+use std::path::Path;
+
+/// Files and folders that MacOS leaves behind, like Finder's view settings and Spotlight's index.
+const MACOS_METADATA: [&str; 6] = [
+    ".DS_Store",
+    ".Spotlight-V100",
+    ".fseventsd",
+    ".Trashes",
+    ".TemporaryItems",
+    ".VolumeIcon.icns",
+];
+/// Files and folders that Windows leaves behind, like thumbnail caches and folder customizations.
+const WINDOWS_METADATA: [&str; 5] = [
+    "Thumbs.db",
+    "ehthumbs.db",
+    "desktop.ini",
+    "$RECYCLE.BIN",
+    "System Volume Information",
+];
+/// Files and folders that Linux desktops leave behind, like trash folders on removable drives.
+const LINUX_METADATA: [&str; 2] = [".Trash", ".directory"];
+
+/// Operating system metadata that's left out of summarizations, exports, and checksum verification.
+///
+/// Drives that have been plugged into a Mac or a Windows PC pick up files like `.DS_Store` and `Thumbs.db`. They're
+/// noise when comparing the same evidence across platforms, so each platform's files can be ignored. Nothing's
+/// ignored by default, so summarizations stay complete unless the user asks otherwise.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)] // Define default fields when deserializing old state.
+pub struct IgnorePresets {
+    pub macos: bool,
+    pub windows: bool,
+    pub linux: bool,
+}
+
+impl IgnorePresets {
+    /// Check if a file or folder's name is OS metadata that one of the chosen presets ignores.
+    ///
+    /// Names are compared case-insensitively because MacOS and Windows filesystems usually are.
+    pub fn ignores(&self, name: &OsStr) -> bool {
+        let name: String = name.to_string_lossy().to_lowercase();
+        let preset_names = [
+            (self.macos, &MACOS_METADATA[..]),
+            (self.windows, &WINDOWS_METADATA[..]),
+            (self.linux, &LINUX_METADATA[..]),
+        ];
+        let is_listed: bool = preset_names
+            .iter()
+            .filter(|(preset_chosen, _)| *preset_chosen)
+            .flat_map(|(_, metadata_names)| metadata_names.iter())
+            .any(|metadata_name| name == metadata_name.to_lowercase());
+        // AppleDouble files (`._photo.jpg`) hold MacOS metadata for files on drives that don't support it.
+        let is_apple_double: bool = self.macos && name.starts_with("._");
+        // Trash folders on removable drives are named after the user's ID, like `.Trash-1000`.
+        let is_user_trash: bool = self.linux && name.starts_with(".trash-");
+        is_listed || is_apple_double || is_user_trash
+    }
+
+    /// Check if any part of a relative path, like a folder that it's in, is ignored OS metadata.
+    pub fn ignores_path(&self, relative_path: &Path) -> bool {
+        relative_path
+            .components()
+            .any(|component| self.ignores(component.as_os_str()))
+    }
+}
@@ -0,0 +1,106 @@
+use std::fs::File;
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::{lock_shared, stop_requested, STOPPED_REASON};
+
+/// Most readers per device that can be chosen in settings.
+pub const MOST_READERS_PER_DEVICE: usize = 16;
+/// How often readers that are waiting for their device check if they've been asked to stop.
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+// How many reads are happening right now on each device, by device ID. There are only ever a few devices, so a list
+// is quicker to search than a map (and it can be made in a `static`).
+static DEVICE_READERS: Mutex<Vec<(u64, usize)>> = Mutex::new(Vec::new());
+// Wakes up readers that are waiting for their device when a read on any device finishes.
+static READER_FINISHED: Condvar = Condvar::new();
+
+/// Identify the device (disk, partition, or network share) that an open file is stored on, if the platform says.
+#[cfg(unix)]
+pub fn device_id(file: &File) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    file.metadata()
+        .ok()
+        .map(|file_metadata| file_metadata.dev())
+}
+
+/// Identify the volume that an open file is stored on by its serial number.
+#[cfg(windows)]
+pub fn device_id(file: &File) -> Option<u64> {
+    winapi_util::file::information(file)
+        .ok()
+        .map(|file_information| file_information.volume_serial_number())
+}
+
+/// Leave files unscheduled on platforms that don't say which device they're on.
+#[cfg(not(any(unix, windows)))]
+pub fn device_id(_file: &File) -> Option<u64> {
+    None
+}
+
+/// Permission to read from a device, which is handed back when it's dropped.
+///
+/// Hashing with many threads at once is fast on SSDs, but makes hard drives seek back and forth between files until
+/// they're slower than reading one file at a time. Every hasher (summarizations, exports, and quick hash) asks for a
+/// permit before each read and hands it back right after, so only a few reads hit the same device at once, no matter
+/// how many files are hashing, and hashing one file never holds up readers on the device while it's hashed.
+#[derive(Debug)]
+pub struct DeviceReadPermit {
+    // Device that's being read from, or `None` if it's unknown and the read isn't being scheduled.
+    device: Option<u64>,
+}
+
+impl DeviceReadPermit {
+    /// Wait until fewer than `readers_per_device` reads are happening on a device, then start one.
+    ///
+    /// Reads from unknown devices start right away. Gives up with [`STOPPED_REASON`] if the reader's asked to stop
+    /// while it's waiting, so a busy device can't keep a worker from stopping.
+    pub fn acquire(device: Option<u64>, readers_per_device: usize) -> Result<Self, &'static str> {
+        if let Some(device) = device {
+            let mut device_readers = lock_shared(&DEVICE_READERS);
+            // Always let one read through, even if the limit is set to zero, so hashing can't get stuck.
+            while reader_count(&device_readers, device) >= readers_per_device.max(1) {
+                if stop_requested() {
+                    return Err(STOPPED_REASON);
+                }
+                device_readers = READER_FINISHED
+                    .wait_timeout(device_readers, STOP_CHECK_INTERVAL)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0;
+            }
+            match device_readers.iter_mut().find(|(id, _)| *id == device) {
+                Some((_, readers)) => *readers += 1,
+                None => device_readers.push((device, 1)),
+            }
+        }
+        Ok(Self { device })
+    }
+}
+
+impl Drop for DeviceReadPermit {
+    fn drop(&mut self) {
+        if let Some(device) = self.device {
+            let mut device_readers = lock_shared(&DEVICE_READERS);
+            if let Some((_, readers)) = device_readers.iter_mut().find(|(id, _)| *id == device) {
+                *readers -= 1;
+            }
+            // Forget devices that aren't being read from so the list stays short.
+            device_readers.retain(|(_, readers)| *readers > 0);
+            drop(device_readers);
+            READER_FINISHED.notify_all();
+        }
+    }
+}
+
+/// Count the reads that are happening on a device right now.
+fn reader_count(device_readers: &[(u64, usize)], device: u64) -> usize {
+    device_readers
+        .iter()
+        .find(|(id, _)| *id == device)
+        .map_or(0, |(_, readers)| *readers)
+}
+
+/// Count the reads that are happening on a device right now, like for showing how busy it is.
+pub fn active_readers(device: u64) -> usize {
+    reader_count(&lock_shared(&DEVICE_READERS), device)
+}
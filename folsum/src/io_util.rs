@@ -0,0 +1,119 @@
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::lock_shared;
+
+/// Start of the names of the temporary files that writes go to before they're renamed into place.
+pub const TEMP_FILE_PREFIX: &str = ".folsum-tmp-";
+
+// Number of temporary files that this process has made, so writes that run at the same time don't share one.
+static TEMP_FILE_COUNT: AtomicUsize = AtomicUsize::new(0);
+// Temporary files that are being written right now, so they can be cleaned up if FolSum closes before they're done.
+static UNFINISHED_WRITES: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Write a file all at once, so it's either completely written or not written at all.
+///
+/// See [`write_atomically_with`].
+pub fn write_atomically(destination: &Path, contents: &[u8]) -> io::Result<()> {
+    write_atomically_with(destination, |temp_file| temp_file.write_all(contents))
+}
+
+/// Write a file with `write`, so it's either completely written or not written at all.
+///
+/// Everything's written to a temporary file next to the destination, flushed to disk, and then renamed over the
+/// destination, which replaces it in one step. If writing fails or panics partway through, or FolSum is closed
+/// while it's writing, then a half-written export never stands in for a whole one, and any older file at the
+/// destination is left alone. Temporary files are cleaned up unless FolSum is killed outright.
+pub fn write_atomically_with<W>(destination: &Path, write: W) -> io::Result<()>
+where
+    W: FnOnce(&mut File) -> io::Result<()>,
+{
+    let mut temp_file = TempFile::create(destination)?;
+    write(&mut temp_file.file)?;
+    // Make sure the contents are on disk before they're renamed into place, or a power cut could leave an empty file.
+    temp_file.file.sync_all()?;
+    temp_file.persist(destination)
+}
+
+/// Temporary file that's deleted when it's dropped, unless it was renamed into place.
+struct TempFile {
+    path: PathBuf,
+    file: File,
+    persisted: bool,
+}
+
+impl TempFile {
+    /// Create a temporary file in the same directory as the destination, since renames can't cross filesystems.
+    fn create(destination: &Path) -> io::Result<Self> {
+        let destination_name: OsString =
+            destination.file_name().map(OsString::from).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "No filename to write to")
+            })?;
+        // Hide temporary files (on Unix) and name them after the destination, so stray ones are easy to trace.
+        let mut temp_name = OsString::from(format!(
+            "{TEMP_FILE_PREFIX}{}-{}-",
+            process::id(),
+            TEMP_FILE_COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        temp_name.push(destination_name);
+        let path: PathBuf = destination.with_file_name(temp_name);
+        // Never write into a file that's already there, even if it has the same name somehow.
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        lock_shared(&UNFINISHED_WRITES).push(path.clone());
+        Ok(Self {
+            path,
+            file,
+            persisted: false,
+        })
+    }
+
+    /// Rename the temporary file over the destination.
+    fn persist(mut self, destination: &Path) -> io::Result<()> {
+        fs::rename(&self.path, destination)?;
+        self.persisted = true;
+        forget_unfinished_write(&self.path);
+        // Flush the rename itself on Unix, where it's recorded in the directory instead of the file.
+        #[cfg(unix)]
+        if let Some(destination_directory) = destination.parent() {
+            let directory: &Path = match destination_directory.as_os_str().is_empty() {
+                true => Path::new("."),
+                false => destination_directory,
+            };
+            // The file's already in place, so failing to flush the directory isn't worth failing the write over.
+            let _sync_result = File::open(directory).and_then(|directory| directory.sync_all());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if !self.persisted {
+            let _delete_result = fs::remove_file(&self.path);
+            forget_unfinished_write(&self.path);
+        }
+    }
+}
+
+/// Stop keeping track of a temporary file that's been renamed into place or deleted.
+fn forget_unfinished_write(temp_path: &Path) {
+    lock_shared(&UNFINISHED_WRITES).retain(|unfinished_write| unfinished_write != temp_path);
+}
+
+/// Delete the temporary files of writes that haven't finished, like when FolSum closes before they're done.
+///
+/// Files that were already renamed into place are whole, so they're kept. Writers that are still going fail when they
+/// try to rename their temporary file, so nothing half-written shows up afterward either.
+pub fn discard_unfinished_writes() {
+    for unfinished_write in lock_shared(&UNFINISHED_WRITES).drain(..) {
+        let _delete_result = fs::remove_file(unfinished_write);
+    }
+}
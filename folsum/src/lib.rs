@@ -1,13 +1,148 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
+// Leave the GUI out of headless builds so they don't pull in eframe, egui, and rfd.
+#[cfg(feature = "gui")]
 mod gui;
+#[cfg(feature = "gui")]
 pub use gui::FolsumGui;
 
+// Crash reports only make sense for the native GUI.
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+mod crash_report;
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+pub use crash_report::{install_crash_reporter, latest_crash_report, recent_log_lines};
+
+mod i18n;
+pub use i18n::Language;
+#[cfg(feature = "gui")]
+pub use i18n::Translations;
+
+// The action log is kept in the user's app data folder, which only the native GUI knows about.
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+mod action_log;
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+pub use action_log::{action_log_path, append_action, record_action};
+
+mod checksum_files;
+pub use checksum_files::{
+    audit_verdict, decode_text, group_discrepancies, is_checksum_file, parse_checksum_file,
+    read_checksum_listing, reverify_findings, verify_checksum_file, verify_listed_file,
+    AuditReport, AuditVerdict, ChecksumFinding, ChecksumListing, ChecksumOutcome, ContentVerdict,
+    FindingDecisions, ListedFileDetails, Severity, SeverityRules, SizeOnlyRules,
+    VerificationProgress, DISCREPANCY_OUTCOMES,
+};
+
+mod diagnostics;
+#[cfg(all(feature = "gui", not(target_arch = "wasm32")))]
+pub use diagnostics::save_diagnostics_bundle;
+pub use diagnostics::{build_description, BUILD_DATE, FOLSUM_VERSION, GIT_COMMIT};
+
+// Sizing up directories before summarizing them needs a filesystem to walk.
+#[cfg(not(target_arch = "wasm32"))]
+mod directory_estimate;
+#[cfg(not(target_arch = "wasm32"))]
+pub use directory_estimate::{
+    estimate_directory, is_system_directory, DirectoryEstimate, ENORMOUS_FILE_COUNT,
+    ENORMOUS_TOTAL_BYTES, ESTIMATE_TIME_BUDGET,
+};
+
+mod disk_usage;
+pub use disk_usage::{allocated_size, is_sparse, StorageTotals};
+
+mod extra_streams;
+pub use extra_streams::{find_extra_streams, ExtraStream};
+
 mod export_csv;
-pub use export_csv::export_csv;
+pub use export_csv::{csv_field, export_csv, extension_rows, ExportMetadata};
+
+mod export_findings;
+#[cfg(not(target_arch = "wasm32"))]
+pub use export_findings::export_findings;
+pub use export_findings::findings_rows;
+
+mod export_stats;
+#[cfg(not(target_arch = "wasm32"))]
+pub use export_stats::export_stats;
+pub use export_stats::{stats_rows, InventoryStats, LARGEST_FILE_COUNT};
+
+// SBOM-style file listings can only be written where there's a filesystem.
+#[cfg(not(target_arch = "wasm32"))]
+mod export_spdx;
+#[cfg(not(target_arch = "wasm32"))]
+pub use export_spdx::{export_spdx, spdx_document, spdx_file_paths, SpdxFile};
+
+mod ignore_presets;
+pub use ignore_presets::IgnorePresets;
+
+// Reads are scheduled per device so that hashing doesn't make hard drives thrash.
+mod io_scheduler;
+pub use io_scheduler::{active_readers, device_id, DeviceReadPermit, MOST_READERS_PER_DEVICE};
+
+// Writers go through temporary files so that interrupted writes never leave half of a file behind.
+#[cfg(not(target_arch = "wasm32"))]
+mod io_util;
+#[cfg(not(target_arch = "wasm32"))]
+pub use io_util::{
+    discard_unfinished_writes, write_atomically, write_atomically_with, TEMP_FILE_PREFIX,
+};
+
+mod quick_hash;
+pub use quick_hash::{
+    hash_bytes, hash_file, hash_file_in_background, hash_file_into, hash_file_with_buffer,
+    FileHashes, QuickHash, FILE_IN_USE_ERROR,
+};
+
+mod read_buffer;
+pub use read_buffer::{
+    ReadBuffer, ReadBufferLimits, BIGGEST_READ_BUFFER_KIB, SMALLEST_READ_BUFFER_KIB,
+};
+
+// Named sets of summarization options for switching between workflows.
+mod profiles;
+pub use profiles::{find_profile, save_profile, starter_profiles, OptionsProfile};
+
+// Options that a directory pins to itself with a `.folsum.toml`.
+mod project_file;
+pub use project_file::{read_project_file, ProjectFile, PROJECT_FILE_NAME};
+
+// Local HTTP API for pulling results into other programs, like case management systems.
+#[cfg(feature = "rest-api")]
+mod rest_api;
+#[cfg(feature = "rest-api")]
+pub use rest_api::{serve_results, ApiResults, ApiServer, ApiSettings, MOST_API_CLIENTS};
+
+// Known-answer tests of the hash algorithms, for labs that have to validate their tools.
+mod self_test;
+pub use self_test::{run_self_test, SelfTest};
 
 mod summarize;
-pub use summarize::summarize_directory;
+pub use summarize::{
+    extension_name, summarize_directory, PhaseTimings, SummarizationOptions, SummarizationState,
+    TOP_LEVEL_GROUP,
+};
+
+// Audit events for central logging through syslog or the Windows Event Log.
+mod system_log;
+pub use system_log::{write_system_log, AuditEvent};
+
+// Seeded fixtures for tests and benchmarks, here and in downstream crates.
+#[cfg(feature = "test-utils")]
+mod test_tree;
+#[cfg(feature = "test-utils")]
+pub use test_tree::{TestTree, TestTreeOptions};
 
 mod utils;
-pub use utils::sort_counts;
+pub use utils::{
+    check_read_only, create_export_path, format_bytes, format_count, format_duration, format_hash,
+    is_within_directory, natural_cmp, next_versioned_path, os_user_name, sort_counts,
+    validate_date_format, validate_filename, DEFAULT_DATE_FORMAT, DEFAULT_FILENAME_TEMPLATE,
+    LEGACY_DATE_FORMAT,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use utils::{open_path, reveal_path};
+
+mod worker;
+pub use worker::{
+    lock_shared, request_stop, running_workers, spawn_worker, stop_requested, stop_worker,
+    wait_for_worker, wait_for_workers, WorkerStatus, STOPPED_REASON,
+};
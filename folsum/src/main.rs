@@ -4,13 +4,44 @@
 // When compiling natively:
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
-    env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
+    // Log to stderr (if you run with `RUST_LOG=debug`) and save a crash report if FolSum panics.
+    folsum::install_crash_reporter();
+
+    // Summarize the directory that was passed as an argument (if any), like from a file manager's context menu, with
+    // the profile that was chosen with `--profile <name>` (if any).
+    let mut launch_directory: Option<std::path::PathBuf> = None;
+    let mut launch_profile: Option<String> = None;
+    let mut launch_args = std::env::args_os().skip(1);
+    while let Some(launch_arg) = launch_args.next() {
+        match launch_arg == "--profile" {
+            true => {
+                launch_profile = launch_args
+                    .next()
+                    .map(|profile_name| profile_name.to_string_lossy().to_string())
+            }
+            false => {
+                launch_directory = Some(std::path::PathBuf::from(launch_arg))
+                    .filter(|launch_path| launch_path.is_dir())
+            }
+        }
+    }
 
     let native_options = eframe::NativeOptions::default();
     eframe::run_native(
         "FolSum",
         native_options,
-        Box::new(|cc| Box::new(folsum::FolsumGui::new(cc))),
+        Box::new(|cc| {
+            let mut folsum_gui = folsum::FolsumGui::new(cc);
+            if let Some(profile_name) = launch_profile {
+                if let Err(profile_error) = folsum_gui.use_profile(&profile_name) {
+                    log::warn!("{}: {}", profile_error, profile_name);
+                }
+            }
+            if let Some(directory) = launch_directory {
+                folsum_gui.summarize_on_launch(directory);
+            }
+            Box::new(folsum_gui)
+        }),
     )
 }
 
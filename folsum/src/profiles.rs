@@ -0,0 +1,83 @@
+use crate::{IgnorePresets, SizeOnlyRules, SummarizationOptions};
+
+/// Named set of summarization options, for switching between workflows without redoing every setting.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)] // Define default fields when deserializing old state.
+pub struct OptionsProfile {
+    pub name: String,
+    pub options: SummarizationOptions,
+}
+
+impl Default for OptionsProfile {
+    fn default() -> Self {
+        Self {
+            name: String::from("Default"),
+            options: SummarizationOptions::default(),
+        }
+    }
+}
+
+/// Profiles that new users start with, which they can change or delete like their own.
+pub fn starter_profiles() -> Vec<OptionsProfile> {
+    vec![
+        OptionsProfile::default(),
+        // Get a rough inventory fast by only recording the sizes of footage, which takes the longest to hash.
+        OptionsProfile {
+            name: String::from("Quick check"),
+            options: SummarizationOptions {
+                verify_checksum_files: true,
+                size_only_rules: SizeOnlyRules {
+                    extensions: String::from("mov, mp4, mxf, braw, r3d, mkv, avi"),
+                    larger_than_mib: Some(1024),
+                },
+                ignore_presets: IgnorePresets {
+                    macos: true,
+                    windows: true,
+                    linux: true,
+                },
+                ..Default::default()
+            },
+        },
+        // Hash everything, look for hidden data, and leave a record in the system log, for evidence that's contested.
+        OptionsProfile {
+            name: String::from("Court-grade"),
+            options: SummarizationOptions {
+                verify_checksum_files: true,
+                retry_locked_files: true,
+                find_extra_streams: true,
+                write_system_log: true,
+                ..Default::default()
+            },
+        },
+    ]
+}
+
+/// Find a profile by name, ignoring case so that `--profile court-grade` finds "Court-grade".
+pub fn find_profile<'a>(profiles: &'a [OptionsProfile], name: &str) -> Option<&'a OptionsProfile> {
+    profiles
+        .iter()
+        .find(|profile| profile.name.trim().eq_ignore_ascii_case(name.trim()))
+}
+
+/// Save options as a profile, replacing the profile that has the same name if there is one.
+pub fn save_profile(
+    profiles: &mut Vec<OptionsProfile>,
+    name: &str,
+    options: &SummarizationOptions,
+) -> Result<(), &'static str> {
+    let name: &str = name.trim();
+    if name.is_empty() {
+        return Err("Name the profile before saving it");
+    }
+    match profiles
+        .iter_mut()
+        .find(|profile| profile.name.eq_ignore_ascii_case(name))
+    {
+        Some(existing_profile) => existing_profile.options = options.clone(),
+        None => profiles.push(OptionsProfile {
+            name: String::from(name),
+            options: options.clone(),
+        }),
+    }
+    Ok(())
+}
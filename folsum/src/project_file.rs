@@ -0,0 +1,116 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{IgnorePresets, SizeOnlyRules, SummarizationOptions};
+
+/// Name of the optional file in a summarized directory that pins options to it.
+pub const PROJECT_FILE_NAME: &str = ".folsum.toml";
+
+/// Options that a directory pins for everyone who summarizes it, read from its `.folsum.toml`.
+///
+/// Teams keep the same options for the same evidence no matter whose computer it's summarized on. Every setting's
+/// optional, and the ones that are set win over the user's own settings. For example:
+///
+/// ```toml
+/// filename_template = "{date}_{dirname}_{case_id}"
+/// max_depth = 3
+///
+/// [ignore_presets]
+/// macos = true
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)] // Catch typos instead of silently ignoring what the team meant to pin.
+pub struct ProjectFile {
+    pub filename_template: Option<String>,
+    pub same_file_system: Option<bool>,
+    pub max_depth: Option<usize>,
+    pub verify_checksum_files: Option<bool>,
+    pub retry_locked_files: Option<bool>,
+    pub find_extra_streams: Option<bool>,
+    pub size_only_rules: Option<SizeOnlyRules>,
+    pub ignore_presets: Option<IgnorePresets>,
+}
+
+impl ProjectFile {
+    /// Apply the pinned options on top of the user's own, leaving the user's settings alone.
+    pub fn pin(&self, user_options: &SummarizationOptions) -> SummarizationOptions {
+        let mut pinned_options: SummarizationOptions = user_options.clone();
+        if let Some(same_file_system) = self.same_file_system {
+            pinned_options.same_file_system = same_file_system;
+        }
+        if let Some(max_depth) = self.max_depth {
+            pinned_options.max_depth = Some(max_depth);
+        }
+        if let Some(verify_checksum_files) = self.verify_checksum_files {
+            pinned_options.verify_checksum_files = verify_checksum_files;
+        }
+        if let Some(retry_locked_files) = self.retry_locked_files {
+            pinned_options.retry_locked_files = retry_locked_files;
+        }
+        if let Some(find_extra_streams) = self.find_extra_streams {
+            pinned_options.find_extra_streams = find_extra_streams;
+        }
+        if let Some(size_only_rules) = &self.size_only_rules {
+            pinned_options.size_only_rules = size_only_rules.clone();
+        }
+        if let Some(ignore_presets) = &self.ignore_presets {
+            pinned_options.ignore_presets = ignore_presets.clone();
+        }
+        pinned_options
+    }
+
+    /// Describe each pinned setting like it's written in the project file, for export headers and the GUI.
+    pub fn pinned_settings(&self) -> Vec<String> {
+        let mut pinned_settings: Vec<String> = Vec::new();
+        if let Some(filename_template) = &self.filename_template {
+            pinned_settings.push(format!("filename_template = {filename_template:?}"));
+        }
+        if let Some(max_depth) = self.max_depth {
+            pinned_settings.push(format!("max_depth = {max_depth}"));
+        }
+        for (setting_name, pinned_value) in [
+            ("same_file_system", self.same_file_system),
+            ("verify_checksum_files", self.verify_checksum_files),
+            ("retry_locked_files", self.retry_locked_files),
+            ("find_extra_streams", self.find_extra_streams),
+        ] {
+            if let Some(pinned_value) = pinned_value {
+                pinned_settings.push(format!("{setting_name} = {pinned_value}"));
+            }
+        }
+        if let Some(size_only_rules) = &self.size_only_rules {
+            let size_limit: String = size_only_rules
+                .larger_than_mib
+                .map_or_else(String::new, |larger_than_mib| {
+                    format!(", larger_than_mib = {larger_than_mib}")
+                });
+            pinned_settings.push(format!(
+                "size_only_rules = {{ extensions = {:?}{size_limit} }}",
+                size_only_rules.extensions
+            ));
+        }
+        if let Some(ignore_presets) = &self.ignore_presets {
+            pinned_settings.push(format!(
+                "ignore_presets = {{ macos = {}, windows = {}, linux = {} }}",
+                ignore_presets.macos, ignore_presets.windows, ignore_presets.linux
+            ));
+        }
+        pinned_settings
+    }
+}
+
+/// Read the `.folsum.toml` in a directory, if it has one.
+///
+/// Project files that can't be read or understood are errors instead of being skipped, so a summarization never
+/// quietly runs without the options that the team pinned.
+pub fn read_project_file(directory: &Path) -> Result<Option<ProjectFile>, String> {
+    let project_contents: String = match fs::read_to_string(directory.join(PROJECT_FILE_NAME)) {
+        Ok(project_contents) => project_contents,
+        Err(read_error) if read_error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(read_error) => return Err(format!("Couldn't read {PROJECT_FILE_NAME}: {read_error}")),
+    };
+    toml::from_str(&project_contents)
+        .map(Some)
+        .map_err(|parse_error| format!("Couldn't understand {PROJECT_FILE_NAME}: {parse_error}"))
+}
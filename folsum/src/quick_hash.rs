@@ -0,0 +1,189 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use md5::Md5;
+use sha2::digest::DynDigest;
+use sha2::{Digest, Sha256};
+use web_time::{Duration, Instant};
+
+use crate::{
+    device_id, lock_shared, spawn_worker, stop_requested, DeviceReadPermit, ReadBuffer,
+    ReadBufferLimits, WorkerStatus, STOPPED_REASON,
+};
+
+/// MD5 and SHA-256 hashes of one file, written in lowercase hex.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileHashes {
+    pub md5: String,
+    pub sha256: String,
+}
+
+impl FileHashes {
+    /// Check a hash that was sent over (in an email, for example) against the file's hashes.
+    ///
+    /// Returns `None` if the provided hash is neither as long as an MD5 hash nor as long as a SHA-256 hash.
+    pub fn matches(&self, provided_hash: &str) -> Option<bool> {
+        // Forgive uppercase hex and whitespace that comes along when copy-pasting.
+        let provided_hash: String = provided_hash.trim().to_lowercase();
+        match provided_hash.len() {
+            32 => Some(provided_hash == self.md5),
+            64 => Some(provided_hash == self.sha256),
+            _ => None,
+        }
+    }
+}
+
+/// Error that [`hash_file`] gives when another program has the file open and won't share it.
+pub const FILE_IN_USE_ERROR: &str = "The file is locked or in use by another program";
+
+/// Hash one file with MD5 and SHA-256 in a single read.
+pub fn hash_file(file_path: &Path) -> Result<FileHashes, &'static str> {
+    hash_file_with_buffer(
+        file_path,
+        &mut ReadBuffer::new(&ReadBufferLimits::default()),
+    )
+}
+
+/// Hash one file like [`hash_file`] does, reading it into a buffer that's reused (and tuned) across many files.
+pub fn hash_file_with_buffer(
+    file_path: &Path,
+    read_buffer: &mut ReadBuffer,
+) -> Result<FileHashes, &'static str> {
+    let mut md5_hasher = Md5::new();
+    let mut sha256_hasher = Sha256::new();
+    hash_file_into(
+        file_path,
+        read_buffer,
+        &mut [&mut md5_hasher, &mut sha256_hasher],
+    )?;
+    Ok(FileHashes {
+        md5: format!("{:x}", md5_hasher.finalize()),
+        sha256: format!("{:x}", sha256_hasher.finalize()),
+    })
+}
+
+/// Read one file into every hasher in a single pass, and return how many bytes were read.
+///
+/// Everything that hashes files (summarizations, exports, and quick hash) reads them through here, so they all wait
+/// their turn for the device, give up when FolSum's closing, and tell locked files apart from unreadable ones.
+pub fn hash_file_into(
+    file_path: &Path,
+    read_buffer: &mut ReadBuffer,
+    hashers: &mut [&mut dyn DynDigest],
+) -> Result<u64, &'static str> {
+    if file_path.is_dir() {
+        return Err("Only files can be hashed, not folders");
+    }
+    let mut file =
+        File::open(file_path).map_err(|open_error| match is_file_in_use(&open_error) {
+            true => FILE_IN_USE_ERROR,
+            false => "Couldn't open the file to hash it",
+        })?;
+    // Read the file in chunks so big files don't have to fit in memory.
+    let file_size: u64 = file.metadata().map_or(0, |metadata| metadata.len());
+    let (file_device, readers_per_device) = (device_id(&file), read_buffer.readers_per_device());
+    let buffer: &mut [u8] = read_buffer.for_file(file_size);
+    let mut total_read: u64 = 0;
+    // Only time reads, since hashing takes just as long with any buffer size and would hide how fast reads went.
+    let mut read_time = Duration::ZERO;
+    loop {
+        // Stop partway through big files if FolSum's closing, since whatever wanted the hash is going away.
+        if stop_requested() {
+            return Err(STOPPED_REASON);
+        }
+        // Wait for a turn to read from the device, and hand it back before hashing so other readers aren't held up.
+        let read_permit = DeviceReadPermit::acquire(file_device, readers_per_device)?;
+        let read_start = Instant::now();
+        let bytes_read: usize = file
+            .read(buffer)
+            // Byte-range locks make reads fail partway through, even though the file opened fine.
+            .map_err(|read_error| match is_file_in_use(&read_error) {
+                true => FILE_IN_USE_ERROR,
+                false => "Couldn't read the file to hash it",
+            })?;
+        read_time += read_start.elapsed();
+        drop(read_permit);
+        if bytes_read == 0 {
+            break;
+        }
+        for hasher in hashers.iter_mut() {
+            hasher.update(&buffer[..bytes_read]);
+        }
+        total_read += bytes_read as u64;
+    }
+    read_buffer.record_read(total_read, read_time);
+    Ok(total_read)
+}
+
+/// Hash bytes that are already in memory with MD5 and SHA-256, like the value of an extended attribute.
+pub fn hash_bytes(contents: &[u8]) -> FileHashes {
+    FileHashes {
+        md5: format!("{:x}", Md5::digest(contents)),
+        sha256: format!("{:x}", Sha256::digest(contents)),
+    }
+}
+
+/// Check if a file couldn't be opened or read because another program has it open or locked.
+#[cfg(windows)]
+fn is_file_in_use(io_error: &io::Error) -> bool {
+    // `ERROR_SHARING_VIOLATION` and `ERROR_LOCK_VIOLATION` from `winerror.h`.
+    matches!(io_error.raw_os_error(), Some(32) | Some(33))
+}
+
+/// Check if a file couldn't be opened or read because another program has it open or locked.
+///
+/// Other platforms only have advisory locks, which don't stop FolSum from reading.
+#[cfg(not(windows))]
+fn is_file_in_use(_io_error: &io::Error) -> bool {
+    false
+}
+
+/// File that's quick hashed in the background, along with its hashes once they're ready.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuickHash {
+    pub file_path: PathBuf,
+    // Counts up with each file that's hashed, so hashes of a file that was picked earlier (even the same file) are
+    // never mistaken for the latest one's.
+    pub generation: u64,
+    // Hashes of the file, which are `None` until hashing finishes.
+    pub hash_result: Option<Result<FileHashes, &'static str>>,
+}
+
+/// Hash one file in a separate thread so the GUI doesn't freeze on big files.
+///
+/// Returns the status of this file's hashing, which fails if hashing panics. If another file's picked before this
+/// one's done, this one's hashes are thrown away instead of showing up under the other file.
+pub fn hash_file_in_background(
+    file_path: PathBuf,
+    read_buffer_limits: &ReadBufferLimits,
+    quick_hash: &Arc<Mutex<Option<QuickHash>>>,
+) -> Arc<Mutex<WorkerStatus>> {
+    // Forget the previous file's hashes so they aren't mistaken for this file's.
+    let mut locked_hash = lock_shared(quick_hash);
+    let generation: u64 = locked_hash
+        .as_ref()
+        .map_or(0, |previous_hash| previous_hash.generation + 1);
+    *locked_hash = Some(QuickHash {
+        file_path: file_path.clone(),
+        generation,
+        hash_result: None,
+    });
+    drop(locked_hash);
+    let quick_hash_copy = Arc::clone(quick_hash);
+    let mut read_buffer = ReadBuffer::new(read_buffer_limits);
+    let hash_status = Arc::new(Mutex::new(WorkerStatus::default()));
+    spawn_worker(&hash_status, move || {
+        let file_hashes = hash_file_with_buffer(&file_path, &mut read_buffer);
+        match &mut *lock_shared(&quick_hash_copy) {
+            Some(latest_hash) if latest_hash.generation == generation => {
+                latest_hash.hash_result = Some(file_hashes);
+            }
+            // Another file was picked while this one was hashing, so its hashes are the ones to show.
+            _ => (),
+        }
+        Ok(())
+    });
+    hash_status
+}
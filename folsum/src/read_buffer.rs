@@ -0,0 +1,136 @@
+use web_time::Duration;
+
+/// Smallest read buffer that can be chosen in settings (4 KiB), which is one page of memory.
+pub const SMALLEST_READ_BUFFER_KIB: usize = 4;
+/// Biggest read buffer that can be chosen in settings (256 MiB).
+pub const BIGGEST_READ_BUFFER_KIB: usize = 256 * 1024;
+
+/// User-configurable limits on how files are read while they're hashed.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)] // Define default fields when deserializing old state.
+pub struct ReadBufferLimits {
+    // Smallest buffer that tuning shrinks to, in kibibytes.
+    pub min_kib: usize,
+    // Biggest buffer that tuning grows to, in kibibytes.
+    pub max_kib: usize,
+    // Most files that are read from the same device at once, across every summarization, export, and quick hash.
+    pub readers_per_device: usize,
+}
+
+impl Default for ReadBufferLimits {
+    fn default() -> Self {
+        Self {
+            // Same size that files were always read in, which suits local disks.
+            min_kib: 64,
+            // Big enough to keep network shares busy without holding much memory.
+            max_kib: 8 * 1024,
+            // Hard drives are slowed down by reading more than one file at a time.
+            readers_per_device: 1,
+        }
+    }
+}
+
+impl ReadBufferLimits {
+    /// Limits in bytes, with the minimum and maximum swapped if they were entered backwards.
+    fn byte_range(&self) -> (usize, usize) {
+        let min_bytes: usize = self
+            .min_kib
+            .clamp(SMALLEST_READ_BUFFER_KIB, BIGGEST_READ_BUFFER_KIB)
+            * 1024;
+        let max_bytes: usize = self
+            .max_kib
+            .clamp(SMALLEST_READ_BUFFER_KIB, BIGGEST_READ_BUFFER_KIB)
+            * 1024;
+        (min_bytes.min(max_bytes), min_bytes.max(max_bytes))
+    }
+}
+
+/// Buffer that files are read into while hashing, which sizes itself for each file and for how fast reads are going.
+///
+/// Small reads bottleneck network shares, where every read is a round trip, while huge buffers waste memory on tiny
+/// files. Tiny files only get as much buffer as they need. For the rest, the buffer's size is tuned between the
+/// limits by trying a bigger (or smaller) buffer after each big file and keeping it if reads got faster.
+#[derive(Clone, Debug)]
+pub struct ReadBuffer {
+    min_bytes: usize,
+    max_bytes: usize,
+    readers_per_device: usize,
+    // Size that tuning's settled on so far.
+    tuned_bytes: usize,
+    // Whether the last change made the buffer bigger, so tuning keeps going that way while it helps.
+    growing: bool,
+    // Throughput (in bytes per second) of the last file that was read with a tuned buffer.
+    last_throughput: Option<f64>,
+    bytes: Vec<u8>,
+}
+
+impl ReadBuffer {
+    /// Start tuning at the smallest size the limits allow.
+    pub fn new(limits: &ReadBufferLimits) -> Self {
+        let (min_bytes, max_bytes) = limits.byte_range();
+        Self {
+            min_bytes,
+            max_bytes,
+            readers_per_device: limits.readers_per_device,
+            tuned_bytes: min_bytes,
+            growing: true,
+            last_throughput: None,
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Size that files big enough to fill the buffer are currently read in.
+    pub fn tuned_size(&self) -> usize {
+        self.tuned_bytes
+    }
+
+    /// Most reads that should happen on the same device at once, for [`DeviceReadPermit`]s.
+    ///
+    /// [`DeviceReadPermit`]: crate::DeviceReadPermit
+    pub fn readers_per_device(&self) -> usize {
+        self.readers_per_device
+    }
+
+    /// Get a buffer to read a file of the given size into.
+    ///
+    /// Files smaller than the tuned size get a buffer with just one byte to spare, so that they're read in one go
+    /// and the end of the file is noticed without another round trip.
+    pub fn for_file(&mut self, file_size: u64) -> &mut [u8] {
+        let wanted_bytes: usize = usize::try_from(file_size.saturating_add(1))
+            .unwrap_or(usize::MAX)
+            .min(self.tuned_bytes);
+        // Keep the memory from earlier files around, since allocating for every file is slower than reusing it.
+        if self.bytes.len() < wanted_bytes {
+            self.bytes.resize(wanted_bytes, 0);
+        }
+        &mut self.bytes[..wanted_bytes]
+    }
+
+    /// Tune the buffer's size with how long it took to read a file, not counting time spent hashing it.
+    ///
+    /// Files that didn't fill the buffer a few times over are too small to say anything about its size, so they're
+    /// left out.
+    pub fn record_read(&mut self, bytes_read: u64, read_time: Duration) {
+        if bytes_read < 4 * self.tuned_bytes as u64 || read_time.is_zero() {
+            return;
+        }
+        let throughput: f64 = bytes_read as f64 / read_time.as_secs_f64();
+        // Turn around when the last change made reads slower, and keep going when it made them faster (or stay put at
+        // a limit, until reads slow down).
+        if let Some(last_throughput) = self.last_throughput {
+            if throughput < last_throughput {
+                self.growing = !self.growing;
+            }
+        }
+        self.last_throughput = Some(throughput);
+        self.tuned_bytes = match self.growing {
+            true => self.tuned_bytes.saturating_mul(2).min(self.max_bytes),
+            false => (self.tuned_bytes / 2).max(self.min_bytes),
+        };
+        // Give back memory that a much bigger buffer was holding onto.
+        if self.bytes.len() > 2 * self.tuned_bytes {
+            self.bytes.truncate(self.tuned_bytes);
+            self.bytes.shrink_to_fit();
+        }
+    }
+}
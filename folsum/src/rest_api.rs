@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::{
+    audit_verdict, lock_shared, sort_counts, AuditVerdict, ChecksumFinding, PhaseTimings,
+    SeverityRules, WorkerStatus,
+};
+
+/// Most clients that are answered at once. Clients past this are told to try again later instead of each getting a
+/// thread, so a flood of connections can't use up the machine's threads while it's summarizing.
+pub const MOST_API_CLIENTS: usize = 8;
+/// Longest request header that's read before giving up on a client, so a misbehaving one can't eat up memory.
+const MAX_HEADER_BYTES: u64 = 8 * 1024;
+/// How long to wait between checking for new connections and for the server being stopped.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// How long to wait on a client that's gone quiet partway through its request before hanging up on it.
+const CLIENT_READ_TIMEOUT: Duration = Duration::from_millis(500);
+/// How long to wait on a client that's being turned away to send its request, which holds up accepting other clients.
+const TURNED_AWAY_READ_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// User's settings for the results API, which other programs (like case management systems) pull results from.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)] // Define default fields when deserializing old state.
+pub struct ApiSettings {
+    pub enabled: bool,
+    // Port on `127.0.0.1` that the API listens on.
+    pub port: u16,
+    // Secret that requests must send as `Authorization: Bearer <token>`.
+    pub token: String,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 7878,
+            token: String::new(),
+        }
+    }
+}
+
+/// Summarization state that the API reads from, shared with the GUI.
+#[derive(Clone)]
+pub struct ApiResults {
+    pub summarization_path: Arc<Mutex<Option<PathBuf>>>,
+    pub extension_counts: Arc<Mutex<HashMap<String, u32>>>,
+    pub extension_bytes: Arc<Mutex<HashMap<String, u64>>>,
+    pub checksum_findings: Arc<Mutex<Vec<ChecksumFinding>>>,
+    pub severity_rules: Arc<Mutex<SeverityRules>>,
+    pub phase_timings: Arc<Mutex<PhaseTimings>>,
+    pub summarization_status: Arc<Mutex<WorkerStatus>>,
+}
+
+/// Running results API, which stops when it's dropped.
+pub struct ApiServer {
+    // Port that the server's listening on, which is picked by the OS if port 0 was asked for.
+    pub port: u16,
+    stop_requested: Arc<AtomicBool>,
+    listener_thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        self.stop_requested.store(true, Ordering::SeqCst);
+        // Wait for the listener to close so its port is free again, like when the user changes ports.
+        if let Some(listener_thread) = self.listener_thread.take() {
+            let _join_result = listener_thread.join();
+        }
+    }
+}
+
+/// Serve read-only summarization results as JSON on `127.0.0.1` in a separate thread.
+///
+/// Endpoints are `GET /summary` (chosen directory, status, and extension counts and sizes) and `GET /checksums`
+/// (checksum file findings). Every request needs the token from `api_settings`.
+pub fn serve_results(
+    api_settings: &ApiSettings,
+    api_results: &ApiResults,
+) -> Result<ApiServer, &'static str> {
+    // Refuse to serve without a token so results aren't open to every program on the machine.
+    if api_settings.token.trim().is_empty() {
+        return Err("Set an API token before turning on the results API");
+    }
+    // Only listen on the loopback interface so results never leave the machine.
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, api_settings.port))
+        .map_err(|_| "Couldn't start the results API. Is another program using its port?")?;
+    let port: u16 = listener
+        .local_addr()
+        .map_err(|_| "Couldn't find the results API's port")?
+        .port();
+    // Poll for connections instead of blocking so the thread notices when it's asked to stop.
+    listener
+        .set_nonblocking(true)
+        .map_err(|_| "Couldn't start the results API")?;
+    let stop_requested = Arc::new(AtomicBool::new(false));
+    let stop_requested_copy = Arc::clone(&stop_requested);
+    let api_token: String = api_settings.token.trim().to_string();
+    let api_results: ApiResults = api_results.clone();
+    let listener_thread = thread::spawn(move || {
+        // Number of clients that are being answered right now.
+        let busy_clients = Arc::new(AtomicUsize::new(0));
+        while !stop_requested_copy.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((client, _)) if busy_clients.load(Ordering::SeqCst) >= MOST_API_CLIENTS => {
+                    let _response_result = turn_away(client);
+                }
+                Ok((client, _)) => {
+                    // Answer each client in its own thread, so one that's slow to send its request doesn't hold up
+                    // everyone else's.
+                    let (client_token, client_results) = (api_token.clone(), api_results.clone());
+                    let client_slot = ClientSlot::take(&busy_clients);
+                    thread::spawn(move || {
+                        let _response_result =
+                            answer_request(client, &client_token, &client_results);
+                        drop(client_slot);
+                    });
+                }
+                Err(_) => thread::sleep(ACCEPT_POLL_INTERVAL),
+            }
+        }
+    });
+    Ok(ApiServer {
+        port,
+        stop_requested,
+        listener_thread: Some(listener_thread),
+    })
+}
+
+/// Read one HTTP request from a client and write back the response.
+fn answer_request(
+    mut client: TcpStream,
+    api_token: &str,
+    api_results: &ApiResults,
+) -> io::Result<()> {
+    // The listener doesn't block, but reading from and writing to clients should.
+    client.set_nonblocking(false)?;
+    client.set_read_timeout(Some(CLIENT_READ_TIMEOUT))?;
+    let mut request_reader = BufReader::new(client.try_clone()?.take(MAX_HEADER_BYTES));
+    let mut request_line = String::new();
+    request_reader.read_line(&mut request_line)?;
+    // Look through the headers for the token. The body (if any) is ignored because every endpoint's read-only.
+    let mut sent_token: Option<String> = None;
+    loop {
+        let mut header_line = String::new();
+        if request_reader.read_line(&mut header_line)? == 0 || header_line.trim().is_empty() {
+            break;
+        }
+        if let Some((header_name, header_value)) = header_line.split_once(':') {
+            if header_name.trim().eq_ignore_ascii_case("authorization") {
+                sent_token = header_value
+                    .trim()
+                    .strip_prefix("Bearer ")
+                    .map(|token| token.trim().to_string());
+            }
+        }
+    }
+    let token_accepted: bool =
+        sent_token.map_or(false, |sent_token| tokens_match(&sent_token, api_token));
+    let mut request_parts = request_line.split_whitespace();
+    let (status_line, response_body): (&str, Value) =
+        match (token_accepted, request_parts.next(), request_parts.next()) {
+            (false, _, _) => (
+                "401 Unauthorized",
+                json!({"error": "Missing or wrong API token"}),
+            ),
+            (true, Some("GET"), Some("/summary")) => ("200 OK", summary_json(api_results)),
+            (true, Some("GET"), Some("/checksums")) => ("200 OK", checksums_json(api_results)),
+            (true, Some("GET"), _) => ("404 Not Found", json!({"error": "No such endpoint"})),
+            (true, _, _) => (
+                "405 Method Not Allowed",
+                json!({"error": "The results API is read-only"}),
+            ),
+        };
+    write_response(&mut client, status_line, &response_body)
+}
+
+/// Tell a client that too many others are being answered right now.
+fn turn_away(mut client: TcpStream) -> io::Result<()> {
+    client.set_nonblocking(false)?;
+    client.set_read_timeout(Some(TURNED_AWAY_READ_TIMEOUT))?;
+    // Read the request first, since hanging up with it unread makes the OS reset the connection, which can throw away
+    // the response before the client reads it.
+    let mut request_reader = BufReader::new(client.try_clone()?.take(MAX_HEADER_BYTES));
+    let mut request_line = String::new();
+    while matches!(request_reader.read_line(&mut request_line), Ok(bytes_read) if bytes_read > 0)
+        && request_line != "\r\n"
+        && request_line != "\n"
+    {
+        request_line.clear();
+    }
+    write_response(
+        &mut client,
+        "503 Service Unavailable",
+        &json!({"error": "Too many clients at once, try again shortly"}),
+    )
+}
+
+/// Spot among the [`MOST_API_CLIENTS`] that are answered at once, which is given back when it's dropped.
+///
+/// Dropping it, instead of counting the client out by hand, gives the spot back even if answering panics.
+struct ClientSlot {
+    busy_clients: Arc<AtomicUsize>,
+}
+
+impl ClientSlot {
+    fn take(busy_clients: &Arc<AtomicUsize>) -> Self {
+        busy_clients.fetch_add(1, Ordering::SeqCst);
+        Self {
+            busy_clients: Arc::clone(busy_clients),
+        }
+    }
+}
+
+impl Drop for ClientSlot {
+    fn drop(&mut self) {
+        self.busy_clients.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Write a JSON response to a client and hang up.
+fn write_response(
+    client: &mut TcpStream,
+    status_line: &str,
+    response_body: &Value,
+) -> io::Result<()> {
+    let response_body: String = response_body.to_string();
+    write!(
+        client,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status_line,
+        response_body.len(),
+        response_body
+    )?;
+    client.flush()
+}
+
+/// Compare tokens without bailing out at the first difference, so response times don't give hints about the token.
+fn tokens_match(sent_token: &str, api_token: &str) -> bool {
+    sent_token.len() == api_token.len()
+        && sent_token
+            .bytes()
+            .zip(api_token.bytes())
+            .fold(0, |difference, (sent_byte, api_byte)| {
+                difference | (sent_byte ^ api_byte)
+            })
+            == 0
+}
+
+/// Describe the chosen directory, the summarization's status, and each extension's count and size.
+fn summary_json(api_results: &ApiResults) -> Value {
+    let summarized_directory: Option<String> = lock_shared(&api_results.summarization_path)
+        .as_ref()
+        .map(|directory| directory.display().to_string());
+    let (status, failure_reason): (&str, Option<String>) =
+        match &*lock_shared(&api_results.summarization_status) {
+            WorkerStatus::Idle => ("idle", None),
+            WorkerStatus::InProgress => ("in_progress", None),
+            WorkerStatus::Finished => ("finished", None),
+            WorkerStatus::Failed(reason) => ("failed", Some(reason.clone())),
+        };
+    let locked_counts = lock_shared(&api_results.extension_counts);
+    let locked_bytes = lock_shared(&api_results.extension_bytes);
+    // List extensions in the same order as the results table and CSV exports.
+    let extensions: Vec<Value> = sort_counts(&locked_counts)
+        .into_iter()
+        .map(|(extension_name, occurrences)| {
+            json!({
+                "extension": extension_name,
+                "occurrences": occurrences,
+                "bytes": locked_bytes.get(extension_name).copied().unwrap_or(0),
+            })
+        })
+        .collect();
+    let locked_timings = lock_shared(&api_results.phase_timings);
+    let verdict: AuditVerdict = audit_verdict(
+        &lock_shared(&api_results.checksum_findings),
+        &lock_shared(&api_results.severity_rules),
+    );
+    let metadata_drift: Vec<String> = verdict
+        .metadata_drift
+        .iter()
+        .map(|drifted_file| drifted_file.display().to_string())
+        .collect();
+    json!({
+        "directory": summarized_directory,
+        "status": status,
+        "failure_reason": failure_reason,
+        // Add up as `u64`, since the counts of several extensions can add up past what a `u32` holds.
+        "total_files": locked_counts.values().map(|count| u64::from(*count)).sum::<u64>(),
+        "total_bytes": locked_bytes.values().sum::<u64>(),
+        "extensions": extensions,
+        // Milliseconds that each phase took, for capacity planning.
+        "timings": {
+            "walk_ms": locked_timings.walk.as_millis() as u64,
+            "checksum_verification_ms": locked_timings.checksum_verification.as_millis() as u64,
+            "export_write_ms": locked_timings.export_write.map(|export_write| export_write.as_millis() as u64),
+        },
+        // Checksum files' verdict, where metadata drift doesn't fail the content check.
+        "audit": {
+            "content": verdict.content.name(),
+            "metadata_drift": metadata_drift,
+        },
+    })
+}
+
+/// List how each file that's listed in a checksum file held up.
+fn checksums_json(api_results: &ApiResults) -> Value {
+    let severity_rules: SeverityRules = lock_shared(&api_results.severity_rules).clone();
+    let findings: Vec<Value> = lock_shared(&api_results.checksum_findings)
+        .iter()
+        .map(|finding| {
+            json!({
+                "checksum_file": finding.checksum_file.display().to_string(),
+                "listed_file": finding.listed_file.display().to_string(),
+                "outcome": finding.outcome.name(),
+                "severity": severity_rules.severity(finding).name(),
+            })
+        })
+        .collect();
+    Value::Array(findings)
+}
@@ -0,0 +1,116 @@
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::hash_bytes;
+
+/// Messages with published answers: the empty message and the one- and two-block examples from RFC 1321 and
+/// FIPS 180, with their MD5, SHA-1, and SHA-256 hashes.
+const KNOWN_ANSWERS: [(&[u8], [&str; 3]); 3] = [
+    (
+        b"",
+        [
+            "d41d8cd98f00b204e9800998ecf8427e",
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ],
+    ),
+    (
+        b"abc",
+        [
+            "900150983cd24fb0d6963f7d28e17f72",
+            "a9993e364706816aba3e25717850c26c9cd0d89d",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ],
+    ),
+    (
+        b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+        [
+            "8215ef0796a20bcaaae116d3876c664a",
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        ],
+    ),
+];
+
+/// Result of checking FolSum's hash algorithms against messages with published answers.
+///
+/// Labs that have to validate their tools can point to this instead of hashing test files by hand. It's run at
+/// startup, again from "Help > Validate tool", and its result is written in the log and in every export's header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelfTest {
+    // Each algorithm that FolSum hashes with (MD5 and SHA-256 everywhere, plus SHA-1 for SPDX), and whether it gave
+    // the published answer for every message.
+    pub algorithms: Vec<(&'static str, bool)>,
+}
+
+impl SelfTest {
+    /// Check if every algorithm gave every published answer.
+    pub fn passed(&self) -> bool {
+        self.algorithms.iter().all(|(_, passed)| *passed)
+    }
+
+    /// Describe the result in one line, like `passed (MD5, SHA-1, SHA-256)`, for logs and export headers.
+    pub fn summary(&self) -> String {
+        let algorithm_names = |wanted_outcome: bool| -> String {
+            self.algorithms
+                .iter()
+                .filter(|(_, passed)| *passed == wanted_outcome)
+                .map(|(algorithm_name, _)| *algorithm_name)
+                .collect::<Vec<&str>>()
+                .join(", ")
+        };
+        match self.passed() {
+            true => format!("passed ({})", algorithm_names(true)),
+            false => format!("FAILED ({})", algorithm_names(false)),
+        }
+    }
+}
+
+/// Hash each known-answer message with every algorithm that FolSum uses, and log how it went.
+///
+/// Messages are hashed all at once and in two pieces, since files are hashed a buffer at a time.
+pub fn run_self_test() -> SelfTest {
+    // Answers are listed in MD5, SHA-1, SHA-256 order.
+    let hashes_match = |hash_message: &dyn Fn(&[u8]) -> String, answer_index: usize| {
+        KNOWN_ANSWERS
+            .iter()
+            .all(|(message, answers)| hash_message(message) == answers[answer_index])
+    };
+    let self_test = SelfTest {
+        algorithms: vec![
+            (
+                "MD5",
+                hashes_match(&|message| hash_bytes(message).md5, 0)
+                    && hashes_match(&|message| hash_in_pieces::<Md5>(message), 0),
+            ),
+            (
+                "SHA-1",
+                hashes_match(&|message| format!("{:x}", Sha1::digest(message)), 1)
+                    && hashes_match(&|message| hash_in_pieces::<Sha1>(message), 1),
+            ),
+            (
+                "SHA-256",
+                hashes_match(&|message| hash_bytes(message).sha256, 2)
+                    && hashes_match(&|message| hash_in_pieces::<Sha256>(message), 2),
+            ),
+        ],
+    };
+    match self_test.passed() {
+        true => log::info!("Tool self-test {}", self_test.summary()),
+        false => log::error!("Tool self-test {}", self_test.summary()),
+    }
+    self_test
+}
+
+/// Hash a message in two pieces, the way a file that doesn't fit in one read buffer is hashed.
+fn hash_in_pieces<D: Digest>(message: &[u8]) -> String
+where
+    sha2::digest::Output<D>: std::fmt::LowerHex,
+{
+    let (first_piece, second_piece) = message.split_at(message.len() / 2);
+    let mut hasher = D::new();
+    hasher.update(first_piece);
+    hasher.update(second_piece);
+    format!("{:x}", hasher.finalize())
+}
@@ -1,68 +1,465 @@
-use std::collections::HashMap;
-use std::ffi::{OsStr, OsString};
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+#[cfg(windows)]
+use std::fs::File;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::thread;
 
 #[cfg(not(target_arch = "wasm32"))]
-use walkdir::WalkDir;
+use walkdir::{DirEntry, WalkDir};
 #[cfg(not(target_arch = "wasm32"))]
 use web_time::{Duration, Instant};
 
+use crate::{
+    allocated_size, find_extra_streams, is_checksum_file, is_sparse, lock_shared,
+    read_checksum_listing, spawn_worker, stop_requested, verify_listed_file, write_system_log,
+    AuditEvent, AuditReport, ChecksumFinding, ChecksumListing, ChecksumOutcome, ExtraStream,
+    IgnorePresets, InventoryStats, ReadBuffer, ReadBufferLimits, SeverityRules, SizeOnlyRules,
+    StorageTotals, VerificationProgress, WorkerStatus, STOPPED_REASON,
+};
+
+/// Group name for files that sit directly inside of the chosen directory instead of in a subdirectory.
+pub const TOP_LEVEL_GROUP: &str = "(files in chosen directory)";
+
+/// Name of the results table row that a file's counted under, like `pdf`, or `No extension`.
+pub fn extension_name(file_path: &Path) -> String {
+    // Categorize extensionless files as "No extension."
+    file_path
+        .extension()
+        .unwrap_or_else(|| OsStr::new("No extension"))
+        .to_string_lossy()
+        .to_string()
+}
+
+/// User-configurable limits on which parts of a directory tree get summarized.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)] // Define default fields when deserializing old state.
+pub struct SummarizationOptions {
+    // Don't descend into directories that are on a different filesystem (mount points) than the chosen directory.
+    pub same_file_system: bool,
+    // Deepest level of subdirectories to summarize, where files directly inside of the chosen directory are depth 1.
+    pub max_depth: Option<usize>,
+    // Check the files that are listed in checksum files (like `checksums.md5`) that are found while summarizing.
+    pub verify_checksum_files: bool,
+    // Listed files that only get their size recorded, instead of being hashed, when checksum files are verified.
+    pub size_only_rules: SizeOnlyRules,
+    // Check listed files that were locked by another program a second time, after every other file's been checked.
+    pub retry_locked_files: bool,
+    // Look for data that's hidden alongside files in alternate data streams (Windows) or extended attributes.
+    pub find_extra_streams: bool,
+    // Record when summarizations start and finish, and any checksum discrepancies, in syslog or the Windows Event Log.
+    pub write_system_log: bool,
+    // Leave out files that operating systems scatter around, like `.DS_Store` and `Thumbs.db`.
+    pub ignore_presets: IgnorePresets,
+    // Bounds on the buffer that files are read into while they're hashed, which tunes itself in between.
+    pub read_buffer_limits: ReadBufferLimits,
+    // How much each kind of checksum finding matters, which decides whether the audit passes.
+    pub severity_rules: SeverityRules,
+}
+
+/// How long each phase of the latest run took, for seeing where the time goes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PhaseTimings {
+    // Walking the directory: counting and sizing files, and looking for extra streams.
+    pub walk: Duration,
+    // Hashing the files that checksum files list and comparing them to their listed hashes, including retries.
+    pub checksum_verification: Duration,
+    // Writing the latest export, which happens after summarizing, so it's timed by whatever starts the export.
+    pub export_write: Option<Duration>,
+}
+
+/// Results of the latest summarization, which the summarization thread fills in while the GUI shows them.
+///
+/// Each piece of state has its own lock so the GUI can read one while the summarization's writing another. Cloning it
+/// shares the same results instead of copying them.
+#[derive(Clone, Debug)]
+pub struct SummarizationState {
+    // Unique file extensions and the number of times each one was encountered.
+    pub extension_counts: Arc<Mutex<HashMap<String, u32>>>,
+    // Total size, in bytes, of the files with each unique file extension.
+    pub extension_bytes: Arc<Mutex<HashMap<String, u64>>>,
+    // How much room the summarized files take up on disk, and how many of them are sparse.
+    pub storage_totals: Arc<Mutex<StorageTotals>>,
+    // Time that summarization starts so it can be used to calculate the time taken.
+    pub summarization_start: Arc<Mutex<Instant>>,
+    // Amount of time that it takes to summarize a directory.
+    pub time_taken: Arc<Mutex<Duration>>,
+    // Number of extra hardlinks that were skipped because the file they point to was already counted.
+    pub hardlink_count: Arc<Mutex<u32>>,
+    // Extension counts for each folder directly inside of the chosen directory.
+    pub folder_counts: Arc<Mutex<HashMap<String, HashMap<String, u32>>>>,
+    // Results of checking the files that are listed in checksum files found while summarizing.
+    pub checksum_findings: Arc<Mutex<Vec<ChecksumFinding>>>,
+    // Alternate data streams and extended attributes that were found attached to summarized files.
+    pub extra_streams: Arc<Mutex<Vec<ExtraStream>>>,
+    // How deep the summarized files are and which ones are the biggest, for the statistics export.
+    pub inventory_stats: Arc<Mutex<InventoryStats>>,
+    // How long each phase of the latest run took.
+    pub phase_timings: Arc<Mutex<PhaseTimings>>,
+    // How many of the files that checksum files list have been checked so far.
+    pub verification_progress: Arc<Mutex<VerificationProgress>>,
+    // Severity rules that the latest summarization was started with, which its findings are judged by.
+    pub severity_rules: Arc<Mutex<SeverityRules>>,
+    // Report of what checking checksum files turned up, once a summarization that checks them finishes.
+    pub audit_report: Arc<Mutex<Option<AuditReport>>>,
+    // Whether the latest summarization is still running, finished, or failed.
+    pub summarization_status: Arc<Mutex<WorkerStatus>>,
+}
+
+impl Default for SummarizationState {
+    fn default() -> Self {
+        Self {
+            extension_counts: Arc::new(Mutex::new(HashMap::new())),
+            extension_bytes: Arc::new(Mutex::new(HashMap::new())),
+            storage_totals: Arc::new(Mutex::new(StorageTotals::default())),
+            summarization_start: Arc::new(Mutex::new(Instant::now())),
+            time_taken: Arc::new(Mutex::new(Duration::ZERO)),
+            hardlink_count: Arc::new(Mutex::new(0)),
+            folder_counts: Arc::new(Mutex::new(HashMap::new())),
+            checksum_findings: Arc::new(Mutex::new(Vec::new())),
+            extra_streams: Arc::new(Mutex::new(Vec::new())),
+            inventory_stats: Arc::new(Mutex::new(InventoryStats::default())),
+            phase_timings: Arc::new(Mutex::new(PhaseTimings::default())),
+            verification_progress: Arc::new(Mutex::new(VerificationProgress::default())),
+            severity_rules: Arc::new(Mutex::new(SeverityRules::default())),
+            audit_report: Arc::new(Mutex::new(None)),
+            summarization_status: Arc::new(Mutex::new(WorkerStatus::default())),
+        }
+    }
+}
+
 pub fn summarize_directory(
     summarization_path: &Arc<Mutex<Option<PathBuf>>>,
-    extension_counts: &Arc<Mutex<HashMap<String, u32>>>,
-    summarization_start: &Arc<Mutex<Instant>>,
-    time_taken: &Arc<Mutex<Duration>>,
+    summarization_options: &SummarizationOptions,
+    summarization_state: &SummarizationState,
 ) -> Result<(), &'static str> {
-    let locked_path: &mut Option<PathBuf> = &mut *summarization_path.lock().unwrap();
+    let SummarizationState {
+        extension_counts,
+        extension_bytes,
+        storage_totals,
+        hardlink_count,
+        folder_counts,
+        checksum_findings,
+        extra_streams,
+        inventory_stats,
+        phase_timings,
+        verification_progress,
+        severity_rules,
+        audit_report,
+        summarization_status,
+        ..
+    } = summarization_state;
+    let locked_path: &mut Option<PathBuf> = &mut *lock_shared(summarization_path);
+    // Refuse to start while the last summarization is still running, like when "Summarize" is clicked twice quickly.
+    // Otherwise both walkers would count every file into the same results, so each file would be counted twice. The
+    // chosen path stays locked until the worker's marked as in progress, so a second start always sees the first one.
+    if *lock_shared(summarization_status) == WorkerStatus::InProgress {
+        return Err("A summarization is already running");
+    }
     // If the user picked a directory to summarize....
     if locked_path.is_some() {
         // ...then recursively count file extensions in the chosen directory.
         // Reset file extension counts to zero.
-        *extension_counts.lock().unwrap() = HashMap::new();
+        *lock_shared(extension_counts) = HashMap::new();
+        // Reset the total size of each file extension to zero.
+        *lock_shared(extension_bytes) = HashMap::new();
+        // Reset the room that files take up on disk to zero.
+        *lock_shared(storage_totals) = StorageTotals::default();
+        // Reset the number of skipped hardlinks to zero.
+        *lock_shared(hardlink_count) = 0;
+        // Forget extension counts from the previous summarization's top-level folders.
+        *lock_shared(folder_counts) = HashMap::new();
+        // Forget the previous summarization's checksum results.
+        *lock_shared(checksum_findings) = Vec::new();
+        // Forget how far the previous run got through checking listed files.
+        *lock_shared(verification_progress) = VerificationProgress::default();
+        // Judge this run's findings by the rules that it was started with, even if they're changed partway through.
+        *lock_shared(severity_rules) = summarization_options.severity_rules.clone();
+        // Forget the previous run's audit report, so it's not mistaken for this one's while it runs.
+        *lock_shared(audit_report) = None;
+        // Forget the previous summarization's extra streams.
+        *lock_shared(extra_streams) = Vec::new();
+        // Forget the previous summarization's depths and largest files.
+        *lock_shared(inventory_stats) = InventoryStats::default();
+        // Forget how long the previous run's phases took, including its export.
+        *lock_shared(phase_timings) = PhaseTimings::default();
 
         // Copy the Arcs of persistent members so they can be accessed by a separate thread.
-        let extension_counts_copy = Arc::clone(&extension_counts);
-        let summarization_path_copy = Arc::clone(&summarization_path);
-        let start_copy = Arc::clone(&summarization_start);
-        let time_taken_copy = Arc::clone(&time_taken);
-
-        thread::spawn(move || {
-            // Categorize extensionless files as "No extension."
-            let default_extension = OsString::from("No extension");
+        let SummarizationState {
+            extension_counts: extension_counts_copy,
+            extension_bytes: extension_bytes_copy,
+            storage_totals: storage_totals_copy,
+            summarization_start: start_copy,
+            time_taken: time_taken_copy,
+            hardlink_count: hardlink_count_copy,
+            folder_counts: folder_counts_copy,
+            checksum_findings: checksum_findings_copy,
+            extra_streams: extra_streams_copy,
+            inventory_stats: inventory_stats_copy,
+            phase_timings: phase_timings_copy,
+            verification_progress: verification_progress_copy,
+            audit_report: audit_report_copy,
+            ..
+        } = summarization_state.clone();
+        let summarization_path_copy = Arc::clone(summarization_path);
+        // Copy the user's options so they can be moved into the summarization thread.
+        let options_copy: SummarizationOptions = summarization_options.clone();
 
+        spawn_worker(summarization_status, move || {
             // Start the stopwatch for summarization time.
-            let mut locked_start_copy = start_copy.lock().unwrap();
-            *locked_start_copy = Instant::now();
+            let summarization_start: Instant = Instant::now();
+            *lock_shared(&start_copy) = summarization_start;
 
-            let locked_summarization_path = summarization_path_copy.lock().unwrap();
+            let locked_summarization_path = lock_shared(&summarization_path_copy);
             // Clone the user's chosen path so we can release it's lock, allowing live table updates.
             let summarization_path_copy = locked_summarization_path.clone();
             // Release the mutex lock on the chosen path so extension count table can update.
             drop(locked_summarization_path);
+            // Keep the chosen directory around so top-level folders can be found relative to it.
+            let summarization_root: PathBuf = summarization_path_copy.clone().unwrap();
+            if options_copy.write_system_log {
+                record_audit_event(&AuditEvent::summarization_started(&summarization_root));
+            }
 
-            // Recursively iterate through each subdirectory and don't add subdirectories to the result.
-            for entry in WalkDir::new(summarization_path_copy.unwrap())
+            // Recursively walk subdirectories, staying on the chosen directory's filesystem if the user asked to.
+            let mut directory_walker = WalkDir::new(summarization_path_copy.unwrap())
                 .min_depth(1)
+                .same_file_system(options_copy.same_file_system);
+            // Stop descending into subdirectories past the user's chosen depth (if they chose one).
+            if let Some(max_depth) = options_copy.max_depth {
+                directory_walker = directory_walker.max_depth(max_depth);
+            }
+            // Remember files with multiple hardlinks so the same data isn't counted once per link.
+            let mut seen_hardlinks: HashSet<(u64, u64)> = HashSet::new();
+            // Collect checksum files so the files they list can be checked once counting's done.
+            let mut found_checksum_files: Vec<PathBuf> = Vec::new();
+            // Recursively iterate through each subdirectory and don't add subdirectories to the result.
+            for entry in directory_walker
                 .into_iter()
+                // Skip ignored OS metadata, along with everything inside of ignored folders like `.Spotlight-V100`.
+                .filter_entry(|entry| !options_copy.ignore_presets.ignores(entry.file_name()))
                 .filter_map(Result::ok)
                 .filter(|e| !e.file_type().is_dir())
             {
+                // Give up between files if FolSum's closing, instead of counting into a GUI that's going away.
+                if stop_requested() {
+                    return Err(String::from(STOPPED_REASON));
+                }
+                // If this is another link to a file that was already counted, then note it and move on.
+                if let Some(file_identity) = hardlinked_file_identity(&entry) {
+                    if !seen_hardlinks.insert(file_identity) {
+                        *lock_shared(&hardlink_count_copy) += 1;
+                        continue;
+                    }
+                }
+                if options_copy.find_extra_streams {
+                    let found_streams: Vec<ExtraStream> = find_extra_streams(entry.path());
+                    if !found_streams.is_empty() {
+                        lock_shared(&extra_streams_copy).extend(found_streams);
+                    }
+                }
+                if options_copy.verify_checksum_files && is_checksum_file(entry.path()) {
+                    found_checksum_files.push(entry.path().to_path_buf());
+                }
                 // Extract the file extension from the file's name.
-                let file_ext: &OsStr = entry.path().extension().unwrap_or(&default_extension);
-                let show_ext: String = String::from(file_ext.to_string_lossy());
+                let show_ext: String = extension_name(entry.path());
                 // Lock the extension counts variable so we can add a file to it.
-                let mut locked_counts_copy = extension_counts_copy.lock().unwrap();
+                let mut locked_counts_copy = lock_shared(&extension_counts_copy);
                 // Add newly encountered file extensions to known file extensions with a counter of 0.
-                let counter: &mut u32 = locked_counts_copy.entry(show_ext).or_insert(0);
+                let counter: &mut u32 = locked_counts_copy.entry(show_ext.clone()).or_insert(0);
                 // Increment the counter for known file extensions by one.
                 *counter += 1;
+                // Release the overall counts before tallying the same file for its top-level folder.
+                drop(locked_counts_copy);
+                // Add the file's size to its extension's total. Files whose size can't be read count as empty.
+                let file_metadata = entry.metadata().ok();
+                let file_size: u64 = file_metadata
+                    .as_ref()
+                    .map_or(0, |file_metadata| file_metadata.len());
+                *lock_shared(&extension_bytes_copy)
+                    .entry(show_ext.clone())
+                    .or_insert(0) += file_size;
+                // Note how deep the file is and whether it's one of the biggest, for the statistics export.
+                lock_shared(&inventory_stats_copy).record_file(
+                    entry.depth(),
+                    entry
+                        .path()
+                        .strip_prefix(&summarization_root)
+                        .unwrap_or(entry.path()),
+                    file_size,
+                );
+                // Tally how much room the file takes up on disk, which is less than its length if it has holes.
+                if let Some(file_metadata) = &file_metadata {
+                    let on_disk: u64 =
+                        allocated_size(entry.path(), file_metadata).unwrap_or(file_size);
+                    let mut locked_totals = lock_shared(&storage_totals_copy);
+                    locked_totals.allocated_bytes += on_disk;
+                    if is_sparse(file_metadata, on_disk) {
+                        locked_totals.sparse_files += 1;
+                    }
+                }
+                // Tally the file under the first folder beneath the chosen directory that it's in.
+                let folder_name: String = match entry.depth() {
+                    1 => String::from(TOP_LEVEL_GROUP),
+                    _ => entry
+                        .path()
+                        .strip_prefix(&summarization_root)
+                        .ok()
+                        .and_then(|relative_path| relative_path.components().next())
+                        .map(|top_folder| top_folder.as_os_str().to_string_lossy().to_string())
+                        .unwrap_or_else(|| String::from(TOP_LEVEL_GROUP)),
+                };
+                *lock_shared(&folder_counts_copy)
+                    .entry(folder_name)
+                    .or_default()
+                    .entry(show_ext)
+                    .or_insert(0) += 1;
                 // Update the summarization time stopwatch.
-                let mut locked_time_taken_copy = time_taken_copy.lock().unwrap();
-                *locked_time_taken_copy = locked_start_copy.elapsed();
+                let mut locked_time_taken_copy = lock_shared(&time_taken_copy);
+                *locked_time_taken_copy = summarization_start.elapsed();
+            }
+            lock_shared(&phase_timings_copy).walk = summarization_start.elapsed();
+            let verification_start: Instant = Instant::now();
+            // Share one buffer across every listed file so its size can be tuned to how fast this disk reads.
+            let mut read_buffer = ReadBuffer::new(&options_copy.read_buffer_limits);
+            let checksum_file_count: usize = found_checksum_files.len();
+            // Read every checksum file before checking anything, so the GUI knows how many listed files there are.
+            let mut checksum_listings: Vec<ChecksumListing> = Vec::new();
+            for checksum_file in found_checksum_files {
+                match read_checksum_listing(&checksum_file) {
+                    Ok(mut checksum_listing) => {
+                        // Don't check OS metadata that was listed on one platform and never made it to another.
+                        checksum_listing.entries.retain(|(_, listed_filename)| {
+                            !options_copy
+                                .ignore_presets
+                                .ignores_path(Path::new(listed_filename))
+                        });
+                        checksum_listings.push(checksum_listing);
+                    }
+                    Err(unreadable_finding) => {
+                        lock_shared(&checksum_findings_copy).push(*unreadable_finding)
+                    }
+                }
+            }
+            lock_shared(&verification_progress_copy).listed = checksum_listings
+                .iter()
+                .map(|checksum_listing| checksum_listing.entries.len())
+                .sum();
+            // Check listed files one at a time, showing each result (and how many are left) as soon as it's known.
+            for checksum_listing in &checksum_listings {
+                for (listed_hash, listed_filename) in &checksum_listing.entries {
+                    if stop_requested() {
+                        return Err(String::from(STOPPED_REASON));
+                    }
+                    let finding: ChecksumFinding = checksum_listing.verify_entry(
+                        listed_hash,
+                        listed_filename,
+                        &options_copy.size_only_rules,
+                        &mut read_buffer,
+                    );
+                    lock_shared(&checksum_findings_copy).push(finding);
+                    lock_shared(&verification_progress_copy).checked += 1;
+                    *lock_shared(&time_taken_copy) = summarization_start.elapsed();
+                }
+            }
+            // Give locked files another chance now that whatever had them open has had time to let go.
+            if options_copy.retry_locked_files {
+                let locked_findings: Vec<(usize, ChecksumFinding)> =
+                    lock_shared(&checksum_findings_copy)
+                        .iter()
+                        .cloned()
+                        .enumerate()
+                        .filter(|(_, finding)| finding.outcome == ChecksumOutcome::Locked)
+                        .collect();
+                lock_shared(&verification_progress_copy).listed += locked_findings.len();
+                // Hash without holding the lock so the GUI can keep showing findings in the meantime.
+                for (finding_index, locked_finding) in locked_findings {
+                    let (retried_outcome, retried_details) = verify_listed_file(
+                        &locked_finding.listed_file,
+                        &locked_finding.listed_hash,
+                        &options_copy.size_only_rules,
+                        &mut read_buffer,
+                    );
+                    if let Some(finding) =
+                        lock_shared(&checksum_findings_copy).get_mut(finding_index)
+                    {
+                        finding.outcome = retried_outcome;
+                        finding.details = retried_details;
+                    }
+                    lock_shared(&verification_progress_copy).checked += 1;
+                    *lock_shared(&time_taken_copy) = summarization_start.elapsed();
+                }
             }
+            lock_shared(&phase_timings_copy).checksum_verification = verification_start.elapsed();
+            // Don't log files that couldn't be hashed because FolSum was closing as discrepancies.
+            if stop_requested() {
+                return Err(String::from(STOPPED_REASON));
+            }
+            // Hand over the audit in one piece, for the GUI to show and exports to record.
+            if options_copy.verify_checksum_files {
+                *lock_shared(&audit_report_copy) = Some(AuditReport::new(
+                    &summarization_root,
+                    checksum_file_count,
+                    lock_shared(&checksum_findings_copy).clone(),
+                    &options_copy.severity_rules,
+                    verification_start.elapsed(),
+                ));
+            }
+            // Log discrepancies once retries are done so files that were only locked for a moment aren't reported.
+            if options_copy.write_system_log {
+                let discrepancies: Vec<AuditEvent> = lock_shared(&checksum_findings_copy)
+                    .iter()
+                    .filter_map(AuditEvent::checksum_discrepancy)
+                    .collect();
+                for discrepancy in &discrepancies {
+                    record_audit_event(discrepancy);
+                }
+                let file_count: u64 = lock_shared(&extension_counts_copy)
+                    .values()
+                    .map(|count| u64::from(*count))
+                    .sum();
+                record_audit_event(&AuditEvent::summarization_finished(
+                    &summarization_root,
+                    file_count,
+                ));
+            }
+            Ok(())
         });
     };
     Ok(())
 }
+
+/// Write an event to the system log, carrying on with the summarization if it can't be written.
+fn record_audit_event(audit_event: &AuditEvent) {
+    if let Err(log_error) = write_system_log(audit_event) {
+        log::warn!("{}: {}", log_error, audit_event.message());
+    }
+}
+
+/// Identify the data behind a file with more than one hardlink by its device and inode numbers.
+#[cfg(unix)]
+fn hardlinked_file_identity(entry: &DirEntry) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    let file_metadata = entry.metadata().ok()?;
+    // Files with a single link can't be double-counted, so there's no need to remember them.
+    (file_metadata.nlink() > 1).then_some((file_metadata.dev(), file_metadata.ino()))
+}
+
+/// Identify the data behind a file with more than one hardlink by its volume serial number and file index.
+#[cfg(windows)]
+fn hardlinked_file_identity(entry: &DirEntry) -> Option<(u64, u64)> {
+    // Windows only reveals file IDs and link counts through an open handle.
+    let opened_file = File::open(entry.path()).ok()?;
+    let file_information = winapi_util::file::information(&opened_file).ok()?;
+    // Files with a single link can't be double-counted, so there's no need to remember them.
+    (file_information.number_of_links() > 1).then_some((
+        file_information.volume_serial_number(),
+        file_information.file_index(),
+    ))
+}
+
+/// Assume that files have a single link on platforms that don't expose link counts.
+#[cfg(not(any(unix, windows)))]
+fn hardlinked_file_identity(_entry: &DirEntry) -> Option<(u64, u64)> {
+    None
+}
@@ -0,0 +1,165 @@
+use std::path::Path;
+
+use crate::{ChecksumFinding, ChecksumOutcome};
+
+/// Name that FolSum's events are filed under in syslog and the Windows Event Log.
+const EVENT_SOURCE: &str = "FolSum";
+
+/// Something that happened during a summarization that's worth recording in the system's central log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuditEvent {
+    SummarizationStarted {
+        directory: String,
+    },
+    SummarizationFinished {
+        directory: String,
+        file_count: u64,
+    },
+    // A file that's listed in a checksum file didn't match its checksum or couldn't be found.
+    ChecksumDiscrepancy {
+        listed_file: String,
+        outcome: String,
+    },
+}
+
+impl AuditEvent {
+    pub fn summarization_started(directory: &Path) -> Self {
+        AuditEvent::SummarizationStarted {
+            directory: directory.display().to_string(),
+        }
+    }
+
+    pub fn summarization_finished(directory: &Path, file_count: u64) -> Self {
+        AuditEvent::SummarizationFinished {
+            directory: directory.display().to_string(),
+            file_count,
+        }
+    }
+
+    /// Describe a checksum finding as a discrepancy, unless it held up (or wasn't hashed on purpose).
+    pub fn checksum_discrepancy(finding: &ChecksumFinding) -> Option<Self> {
+        let outcome: &str = match finding.outcome {
+            ChecksumOutcome::Mismatch => "mismatch",
+            ChecksumOutcome::Missing => "missing",
+            ChecksumOutcome::Unreadable => "unreadable",
+            ChecksumOutcome::Locked => "locked",
+            ChecksumOutcome::OutsideDirectory => "outside_directory",
+            ChecksumOutcome::Verified | ChecksumOutcome::SizeOnly { .. } => return None,
+        };
+        Some(AuditEvent::ChecksumDiscrepancy {
+            listed_file: finding.listed_file.display().to_string(),
+            outcome: String::from(outcome),
+        })
+    }
+
+    /// One-line description of the event, like the ones that show up in `journalctl` or Event Viewer.
+    pub fn message(&self) -> String {
+        match self {
+            AuditEvent::SummarizationStarted { directory } => {
+                format!("Started summarizing {}", directory)
+            }
+            AuditEvent::SummarizationFinished {
+                directory,
+                file_count,
+            } => format!("Finished summarizing {} ({} files)", directory, file_count),
+            AuditEvent::ChecksumDiscrepancy {
+                listed_file,
+                outcome,
+            } => format!("Checksum discrepancy ({}): {}", outcome, listed_file),
+        }
+    }
+
+    // Discrepancies are filed as warnings so they stand out from routine events.
+    fn is_warning(&self) -> bool {
+        matches!(self, AuditEvent::ChecksumDiscrepancy { .. })
+    }
+}
+
+/// Send an event to the local syslog daemon, which forwards it to central logging if it's set up to.
+#[cfg(unix)]
+pub fn write_system_log(audit_event: &AuditEvent) -> Result<(), &'static str> {
+    use std::os::unix::net::UnixDatagram;
+    use std::process;
+
+    // Linux listens on `/dev/log` and MacOS listens on `/var/run/syslog`.
+    let syslog_socket = UnixDatagram::unbound().map_err(|_| "Couldn't open a syslog socket")?;
+    ["/dev/log", "/var/run/syslog"]
+        .iter()
+        .find(|socket_path| syslog_socket.connect(socket_path).is_ok())
+        .ok_or("Couldn't find the syslog daemon")?;
+    // Priority is the facility (1 for user programs) times 8, plus the severity (4 for warnings, 6 for info).
+    let priority: u8 = match audit_event.is_warning() {
+        true => 8 + 4,
+        false => 8 + 6,
+    };
+    let syslog_line = format!(
+        "<{}>{}[{}]: {}",
+        priority,
+        EVENT_SOURCE,
+        process::id(),
+        audit_event.message()
+    );
+    syslog_socket
+        .send(syslog_line.as_bytes())
+        .map_err(|_| "Couldn't write to syslog")?;
+    Ok(())
+}
+
+/// Send an event to the Windows Event Log, where it's filed in the Application log.
+#[cfg(windows)]
+pub fn write_system_log(audit_event: &AuditEvent) -> Result<(), &'static str> {
+    use std::ffi::OsStr;
+    use std::iter;
+    use std::os::windows::ffi::OsStrExt;
+    use std::ptr;
+
+    use windows_sys::Win32::System::EventLog::{
+        DeregisterEventSource, RegisterEventSourceW, ReportEventW, EVENTLOG_INFORMATION_TYPE,
+        EVENTLOG_WARNING_TYPE,
+    };
+
+    let to_wide = |text: &str| -> Vec<u16> {
+        OsStr::new(text)
+            .encode_wide()
+            .chain(iter::once(0))
+            .collect()
+    };
+    let source_name: Vec<u16> = to_wide(EVENT_SOURCE);
+    let message: Vec<u16> = to_wide(&audit_event.message());
+    // Safety: `source_name` is null-terminated, and a null server name means this computer.
+    let event_source = unsafe { RegisterEventSourceW(ptr::null(), source_name.as_ptr()) };
+    if event_source == 0 {
+        return Err("Couldn't open the Windows Event Log");
+    }
+    let event_type = match audit_event.is_warning() {
+        true => EVENTLOG_WARNING_TYPE,
+        false => EVENTLOG_INFORMATION_TYPE,
+    };
+    let message_strings = [message.as_ptr()];
+    // Safety: `event_source` is open and `message_strings` holds one null-terminated string that outlives the call.
+    let reported: bool = unsafe {
+        ReportEventW(
+            event_source,
+            event_type,
+            0,
+            0,
+            ptr::null_mut(),
+            1,
+            0,
+            message_strings.as_ptr(),
+            ptr::null(),
+        )
+    } != 0;
+    // Safety: `event_source` is open and isn't used after it's closed.
+    unsafe { DeregisterEventSource(event_source) };
+    match reported {
+        true => Ok(()),
+        false => Err("Couldn't write to the Windows Event Log"),
+    }
+}
+
+/// Refuse to log events on platforms without a system log, like the web.
+#[cfg(not(any(unix, windows)))]
+pub fn write_system_log(_audit_event: &AuditEvent) -> Result<(), &'static str> {
+    Err("This platform doesn't have a system log")
+}
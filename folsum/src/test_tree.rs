@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{hash_file, FileHashes};
+
+// Number the trees that this process creates so tests running in parallel don't share a directory.
+static TREES_CREATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Shape of a generated test tree. The same options always produce the same files with the same contents.
+#[derive(Clone, Debug)]
+pub struct TestTreeOptions {
+    // Starting point for the random number generator that picks names, depths, sizes, and contents.
+    pub seed: u64,
+    pub file_count: usize,
+    // How many subdirectories deep files can be placed.
+    pub max_depth: usize,
+    // Upper bound (inclusive) for the size of each file.
+    pub max_file_size: usize,
+    // Extensions to pick from when naming files.
+    pub extensions: Vec<String>,
+}
+
+impl Default for TestTreeOptions {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            file_count: 50,
+            max_depth: 5,
+            max_file_size: 4 * 1024,
+            extensions: ["py", "pdf", "doc", "zip", "xml"]
+                .iter()
+                .map(|extension| extension.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Temporary directory of generated files along with an "answer key" of what's in it.
+///
+/// The directory is deleted when the tree is dropped, whether the test using it passes or fails.
+pub struct TestTree {
+    pub base_path: PathBuf,
+    // Number of files created for each extension.
+    pub extension_counts: HashMap<String, u32>,
+    // Hashes of each file, keyed by its path relative to `base_path`.
+    pub file_hashes: HashMap<PathBuf, FileHashes>,
+}
+
+impl TestTree {
+    /// Create a tree of files in the system's temp directory.
+    pub fn new(tree_options: &TestTreeOptions) -> io::Result<Self> {
+        let tree_number: usize = TREES_CREATED.fetch_add(1, Ordering::SeqCst);
+        let base_path: PathBuf = env::temp_dir().join(format!(
+            "folsum_test_tree_{}_{}_{}",
+            process::id(),
+            tree_number,
+            tree_options.seed
+        ));
+        // Start from scratch in case an earlier run with the same process ID left a directory behind.
+        let _delete_result = fs::remove_dir_all(&base_path);
+        fs::create_dir_all(&base_path)?;
+        // Create `Self` before filling the tree so `Drop` cleans up if a file can't be written.
+        let mut test_tree = Self {
+            base_path,
+            extension_counts: HashMap::new(),
+            file_hashes: HashMap::new(),
+        };
+        test_tree.fill(tree_options)?;
+        Ok(test_tree)
+    }
+
+    fn fill(&mut self, tree_options: &TestTreeOptions) -> io::Result<()> {
+        let mut random = SplitMix64::new(tree_options.seed);
+        for file_number in 0..tree_options.file_count {
+            // Nest the file in `subdir_1/subdir_2/...` down to a random depth.
+            let file_depth: usize = random.below(tree_options.max_depth + 1);
+            let relative_directory: PathBuf = (1..=file_depth)
+                .map(|subdir_depth| format!("subdir_{}", subdir_depth))
+                .collect();
+            fs::create_dir_all(self.base_path.join(&relative_directory))?;
+            // Name files by number so no two files collide.
+            let extension: &str = match tree_options.extensions.is_empty() {
+                true => "",
+                false => &tree_options.extensions[random.below(tree_options.extensions.len())],
+            };
+            let filename: String = match extension.is_empty() {
+                true => format!("file_{}", file_number),
+                false => format!("file_{}.{}", file_number, extension),
+            };
+            let relative_path: PathBuf = relative_directory.join(filename);
+            // Fill the file with random bytes so every file has its own hash.
+            let file_size: usize = random.below(tree_options.max_file_size + 1);
+            let file_contents: Vec<u8> = (0..file_size).map(|_| random.next_u64() as u8).collect();
+            let file_path: PathBuf = self.base_path.join(&relative_path);
+            fs::write(&file_path, file_contents)?;
+            // Record the file in the answer key.
+            if !extension.is_empty() {
+                *self
+                    .extension_counts
+                    .entry(extension.to_string())
+                    .or_insert(0) += 1;
+            }
+            let file_hashes: FileHashes = hash_file(&file_path)
+                .map_err(|hash_error| io::Error::new(io::ErrorKind::Other, hash_error))?;
+            self.file_hashes.insert(relative_path, file_hashes);
+        }
+        Ok(())
+    }
+
+    /// Full path of a file in the tree, given its path relative to the tree's base.
+    pub fn path_of<P: AsRef<Path>>(&self, relative_path: P) -> PathBuf {
+        self.base_path.join(relative_path)
+    }
+}
+
+impl Drop for TestTree {
+    fn drop(&mut self) {
+        let _delete_result = fs::remove_dir_all(&self.base_path);
+    }
+}
+
+// Small, seedable random number generator so fixtures don't depend on an RNG crate.
+// See https://prng.di.unimi.it/splitmix64.c for the algorithm.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut mixed: u64 = self.state;
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        mixed ^ (mixed >> 31)
+    }
+
+    // Pick a number from zero up to (but not including) `upper_bound`.
+    fn below(&mut self, upper_bound: usize) -> usize {
+        match upper_bound {
+            0 => 0,
+            _ => (self.next_u64() % upper_bound as u64) as usize,
+        }
+    }
+}
@@ -1,12 +1,416 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, TimeZone};
 // Add `iter()` to HashMap for sorting.
 use itertools::Itertools;
 
+use crate::Language;
+
+/// Export filename template that follows FolSum's original `<date>_folsum_export` naming.
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{date}_folsum_export";
+
+/// Format of `{date}` in export filenames, which is ISO 8601 (`2024-01-31`) so exports sort by date.
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+/// Format of `{date}` in FolSum's original `YY_MM_DD_folsum_export` filenames.
+pub const LEGACY_DATE_FORMAT: &str = "%y_%m_%d";
+
+/// Placeholders that can be used in export filename templates.
+const TEMPLATE_PLACEHOLDERS: [&str; 3] = ["{date}", "{dirname}", "{case_id}"];
+
+/// Characters that can't appear in filenames on Windows.
+#[cfg(target_os = "windows")]
+const ILLEGAL_FILENAME_CHARACTERS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+/// Characters that can't appear in filenames on macOS (Finder treats colons as slashes).
+#[cfg(target_os = "macos")]
+const ILLEGAL_FILENAME_CHARACTERS: &[char] = &['/', ':'];
+/// Characters that can't appear in filenames on Linux and other Unixes.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const ILLEGAL_FILENAME_CHARACTERS: &[char] = &['/'];
+
+/// Device names that Windows won't let files be named after, even with an extension.
+#[cfg(target_os = "windows")]
+const RESERVED_WINDOWS_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Open a file or folder with the operating system's default app, like a text editor or the file manager.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_path(path: &Path) -> Result<(), &'static str> {
+    #[cfg(target_os = "windows")]
+    let opener = "explorer";
+    #[cfg(target_os = "macos")]
+    let opener = "open";
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let opener = "xdg-open";
+    // Don't wait for the opened app to close.
+    std::process::Command::new(opener)
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|_| "Couldn't open the path with the default app")
+}
+
+/// Show a file in the operating system's file manager, selected inside of the folder that it's in.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn reveal_path(path: &Path) -> Result<(), &'static str> {
+    #[cfg(target_os = "windows")]
+    let reveal_command = std::process::Command::new("explorer")
+        .arg(format!("/select,{}", path.display()))
+        .spawn();
+    #[cfg(target_os = "macos")]
+    let reveal_command = std::process::Command::new("open")
+        .arg("-R")
+        .arg(path)
+        .spawn();
+    // Linux file managers don't agree on a way to select files, so open the folder that it's in instead.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let reveal_command = std::process::Command::new("xdg-open")
+        .arg(path.parent().ok_or("The file isn't inside of a folder")?)
+        .spawn();
+    reveal_command
+        .map(|_| ())
+        .map_err(|_| "Couldn't show the file in the file manager")
+}
+
+/// Name of the account that FolSum's running under, which says who did what on shared workstations.
+///
+/// The OS is asked first, since environment variables can be set to anything before FolSum's launched. They're only
+/// used if the OS can't say, like when the account's missing from the user database.
+pub fn os_user_name() -> String {
+    account_name()
+        .or_else(|| {
+            // Unix shells set `USER` (or `LOGNAME` if they're old), and Windows sets `USERNAME`.
+            ["USER", "LOGNAME", "USERNAME"]
+                .iter()
+                .find_map(|variable_name| std::env::var(variable_name).ok())
+        })
+        .filter(|user_name| !user_name.trim().is_empty())
+        .unwrap_or_else(|| String::from("unknown"))
+}
+
+/// Look up the name of the account that FolSum's running under in the user database.
+#[cfg(unix)]
+fn account_name() -> Option<String> {
+    use std::ffi::CStr;
+    use std::{mem, ptr};
+
+    // Safety: `getuid` always succeeds and only reads the process's user ID.
+    let user_id: libc::uid_t = unsafe { libc::getuid() };
+    // Safety: `passwd` is plain old data, so all zeroes is a valid (if empty) entry.
+    let mut user_entry: libc::passwd = unsafe { mem::zeroed() };
+    let mut found_entry: *mut libc::passwd = ptr::null_mut();
+    // Start with room for most entries, and grow it if the user database says it's too small.
+    let mut entry_buffer: Vec<libc::c_char> = vec![0; 1024];
+    loop {
+        // Safety: every pointer is valid for the call, and the buffer's passed along with its length.
+        let lookup_result: libc::c_int = unsafe {
+            libc::getpwuid_r(
+                user_id,
+                &mut user_entry,
+                entry_buffer.as_mut_ptr(),
+                entry_buffer.len(),
+                &mut found_entry,
+            )
+        };
+        match lookup_result {
+            libc::ERANGE if entry_buffer.len() < 1024 * 1024 => {
+                entry_buffer.resize(entry_buffer.len() * 2, 0)
+            }
+            0 if !found_entry.is_null() && !user_entry.pw_name.is_null() => break,
+            _ => return None,
+        }
+    }
+    // Safety: `pw_name` points to a null-terminated name in `entry_buffer`, which is still alive.
+    let user_name: &CStr = unsafe { CStr::from_ptr(user_entry.pw_name) };
+    Some(user_name.to_string_lossy().into_owned())
+}
+
+/// Ask Windows for the name of the account that FolSum's running under.
+#[cfg(windows)]
+fn account_name() -> Option<String> {
+    use windows_sys::Win32::System::WindowsProgramming::GetUserNameW;
+
+    // Account names are at most 256 characters (`UNLEN`), plus the null at the end.
+    let mut name_buffer: Vec<u16> = vec![0; 257];
+    let mut name_length: u32 = name_buffer.len() as u32;
+    // Safety: `name_length` is the buffer's length, so the name can't be written past its end.
+    let found_name: bool = unsafe { GetUserNameW(name_buffer.as_mut_ptr(), &mut name_length) } != 0;
+    // The length that comes back counts the null at the end.
+    let name_characters: usize = (name_length as usize).saturating_sub(1);
+    match found_name {
+        true => String::from_utf16(&name_buffer[..name_characters]).ok(),
+        false => None,
+    }
+}
+
+/// Leave the account name to environment variables on platforms without a user database, like the web.
+#[cfg(not(any(unix, windows)))]
+fn account_name() -> Option<String> {
+    None
+}
+
 pub fn sort_counts(extension_counts: &HashMap<String, u32>) -> Vec<(&String, &u32)> {
-    // Alphabetize file extensions before occurrence sorting so those with the same count appear alphabetically.
-    let mut sorted_extensions: Vec<(&String, &u32)> = extension_counts.iter().sorted().collect();
+    let mut sorted_extensions: Vec<(&String, &u32)> = extension_counts.iter().collect();
     // Sort file extensions from most to least occurrences, assuming the user wants to see the most numerous filetypes first.
-    sorted_extensions.sort_by(|a, b| b.1.cmp(a.1));
+    // Extensions with the same count are put in natural order, so `r2` comes before `r10`.
+    sorted_extensions.sort_by(|a, b| b.1.cmp(a.1).then_with(|| natural_cmp(a.0, b.0)));
     sorted_extensions
 }
+
+/// Compare names the way people expect, so `file2.pdf` comes before `file10.pdf` and `Notes` sits next to `notes`.
+///
+/// Runs of digits are compared by their value and everything else is compared without regard to case. Names that
+/// only differ by case or leading zeros fall back to a plain comparison, so every name still has one fixed place.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_characters = a.chars().peekable();
+    let mut b_characters = b.chars().peekable();
+    loop {
+        let natural_ordering: Ordering = match (a_characters.peek(), b_characters.peek()) {
+            (None, None) => break,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(a_character), Some(b_character))
+                if a_character.is_ascii_digit() && b_character.is_ascii_digit() =>
+            {
+                let a_number: String = a_characters
+                    .peeking_take_while(char::is_ascii_digit)
+                    .collect();
+                let b_number: String = b_characters
+                    .peeking_take_while(char::is_ascii_digit)
+                    .collect();
+                // Compare the digits as text so numbers that are too long for any integer type still work.
+                let a_number: &str = a_number.trim_start_matches('0');
+                let b_number: &str = b_number.trim_start_matches('0');
+                a_number
+                    .len()
+                    .cmp(&b_number.len())
+                    .then_with(|| a_number.cmp(b_number))
+            }
+            (Some(a_character), Some(b_character)) => {
+                let character_ordering: Ordering =
+                    a_character.to_lowercase().cmp(b_character.to_lowercase());
+                a_characters.next();
+                b_characters.next();
+                character_ordering
+            }
+        };
+        if natural_ordering != Ordering::Equal {
+            return natural_ordering;
+        }
+    }
+    a.cmp(b)
+}
+
+/// Render an amount of time like `1h 30m 12s`, or in milliseconds if it took less than a second.
+pub fn format_duration(duration: &Duration) -> String {
+    if duration.as_secs() == 0 {
+        return format!("{}ms", duration.as_millis());
+    }
+    let total_seconds: u64 = duration.as_secs();
+    let duration_parts: [(u64, &str); 3] = [
+        (total_seconds / 3600, "h"),
+        (total_seconds / 60 % 60, "m"),
+        (total_seconds % 60, "s"),
+    ];
+    // Leave out leading units that are zero so `12s` isn't shown as `0h 0m 12s`.
+    duration_parts
+        .iter()
+        .skip_while(|(amount, _)| *amount == 0)
+        .map(|(amount, unit)| format!("{amount}{unit}"))
+        .join(" ")
+}
+
+/// Render a count with the user's language's thousands separators, like `1,250,000`.
+pub fn format_count(count: u64, language: Language) -> String {
+    let digits: Vec<char> = count.to_string().chars().collect();
+    let mut formatted_count = String::new();
+    for (digit_index, digit) in digits.iter().enumerate() {
+        // Start a new group of three digits, counting from the right.
+        if digit_index > 0 && (digits.len() - digit_index) % 3 == 0 {
+            formatted_count.push(language.digit_group_separator());
+        }
+        formatted_count.push(*digit);
+    }
+    formatted_count
+}
+
+/// Render a number of bytes in the biggest binary unit that fits, like `1.5 MiB`.
+pub fn format_bytes(bytes: u64, language: Language) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut amount: f64 = bytes as f64 / 1024.0;
+    let mut unit_index: usize = 0;
+    while amount >= 1024.0 && unit_index + 1 < UNITS.len() {
+        amount /= 1024.0;
+        unit_index += 1;
+    }
+    // Use the user's decimal separator, like `1,5 MiB` in Spanish.
+    let shown_amount: String =
+        format!("{amount:.1}").replace('.', &language.decimal_separator().to_string());
+    format!("{} {}", shown_amount, UNITS[unit_index])
+}
+
+/// Shorten a hash to its first and last six characters, like `ba7816…0015ad`, so it fits in a narrow column.
+///
+/// That's still plenty for telling hashes apart at a glance. Short hashes are left whole.
+pub fn format_hash(hash: &str) -> String {
+    const SHOWN_CHARACTERS: usize = 6;
+    match hash.len() > SHOWN_CHARACTERS * 2 + 1 && hash.is_ascii() {
+        true => format!(
+            "{}…{}",
+            &hash[..SHOWN_CHARACTERS],
+            &hash[hash.len() - SHOWN_CHARACTERS..]
+        ),
+        false => hash.to_string(),
+    }
+}
+
+/// Check if a (possibly not yet existing) file path would land inside of the given directory.
+pub fn is_within_directory(candidate: &Path, directory: &Path) -> bool {
+    // Resolve symlinks and relative segments in the directory so they can't sneak a path past the comparison.
+    let resolved_directory = directory
+        .canonicalize()
+        .unwrap_or_else(|_| directory.to_path_buf());
+    // Treat bare filenames as living in the current directory.
+    let candidate_parent = match candidate.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    // The candidate file might not exist yet, so resolve its parent directory instead of the file itself.
+    let resolved_candidate = match (candidate_parent.canonicalize(), candidate.file_name()) {
+        (Ok(resolved_parent), Some(filename)) => resolved_parent.join(filename),
+        _ => candidate.to_path_buf(),
+    };
+    resolved_candidate.starts_with(resolved_directory)
+}
+
+/// Check that an export is allowed to be written to `export_path`, which read-only mode forbids inside of the
+/// protected directory (`read_only_root`, which is `None` when read-only mode is off).
+pub fn check_read_only(
+    export_path: &Path,
+    read_only_root: &Option<PathBuf>,
+) -> Result<(), &'static str> {
+    match read_only_root {
+        Some(protected_directory) if is_within_directory(export_path, protected_directory) => {
+            Err("Read-only mode forbids writing exports inside of the chosen directory")
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Find the first versioned sibling of a file (`name_2.csv`, `name_3.csv`, ...) that doesn't exist yet.
+pub fn next_versioned_path(existing_path: &Path) -> PathBuf {
+    // Split the filename so the version number goes between its name and its extension.
+    let file_stem: String = existing_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension: Option<String> = existing_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_string());
+    // Start counting at two because the existing file is the first version.
+    (2..)
+        .map(|version| {
+            let versioned_name: String = match &extension {
+                Some(extension) => format!("{file_stem}_{version}.{extension}"),
+                None => format!("{file_stem}_{version}"),
+            };
+            existing_path.with_file_name(versioned_name)
+        })
+        .find(|versioned_path| !versioned_path.exists())
+        .expect("Ran out of version numbers for the export file")
+}
+
+/// Render an export filename template into a path in the given directory.
+///
+/// Templates can use `{date}` (written with `date_format`, like `%Y-%m-%d`), `{dirname}` (name of the summarized
+/// directory), and `{case_id}`. Dates are written in whichever timezone `export_date` is in.
+pub fn create_export_path<Tz>(
+    export_directory: &Path,
+    filename_template: &str,
+    date_format: &str,
+    summarization_path: &Option<PathBuf>,
+    case_id: &str,
+    export_date: &DateTime<Tz>,
+) -> Result<PathBuf, &'static str>
+where
+    Tz: TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    // Catch typos like `{dirnmae}` before they end up in a filename.
+    let unknown_placeholders: String = TEMPLATE_PLACEHOLDERS
+        .iter()
+        .fold(filename_template.to_string(), |remaining, placeholder| {
+            remaining.replace(placeholder, "")
+        });
+    if unknown_placeholders.contains('{') || unknown_placeholders.contains('}') {
+        return Err("Export filename template contains an unknown placeholder");
+    }
+    // Don't quietly drop the case ID from the filename if the filing convention calls for one.
+    if filename_template.contains("{case_id}") && case_id.trim().is_empty() {
+        return Err("Export filename template needs a case ID, but none was given");
+    }
+    validate_date_format(date_format)?;
+    let formatted_date: String = export_date.format(date_format).to_string();
+    // Name exports after the summarized directory, or say so if there isn't one yet.
+    let directory_name: String = summarization_path
+        .as_ref()
+        .and_then(|summarized_directory| summarized_directory.file_name())
+        .map(|directory_name| directory_name.to_string_lossy().to_string())
+        .unwrap_or_else(|| String::from("no_directory"));
+    let export_filename: String = filename_template
+        .replace("{date}", &formatted_date)
+        .replace("{dirname}", &directory_name)
+        .replace("{case_id}", case_id.trim());
+    validate_filename(&export_filename)?;
+    Ok(export_directory.join(export_filename))
+}
+
+/// Check that a date format (like `%Y-%m-%d`) only uses specifiers that chrono knows how to write.
+pub fn validate_date_format(date_format: &str) -> Result<(), &'static str> {
+    if date_format.trim().is_empty() {
+        return Err("Date format can't be empty");
+    }
+    // Formatting with an unknown specifier fails partway through, so catch them before formatting.
+    if StrftimeItems::new(date_format).any(|format_item| matches!(format_item, Item::Error)) {
+        return Err("Date format contains an unknown specifier");
+    }
+    Ok(())
+}
+
+/// Check that a filename can be created on the platform that FolSum's running on.
+pub fn validate_filename(filename: &str) -> Result<(), &'static str> {
+    if filename.trim().is_empty() {
+        return Err("Export filename can't be empty");
+    }
+    if filename
+        .chars()
+        .any(|character| character.is_control() || ILLEGAL_FILENAME_CHARACTERS.contains(&character))
+    {
+        return Err("Export filename contains characters that aren't allowed in filenames");
+    }
+    // Windows has a few more rules on top of forbidden characters.
+    #[cfg(target_os = "windows")]
+    {
+        if filename.ends_with('.') || filename.ends_with(' ') {
+            return Err("Export filename can't end with a period or a space on Windows");
+        }
+        // Reserved device names are off limits even when they're followed by an extension.
+        let base_name: String = filename
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .to_uppercase();
+        if RESERVED_WINDOWS_NAMES.contains(&base_name.as_str()) {
+            return Err("Export filename is a device name that's reserved by Windows");
+        }
+    }
+    Ok(())
+}
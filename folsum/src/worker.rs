@@ -0,0 +1,170 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+use web_time::{Duration, Instant};
+
+/// Reason that work gives for failing when it's stopped, like because FolSum is closing.
+pub const STOPPED_REASON: &str = "Stopped before it was done";
+
+/// Flag that's set to ask one worker to stop.
+type StopFlag = Arc<AtomicBool>;
+
+// Whether FolSum is closing, so work should wrap up instead of writing into a GUI that's going away.
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+// Number of workers that haven't finished or failed yet.
+static RUNNING_WORKERS: Mutex<usize> = Mutex::new(0);
+// Wakes up whoever's waiting for workers to finish when one does.
+static WORKER_FINISHED: Condvar = Condvar::new();
+// Stop flag of each running worker, along with the status it reports to, so one worker can be stopped on its own.
+static WORKER_STOP_FLAGS: Mutex<Vec<(Arc<Mutex<WorkerStatus>>, StopFlag)>> = Mutex::new(Vec::new());
+
+thread_local! {
+    // Stop flag of the worker that's running on this thread, if one is.
+    static WORKER_STOP_FLAG: RefCell<Option<StopFlag>> = const { RefCell::new(None) };
+}
+
+/// Progress of work that runs in a separate thread, like a summarization or an export.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum WorkerStatus {
+    // Nothing has been started yet.
+    #[default]
+    Idle,
+    InProgress,
+    Finished,
+    // The work returned an error or panicked, for the given reason.
+    Failed(String),
+}
+
+/// Run work in a separate thread and record whether it finished or failed in `worker_status`.
+///
+/// Errors that the work returns and panics that it hits both end up as `WorkerStatus::Failed`, so the GUI
+/// never waits on work that's already dead.
+pub fn spawn_worker<W>(worker_status: &Arc<Mutex<WorkerStatus>>, work: W)
+where
+    W: FnOnce() -> Result<(), String> + Send + 'static,
+{
+    *lock_shared(worker_status) = WorkerStatus::InProgress;
+    let worker_status_copy = Arc::clone(worker_status);
+    *lock_shared(&RUNNING_WORKERS) += 1;
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    lock_shared(&WORKER_STOP_FLAGS).push((Arc::clone(worker_status), Arc::clone(&stop_flag)));
+    // Watch the worker from a second thread so its failures are noticed without blocking the caller.
+    thread::spawn(move || {
+        let worker_stop_flag = Arc::clone(&stop_flag);
+        let worker = thread::spawn(move || {
+            WORKER_STOP_FLAG.with(|thread_stop_flag| {
+                *thread_stop_flag.borrow_mut() = Some(worker_stop_flag);
+            });
+            work()
+        });
+        let final_status: WorkerStatus = match worker.join() {
+            Ok(Ok(())) => WorkerStatus::Finished,
+            Ok(Err(failure_reason)) => WorkerStatus::Failed(failure_reason),
+            Err(panic_payload) => WorkerStatus::Failed(panic_reason(&*panic_payload)),
+        };
+        *lock_shared(&worker_status_copy) = final_status;
+        lock_shared(&WORKER_STOP_FLAGS)
+            .retain(|(_, worker_flag)| !Arc::ptr_eq(worker_flag, &stop_flag));
+        *lock_shared(&RUNNING_WORKERS) -= 1;
+        WORKER_FINISHED.notify_all();
+    });
+}
+
+/// Ask every worker to stop, like when FolSum is closing.
+///
+/// Work checks [`stop_requested`] between files and gives up with [`STOPPED_REASON`], so it never writes a
+/// half-finished export. Once it's asked, it can't be taken back, because FolSum's on its way out.
+pub fn request_stop() {
+    STOP_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Ask the worker that reports to `worker_status` to stop, leaving every other worker running.
+///
+/// The worker gives up with [`STOPPED_REASON`] just like it would if FolSum were closing. Workers that have already
+/// finished aren't affected.
+pub fn stop_worker(worker_status: &Arc<Mutex<WorkerStatus>>) {
+    for (status, stop_flag) in lock_shared(&WORKER_STOP_FLAGS).iter() {
+        if Arc::ptr_eq(status, worker_status) {
+            stop_flag.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Check if workers have been asked to stop, or if the worker that's calling this has been asked on its own.
+pub fn stop_requested() -> bool {
+    STOP_REQUESTED.load(Ordering::SeqCst)
+        || WORKER_STOP_FLAG.with(|thread_stop_flag| {
+            thread_stop_flag
+                .borrow()
+                .as_ref()
+                .map_or(false, |stop_flag| stop_flag.load(Ordering::SeqCst))
+        })
+}
+
+/// Count the workers that haven't finished or failed yet.
+pub fn running_workers() -> usize {
+    *lock_shared(&RUNNING_WORKERS)
+}
+
+/// Wait for every worker to finish or fail, for up to `timeout`.
+///
+/// Returns `false` if some were still running when time ran out.
+pub fn wait_for_workers(timeout: Duration) -> bool {
+    let deadline: Instant = Instant::now() + timeout;
+    let mut running_workers = lock_shared(&RUNNING_WORKERS);
+    while *running_workers > 0 {
+        let time_left: Duration = deadline.saturating_duration_since(Instant::now());
+        if time_left.is_zero() {
+            return false;
+        }
+        running_workers = WORKER_FINISHED
+            .wait_timeout(running_workers, time_left)
+            .unwrap_or_else(PoisonError::into_inner)
+            .0;
+    }
+    true
+}
+
+/// Wait for the worker that reports to `worker_status` to finish or fail, for up to `timeout`.
+///
+/// Unlike [`wait_for_workers`], other workers that are still running don't hold this up. Returns `false` if the
+/// worker was still running when time ran out.
+pub fn wait_for_worker(worker_status: &Arc<Mutex<WorkerStatus>>, timeout: Duration) -> bool {
+    let deadline: Instant = Instant::now() + timeout;
+    // Check the status while holding the count, since workers record their status before they're counted out.
+    let mut running_workers = lock_shared(&RUNNING_WORKERS);
+    while *lock_shared(worker_status) == WorkerStatus::InProgress {
+        let time_left: Duration = deadline.saturating_duration_since(Instant::now());
+        if time_left.is_zero() {
+            return false;
+        }
+        running_workers = WORKER_FINISHED
+            .wait_timeout(running_workers, time_left)
+            .unwrap_or_else(PoisonError::into_inner)
+            .0;
+    }
+    true
+}
+
+/// Lock state that's shared with workers, even if a worker panicked while holding it.
+///
+/// A panicking worker already ends up as `WorkerStatus::Failed`, so whatever it left behind is shown as an
+/// unfinished run instead of taking the GUI down along with it.
+pub fn lock_shared<T: ?Sized>(shared: &Mutex<T>) -> MutexGuard<'_, T> {
+    shared.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Pull the message out of a panic, which is usually a `&str` or a `String`.
+fn panic_reason(panic_payload: &(dyn Any + Send)) -> String {
+    match (
+        panic_payload.downcast_ref::<&str>(),
+        panic_payload.downcast_ref::<String>(),
+    ) {
+        (Some(panic_message), _) => panic_message.to_string(),
+        (_, Some(panic_message)) => panic_message.clone(),
+        _ => String::from("Worker thread panicked"),
+    }
+}
@@ -1,10 +1,13 @@
 use std::collections::HashMap;
+use std::ffi::OsStr;
 use std::fs::{self, File};
-use std::io::{self, BufRead, BufReader};
-use std::path::PathBuf;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
+use proptest::prelude::*;
 use web_time::{Duration, Instant};
 
 use folsum;
@@ -13,7 +16,7 @@ use folsum;
 fn test_summarization_and_export() {
     // Test Summarization /////////////////////////////////////////////////////////////////////////
     // Create nested directories with empty test files.
-    let actual_extensions = TestFiles::new().unwrap();
+    let actual_extensions = TestFiles::new("test_dir").unwrap();
 
     // Mock global state variables that are mutated by `folsum::summarize_directory`.
     let extension_counts = Arc::new(Mutex::new(HashMap::new()));
@@ -24,9 +27,13 @@ fn test_summarization_and_export() {
     // Summarize the test directory so we can compare its output with the answer key.
     let _summarization_attempt = folsum::summarize_directory(
         &summarization_path,
-        &extension_counts,
-        &summarization_start,
-        &time_taken,
+        &folsum::SummarizationOptions::default(),
+        &folsum::SummarizationState {
+            extension_counts: Arc::clone(&extension_counts),
+            summarization_start: Arc::clone(&summarization_start),
+            time_taken: Arc::clone(&time_taken),
+            ..Default::default()
+        },
     );
     // Wait a bit so the summarization thread has a chance to do it's thing.
     thread::sleep(Duration::from_secs(1));
@@ -37,7 +44,13 @@ fn test_summarization_and_export() {
     // Mock the export filename as if the investigator named the file `export_test`.
     let mocked_export_file = Arc::new(Mutex::new(Some(export_filename.clone())));
     // Export summarization results of the mocked directory to CSV.
-    let _export_attempt = folsum::export_csv(&mocked_export_file, &extension_counts);
+    let _export_attempt = folsum::export_csv(
+        &mocked_export_file,
+        &extension_counts,
+        &None,
+        &folsum::ExportMetadata::default(),
+        &Arc::new(Mutex::new(folsum::WorkerStatus::default())),
+    );
     // Wait a sec for the export to run so the export file exists before we try reading from it.
     thread::sleep(Duration::from_secs(1));
     // Test: Ensure that an export file was produced.
@@ -68,6 +81,2349 @@ fn test_summarization_and_export() {
     }
 }
 
+#[test]
+fn test_read_only_mode_refuses_export_into_target() {
+    // Mock a directory under legal hold that the investigator chose to summarize.
+    let protected_directory = PathBuf::from("read_only_test_dir");
+    fs::create_dir_all(&protected_directory).unwrap();
+    // Mock an export file that the investigator (mistakenly) tried to save inside of the protected directory.
+    let export_filename = protected_directory.join("export_test.csv");
+    let mocked_export_file = Arc::new(Mutex::new(Some(export_filename.clone())));
+    let extension_counts = Arc::new(Mutex::new(HashMap::from([(String::from("pdf"), 1)])));
+    // Try to export while read-only mode protects the chosen directory.
+    let export_attempt = folsum::export_csv(
+        &mocked_export_file,
+        &extension_counts,
+        &Some(protected_directory.clone()),
+        &folsum::ExportMetadata::default(),
+        &Arc::new(Mutex::new(folsum::WorkerStatus::default())),
+    );
+    // Wait a sec so a (wrongly) spawned export thread would have had time to write.
+    thread::sleep(Duration::from_secs(1));
+    // Test: Ensure that the export was refused and that nothing was written into the protected directory.
+    assert!(export_attempt.is_err());
+    assert!(!export_filename.exists());
+    let _delete_result = fs::remove_dir_all(&protected_directory);
+}
+
+#[test]
+fn test_existing_exports_get_versioned_names() {
+    let export_directory = PathBuf::from("versioning_test_dir");
+    fs::create_dir_all(&export_directory).unwrap();
+    // Mock an export from earlier in the day.
+    let first_export = export_directory.join("export_test.csv");
+    File::create(&first_export).unwrap();
+    // Test: The second export of the day should get a `_2` suffix instead of overwriting the first.
+    let second_export = folsum::next_versioned_path(&first_export);
+    assert_eq!(second_export, export_directory.join("export_test_2.csv"));
+    // Test: Once the second export exists, the third one should skip to `_3`.
+    File::create(&second_export).unwrap();
+    let third_export = folsum::next_versioned_path(&first_export);
+    assert_eq!(third_export, export_directory.join("export_test_3.csv"));
+    let _delete_result = fs::remove_dir_all(&export_directory);
+}
+
+#[test]
+fn test_export_filename_templates() {
+    let export_directory = PathBuf::from("exports");
+    let summarization_path = Some(PathBuf::from("evidence").join("suspect_laptop"));
+    // Mock an export on New Year's Day, 2024.
+    let export_date: DateTime<Local> = Local.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    // Test: The default template and date format name exports with ISO 8601 dates, which sort by date.
+    let default_path = folsum::create_export_path(
+        &export_directory,
+        folsum::DEFAULT_FILENAME_TEMPLATE,
+        folsum::DEFAULT_DATE_FORMAT,
+        &summarization_path,
+        "",
+        &export_date,
+    );
+    assert_eq!(
+        default_path,
+        Ok(export_directory.join("2024-01-01_folsum_export"))
+    );
+    // Test: The legacy date format reproduces the original `YY_MM_DD_folsum_export` naming.
+    let legacy_path = folsum::create_export_path(
+        &export_directory,
+        folsum::DEFAULT_FILENAME_TEMPLATE,
+        folsum::LEGACY_DATE_FORMAT,
+        &summarization_path,
+        "",
+        &export_date,
+    );
+    assert_eq!(
+        legacy_path,
+        Ok(export_directory.join("24_01_01_folsum_export"))
+    );
+    // Test: Every placeholder gets filled in.
+    let case_path = folsum::create_export_path(
+        &export_directory,
+        "{date}_{dirname}_{case_id}",
+        folsum::LEGACY_DATE_FORMAT,
+        &summarization_path,
+        " 2024-0042 ",
+        &export_date,
+    );
+    assert_eq!(
+        case_path,
+        Ok(export_directory.join("24_01_01_suspect_laptop_2024-0042"))
+    );
+    // Test: Dates are written in the timezone they're given in.
+    let utc_date = Utc.with_ymd_and_hms(2024, 1, 1, 23, 30, 0).unwrap();
+    assert_eq!(
+        folsum::create_export_path(
+            &export_directory,
+            "{date}",
+            "%Y-%m-%dT%H%M%z",
+            &summarization_path,
+            "",
+            &utc_date.with_timezone(&FixedOffset::east_opt(2 * 3600).unwrap()),
+        ),
+        Ok(export_directory.join("2024-01-02T0130+0200"))
+    );
+    // Test: Date formats with unknown specifiers or path separators are refused.
+    assert!(folsum::validate_date_format("%Y-%Q").is_err());
+    assert!(folsum::create_export_path(
+        &export_directory,
+        "{date}",
+        "%Y/%m/%d",
+        &summarization_path,
+        "",
+        &export_date
+    )
+    .is_err());
+    // Test: Templates that need a case ID refuse to render without one.
+    assert!(folsum::create_export_path(
+        &export_directory,
+        "{date}_{case_id}",
+        folsum::DEFAULT_DATE_FORMAT,
+        &summarization_path,
+        "",
+        &export_date
+    )
+    .is_err());
+    // Test: Misspelled placeholders are caught instead of ending up in the filename.
+    assert!(folsum::create_export_path(
+        &export_directory,
+        "{dirnmae}",
+        folsum::DEFAULT_DATE_FORMAT,
+        &summarization_path,
+        "",
+        &export_date
+    )
+    .is_err());
+    // Test: Path separators can't be smuggled into filenames through the case ID.
+    assert!(folsum::validate_filename("case/42").is_err());
+    assert!(folsum::validate_filename("").is_err());
+}
+
+#[test]
+fn test_export_metadata_header() {
+    let export_directory = PathBuf::from("metadata_test_dir");
+    fs::create_dir_all(&export_directory).unwrap();
+    let export_filename = export_directory.join("export_test.csv");
+    let mocked_export_file = Arc::new(Mutex::new(Some(export_filename.clone())));
+    let extension_counts = Arc::new(Mutex::new(HashMap::from([(String::from("pdf"), 3)])));
+    // Mock an investigator recording who they are and why they ran the summarization.
+    let export_metadata = folsum::ExportMetadata {
+        exported_at: Some(
+            FixedOffset::west_opt(5 * 3600)
+                .unwrap()
+                .with_ymd_and_hms(2024, 1, 1, 12, 0, 0)
+                .unwrap(),
+        ),
+        operator: String::from("Jane Doe"),
+        os_user: String::from("jdoe"),
+        case_id: String::from("2024-0042"),
+        note: String::from("Intake of seized drive\nRequested by counsel"),
+        pinned_settings: vec![String::from("max_depth = 3")],
+        self_test: Some(String::from("passed (MD5, SHA-1, SHA-256)")),
+        audit: Some(String::from(
+            "passed (0 critical, 0 warning, 1 info) in 1 checksum files, checked in 0s",
+        )),
+    };
+    let _export_attempt = folsum::export_csv(
+        &mocked_export_file,
+        &extension_counts,
+        &None,
+        &export_metadata,
+        &Arc::new(Mutex::new(folsum::WorkerStatus::default())),
+    );
+    // Wait a sec for the export to run so the export file exists before we try reading from it.
+    thread::sleep(Duration::from_secs(1));
+    let exported_lines: Vec<String> = fs::read_to_string(&export_filename)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect();
+    // Test: Metadata comes first, with each line of the note prefixed separately, followed by the usual CSV.
+    assert_eq!(
+        exported_lines,
+        vec![
+            "# Exported: 2024-01-01T12:00:00-05:00",
+            "# Operator: Jane Doe",
+            "# OS User: jdoe",
+            "# Case ID: 2024-0042",
+            "# Note: Intake of seized drive",
+            "# Note: Requested by counsel",
+            "# Pinned by .folsum.toml: max_depth = 3",
+            "# Tool self-test: passed (MD5, SHA-1, SHA-256)",
+            "# Checksum audit: passed (0 critical, 0 warning, 1 info) in 1 checksum files, checked in 0s",
+            "File Extension, Occurrences",
+            "pdf,3",
+        ]
+    );
+    let _delete_result = fs::remove_dir_all(&export_directory);
+}
+
+#[test]
+fn test_project_file() {
+    let project_directory = PathBuf::from("project_file_test_dir");
+    fs::create_dir_all(&project_directory).unwrap();
+    let project_path = project_directory.join(folsum::PROJECT_FILE_NAME);
+
+    // Test: Directories without a project file don't pin anything.
+    assert_eq!(folsum::read_project_file(&project_directory), Ok(None));
+
+    fs::write(
+        &project_path,
+        "filename_template = \"{dirname}_{case_id}\"\nmax_depth = 3\n\n[ignore_presets]\nmacos = true\n",
+    )
+    .unwrap();
+    let project_file: folsum::ProjectFile = folsum::read_project_file(&project_directory)
+        .unwrap()
+        .unwrap();
+    // Test: Pinned options win over the user's, and the rest of the user's options are kept.
+    let user_options = folsum::SummarizationOptions {
+        max_depth: Some(10),
+        find_extra_streams: true,
+        ..Default::default()
+    };
+    let pinned_options: folsum::SummarizationOptions = project_file.pin(&user_options);
+    assert_eq!(pinned_options.max_depth, Some(3));
+    assert!(pinned_options.ignore_presets.macos);
+    assert!(!pinned_options.ignore_presets.windows);
+    assert!(pinned_options.find_extra_streams);
+    // Test: Pinned settings are described the way they're written, for export headers.
+    assert_eq!(
+        project_file.pinned_settings(),
+        vec![
+            "filename_template = \"{dirname}_{case_id}\"",
+            "max_depth = 3",
+            "ignore_presets = { macos = true, windows = false, linux = false }",
+        ]
+    );
+
+    // Test: Typos and malformed project files are errors instead of being ignored.
+    fs::write(&project_path, "max_dept = 3\n").unwrap();
+    assert!(folsum::read_project_file(&project_directory).is_err());
+    fs::write(&project_path, "max_depth = \n").unwrap();
+    assert!(folsum::read_project_file(&project_directory).is_err());
+    let _delete_result = fs::remove_dir_all(&project_directory);
+}
+
+#[test]
+fn test_hostile_extensions_survive_export() {
+    let export_directory = PathBuf::from("hostile_export_test_dir");
+    fs::create_dir_all(&export_directory).unwrap();
+    let export_filename = export_directory.join("export_test.csv");
+    let mocked_export_file = Arc::new(Mutex::new(Some(export_filename.clone())));
+    // Mock extensions that break naive CSV, with different counts so their order is predictable.
+    let hostile_extensions: Vec<(String, u32)> = vec![
+        (String::from("tar,gz"), 5),
+        (String::from("say \"cheese\""), 4),
+        (String::from("two\nlines"), 3),
+        (String::from("📷"), 2),
+        (String::from("pdf"), 1),
+    ];
+    let extension_counts = Arc::new(Mutex::new(
+        hostile_extensions
+            .iter()
+            .cloned()
+            .collect::<HashMap<String, u32>>(),
+    ));
+    let export_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    let _export_attempt = folsum::export_csv(
+        &mocked_export_file,
+        &extension_counts,
+        &None,
+        &folsum::ExportMetadata::default(),
+        &export_status,
+    );
+    // Wait a sec for the export to run so the export file exists before we try reading from it.
+    thread::sleep(Duration::from_secs(1));
+    // Test: Extensions with commas, quotes, or newlines are exported instead of failing the whole export.
+    assert!(matches!(
+        *export_status.lock().unwrap(),
+        folsum::WorkerStatus::Finished
+    ));
+    let exported_contents: String = fs::read_to_string(&export_filename).unwrap();
+    let _delete_result = fs::remove_dir_all(&export_directory);
+    // Test: Quoted fields read back as the original extensions, in the original order.
+    let exported_rows: Vec<Vec<String>> = parse_quoted_csv(&exported_contents);
+    let exported_counts: Vec<(String, u32)> = exported_rows[1..]
+        .iter()
+        .map(|row| (row[0].clone(), row[1].parse::<u32>().unwrap()))
+        .collect();
+    assert_eq!(exported_counts, hostile_extensions);
+    // Test: Export previews show the same rows that are written.
+    let previewed_rows: Vec<String> = folsum::extension_rows(&extension_counts.lock().unwrap());
+    assert_eq!(
+        format!(
+            "File Extension, Occurrences\n{}\n",
+            previewed_rows.join("\n")
+        ),
+        exported_contents
+    );
+    // Test: Plain fields are left alone so simple exports look the same as before.
+    assert_eq!(folsum::csv_field("pdf"), "pdf");
+    assert_eq!(
+        folsum::csv_field("say \"cheese\""),
+        "\"say \"\"cheese\"\"\""
+    );
+}
+
+#[test]
+fn test_spdx_export() {
+    let summarized_directory = PathBuf::from("spdx_test_dir");
+    fs::create_dir_all(summarized_directory.join("photos")).unwrap();
+    fs::write(summarized_directory.join("notes.txt"), "abc").unwrap();
+    fs::write(summarized_directory.join("photos/IMG_0001.jpg"), "").unwrap();
+    let export_filename = PathBuf::from("spdx_test_export.spdx");
+    let export_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    let _export_attempt = folsum::export_spdx(
+        &Arc::new(Mutex::new(Some(export_filename.clone()))),
+        &Arc::new(Mutex::new(Some(summarized_directory.clone()))),
+        &None,
+        &folsum::ExportMetadata {
+            operator: String::from("Jane Doe"),
+            case_id: String::from("2024-0042"),
+            ..Default::default()
+        },
+        &folsum::SummarizationOptions::default(),
+        &export_status,
+    );
+    // Wait a sec for the export to run so the export file exists before we try reading from it.
+    thread::sleep(Duration::from_secs(1));
+    let spdx_document: String = fs::read_to_string(&export_filename).unwrap();
+    let previewed_paths: Vec<String> = folsum::spdx_file_paths(
+        &summarized_directory,
+        &folsum::SummarizationOptions::default(),
+    )
+    .map(|(_, relative_path)| relative_path)
+    .collect();
+    let _delete_result = fs::remove_dir_all(&summarized_directory);
+    let _delete_result = fs::remove_file(&export_filename);
+    assert_eq!(
+        *export_status.lock().unwrap(),
+        folsum::WorkerStatus::Finished
+    );
+    // Test: The document says which version of SPDX it follows and who made it.
+    assert!(spdx_document.starts_with("SPDXVersion: SPDX-2.3\n"));
+    assert!(spdx_document.contains("Creator: Person: Jane Doe\n"));
+    assert!(spdx_document.contains("Case ID: 2024-0042"));
+    // Test: Files are listed by name with forward slashes, along with the hashes that SPDX tools expect.
+    let notes_entry: &str = spdx_document
+        .split("\n\n")
+        .find(|entry| entry.starts_with("FileName: ./notes.txt\n"))
+        .unwrap();
+    assert!(notes_entry.contains("FileChecksum: SHA1: a9993e364706816aba3e25717850c26c9cd0d89d\n"));
+    assert!(notes_entry.contains(
+        "FileChecksum: SHA256: ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\n"
+    ));
+    assert!(notes_entry.contains("FileChecksum: MD5: 900150983cd24fb0d6963f7d28e17f72\n"));
+    assert!(notes_entry.contains("Size: 3 bytes"));
+    assert!(spdx_document.contains("FileName: ./photos/IMG_0001.jpg\n"));
+    // Test: The package's verification code is the SHA-1 of its files' sorted SHA-1s, joined together.
+    assert!(spdx_document
+        .contains("PackageVerificationCode: 29d0dbdfff975c93e2d75e5024fe8b650ec847af\n"));
+    // Test: Export previews list the same files, in the same order, as the document.
+    assert_eq!(
+        previewed_paths,
+        vec!["./notes.txt", "./photos/IMG_0001.jpg"]
+    );
+}
+
+#[test]
+fn test_inventory_statistics() {
+    let summarized_directory = PathBuf::from("stats_test_dir");
+    fs::create_dir_all(summarized_directory.join("nested/deeper")).unwrap();
+    fs::write(summarized_directory.join("notes.txt"), "abc").unwrap();
+    fs::write(summarized_directory.join("nested/draft.txt"), "abcdefg").unwrap();
+    fs::write(summarized_directory.join("nested/deeper/photo.jpg"), "").unwrap();
+    let extension_counts = Arc::new(Mutex::new(HashMap::new()));
+    let extension_bytes = Arc::new(Mutex::new(HashMap::new()));
+    let inventory_stats = Arc::new(Mutex::new(folsum::InventoryStats::default()));
+    let _summarization_attempt = folsum::summarize_directory(
+        &Arc::new(Mutex::new(Some(summarized_directory.clone()))),
+        &folsum::SummarizationOptions::default(),
+        &folsum::SummarizationState {
+            extension_counts: Arc::clone(&extension_counts),
+            extension_bytes: Arc::clone(&extension_bytes),
+            inventory_stats: Arc::clone(&inventory_stats),
+            ..Default::default()
+        },
+    );
+    // Wait a bit so the summarization thread has a chance to do it's thing.
+    thread::sleep(Duration::from_secs(1));
+    let export_filename = PathBuf::from("stats_test_export.csv");
+    let _export_attempt = folsum::export_stats(
+        &Arc::new(Mutex::new(Some(export_filename.clone()))),
+        &extension_counts,
+        &extension_bytes,
+        &inventory_stats,
+        &None,
+        &folsum::ExportMetadata::default(),
+        &Arc::new(Mutex::new(folsum::WorkerStatus::default())),
+    );
+    // Wait a sec for the export to run so the export file exists before we try reading from it.
+    thread::sleep(Duration::from_secs(1));
+    let exported_contents: String = fs::read_to_string(&export_filename).unwrap();
+    let _delete_result = fs::remove_dir_all(&summarized_directory);
+    let _delete_result = fs::remove_file(&export_filename);
+    // Test: Extensions, then depths, then the largest files (biggest first) are exported from the same pass.
+    let expected_lines: Vec<String> = vec![
+        String::from("Section, Name, Files, Bytes"),
+        String::from("extension,txt,2,10"),
+        String::from("extension,jpg,1,0"),
+        String::from("depth,1,1,"),
+        String::from("depth,2,1,"),
+        String::from("depth,3,1,"),
+        format!(
+            "largest file,{},,7",
+            Path::new("nested/draft.txt").display()
+        ),
+        String::from("largest file,notes.txt,,3"),
+        format!(
+            "largest file,{},,0",
+            Path::new("nested/deeper/photo.jpg").display()
+        ),
+    ];
+    assert_eq!(
+        exported_contents.lines().collect::<Vec<&str>>(),
+        expected_lines
+    );
+    // Test: Only the biggest files are kept, so huge directories don't use up memory.
+    let mut many_files = folsum::InventoryStats::default();
+    for file_size in 0..25 {
+        many_files.record_file(1, Path::new(&format!("file{file_size}")), file_size);
+    }
+    assert_eq!(many_files.largest_files.len(), folsum::LARGEST_FILE_COUNT);
+    assert_eq!(many_files.largest_files[0], (PathBuf::from("file24"), 24));
+    assert_eq!(many_files.depth_counts[&1], 25);
+}
+
+#[test]
+fn test_directory_estimates() {
+    let estimated_directory = PathBuf::from("estimate_test_dir");
+    fs::create_dir_all(estimated_directory.join("nested")).unwrap();
+    fs::write(estimated_directory.join("notes.txt"), "abc").unwrap();
+    fs::write(estimated_directory.join("nested/photo.jpg"), "abcdefg").unwrap();
+    let directory_estimate = folsum::estimate_directory(
+        &estimated_directory,
+        &folsum::SummarizationOptions::default(),
+        folsum::ESTIMATE_TIME_BUDGET,
+    );
+    let nested_estimate = folsum::estimate_directory(
+        &estimated_directory,
+        &folsum::SummarizationOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        },
+        folsum::ESTIMATE_TIME_BUDGET,
+    );
+    let is_system_directory: bool = folsum::is_system_directory(&estimated_directory);
+    let _delete_result = fs::remove_dir_all(&estimated_directory);
+    // Test: Small directories are counted completely and don't need a warning.
+    assert_eq!(
+        directory_estimate,
+        folsum::DirectoryEstimate {
+            file_count: 2,
+            total_bytes: 10,
+            finished: true,
+        }
+    );
+    assert!(!directory_estimate.is_enormous());
+    // Test: Estimates cover the same files as the summarization would.
+    assert_eq!(nested_estimate.file_count, 1);
+    // Test: Directories that couldn't be counted in time, or that are huge, need a warning.
+    assert!(folsum::DirectoryEstimate::default().is_enormous());
+    assert!(folsum::DirectoryEstimate {
+        file_count: folsum::ENORMOUS_FILE_COUNT,
+        total_bytes: 0,
+        finished: true,
+    }
+    .is_enormous());
+    // Test: Roots of drives are system directories, but ordinary folders aren't.
+    let current_directory: PathBuf = std::env::current_dir().unwrap();
+    assert!(folsum::is_system_directory(
+        current_directory.ancestors().last().unwrap()
+    ));
+    assert!(!is_system_directory);
+}
+
+#[test]
+fn test_summarization_max_depth() {
+    // Create nested directories with empty test files, ten subdirectories deep.
+    let actual_extensions = TestFiles::new("max_depth_test_dir").unwrap();
+    let extension_counts = Arc::new(Mutex::new(HashMap::new()));
+    let summarization_path = Arc::new(Mutex::new(Some(actual_extensions.base_path.clone())));
+    let summarization_start = Arc::new(Mutex::new(Instant::now()));
+    let time_taken = Arc::new(Mutex::new(Duration::ZERO));
+    // Only summarize files that are at most three levels below the chosen directory.
+    let summarization_options = folsum::SummarizationOptions {
+        max_depth: Some(3),
+        ..Default::default()
+    };
+    let _summarization_attempt = folsum::summarize_directory(
+        &summarization_path,
+        &summarization_options,
+        &folsum::SummarizationState {
+            extension_counts: Arc::clone(&extension_counts),
+            summarization_start: Arc::clone(&summarization_start),
+            time_taken: Arc::clone(&time_taken),
+            ..Default::default()
+        },
+    );
+    // Wait a bit so the summarization thread has a chance to do it's thing.
+    thread::sleep(Duration::from_secs(1));
+    // Test: Only the single file in `subdir_1/subdir_2/` is shallow enough to be counted.
+    assert_eq!(
+        *extension_counts.lock().unwrap(),
+        HashMap::from([(String::from("pdf"), 1)])
+    );
+}
+
+#[test]
+fn test_hardlinks_are_counted_once() {
+    let summarized_directory = PathBuf::from("hardlink_test_dir");
+    fs::create_dir_all(&summarized_directory).unwrap();
+    // Mock a file that's linked into the directory twice, next to an unrelated file.
+    let original_file = summarized_directory.join("original.pdf");
+    fs::write(&original_file, "12345").unwrap();
+    fs::hard_link(&original_file, summarized_directory.join("linked.pdf")).unwrap();
+    fs::write(summarized_directory.join("unrelated.pdf"), "abc").unwrap();
+    let extension_counts = Arc::new(Mutex::new(HashMap::new()));
+    let extension_bytes = Arc::new(Mutex::new(HashMap::new()));
+    let summarization_path = Arc::new(Mutex::new(Some(summarized_directory.clone())));
+    let hardlink_count = Arc::new(Mutex::new(0));
+    let _summarization_attempt = folsum::summarize_directory(
+        &summarization_path,
+        &folsum::SummarizationOptions::default(),
+        &folsum::SummarizationState {
+            extension_counts: Arc::clone(&extension_counts),
+            hardlink_count: Arc::clone(&hardlink_count),
+            extension_bytes: Arc::clone(&extension_bytes),
+            ..Default::default()
+        },
+    );
+    // Wait a bit so the summarization thread has a chance to do it's thing.
+    thread::sleep(Duration::from_secs(1));
+    // Test: The linked file's data is only counted once and the extra link is reported separately.
+    assert_eq!(
+        *extension_counts.lock().unwrap(),
+        HashMap::from([(String::from("pdf"), 2)])
+    );
+    assert_eq!(*hardlink_count.lock().unwrap(), 1);
+    // Test: The linked file's size is only added to the extension's total once, too.
+    assert_eq!(
+        *extension_bytes.lock().unwrap(),
+        HashMap::from([(String::from("pdf"), 8)])
+    );
+    let _delete_result = fs::remove_dir_all(&summarized_directory);
+}
+
+#[test]
+fn test_checksum_files_are_verified() {
+    let summarized_directory = PathBuf::from("checksum_test_dir");
+    let vendor_folder = summarized_directory.join("vendor");
+    fs::create_dir_all(&vendor_folder).unwrap();
+    // Mock a vendor's checksum file that lists a good file, a tampered file, and a file that never arrived.
+    fs::write(vendor_folder.join("good.txt"), "abc").unwrap();
+    fs::write(vendor_folder.join("tampered.txt"), "abd").unwrap();
+    fs::write(
+        vendor_folder.join("checksums.md5"),
+        "900150983cd24fb0d6963f7d28e17f72  good.txt\n\
+         MD5 (tampered.txt) = 900150983cd24fb0d6963f7d28e17f72\n\
+         # Comments aren't checksums.\n\
+         900150983cd24fb0d6963f7d28e17f72 *missing.txt\n",
+    )
+    .unwrap();
+    let checksum_findings = Arc::new(Mutex::new(Vec::new()));
+    let verification_progress = Arc::new(Mutex::new(folsum::VerificationProgress::default()));
+    let audit_report = Arc::new(Mutex::new(None));
+    let _summarization_attempt = folsum::summarize_directory(
+        &Arc::new(Mutex::new(Some(summarized_directory.clone()))),
+        &folsum::SummarizationOptions {
+            verify_checksum_files: true,
+            ..Default::default()
+        },
+        &folsum::SummarizationState {
+            checksum_findings: Arc::clone(&checksum_findings),
+            verification_progress: Arc::clone(&verification_progress),
+            audit_report: Arc::clone(&audit_report),
+            ..Default::default()
+        },
+    );
+    // Wait a bit so the summarization thread has a chance to do it's thing.
+    thread::sleep(Duration::from_secs(1));
+    // Test: Each listed file gets its own outcome, in the order that they're listed.
+    let outcomes: Vec<(PathBuf, folsum::ChecksumOutcome)> = checksum_findings
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|finding| (finding.listed_file.clone(), finding.outcome))
+        .collect();
+    let tampered_details: folsum::ListedFileDetails =
+        checksum_findings.lock().unwrap()[1].details.clone();
+    let _delete_result = fs::remove_dir_all(&summarized_directory);
+    // Test: The run's audit is handed over as one report, with its findings judged by the default rules.
+    let report: folsum::AuditReport = audit_report.lock().unwrap().clone().unwrap();
+    assert_eq!(report.directory, summarized_directory);
+    assert_eq!(report.checksum_file_count, 1);
+    assert_eq!(report.findings.len(), 3);
+    assert_eq!(report.verdict.content, folsum::ContentVerdict::Failed);
+    assert_eq!(
+        report.severity_counts,
+        vec![
+            (folsum::Severity::Info, 1),
+            (folsum::Severity::Warning, 0),
+            (folsum::Severity::Critical, 2),
+        ]
+    );
+    assert!(report
+        .summary()
+        .starts_with("failed (2 critical, 0 warning, 1 info) in 1 checksum files, checked in "));
+    // Test: Progress counts every listed file once it's checked, leaving out comments.
+    assert_eq!(
+        *verification_progress.lock().unwrap(),
+        folsum::VerificationProgress {
+            checked: 3,
+            listed: 3
+        }
+    );
+    // Test: Mismatches say what the file hashes to now, so they can be told apart from typos in the listing.
+    assert_eq!(
+        tampered_details.actual_hash.as_deref(),
+        Some("4911e516e5aa21d327512e0c8b197616")
+    );
+    assert_eq!(tampered_details.file_size, Some(3));
+    assert!(tampered_details.modified.is_some());
+    assert_eq!(tampered_details.error, None);
+    assert_eq!(
+        outcomes,
+        vec![
+            (
+                vendor_folder.join("good.txt"),
+                folsum::ChecksumOutcome::Verified
+            ),
+            (
+                vendor_folder.join("tampered.txt"),
+                folsum::ChecksumOutcome::Mismatch
+            ),
+            (
+                vendor_folder.join("missing.txt"),
+                folsum::ChecksumOutcome::Missing
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_checksum_files_cant_reach_outside_their_folder() {
+    let test_directory = PathBuf::from("checksum_escape_test_dir");
+    let evidence_folder = test_directory.join("evidence");
+    fs::create_dir_all(&evidence_folder).unwrap();
+    // Mock a file next to the evidence that a planted checksum file tries to make FolSum hash.
+    let outside_file = test_directory.join("outside.txt");
+    fs::write(&outside_file, "abc").unwrap();
+    let absolute_outside: PathBuf = fs::canonicalize(&outside_file).unwrap();
+    fs::write(evidence_folder.join("inside.txt"), "abc").unwrap();
+    let checksum_file = evidence_folder.join("checksums.md5");
+    fs::write(
+        &checksum_file,
+        format!(
+            "900150983cd24fb0d6963f7d28e17f72  ../outside.txt\n\
+             900150983cd24fb0d6963f7d28e17f72  {}\n\
+             900150983cd24fb0d6963f7d28e17f72  ./inside.txt\n",
+            absolute_outside.display()
+        ),
+    )
+    .unwrap();
+    let checksum_findings: Vec<folsum::ChecksumFinding> = folsum::verify_checksum_file(
+        &checksum_file,
+        &folsum::SizeOnlyRules::default(),
+        &mut folsum::ReadBuffer::new(&folsum::ReadBufferLimits::default()),
+    );
+    let _delete_result = fs::remove_dir_all(&test_directory);
+    let outcomes: Vec<folsum::ChecksumOutcome> = checksum_findings
+        .iter()
+        .map(|finding| finding.outcome)
+        .collect();
+    // Test: Relative paths with `..` and absolute paths are reported instead of being read, while paths inside of
+    // the checksum file's folder are still verified.
+    assert_eq!(
+        outcomes,
+        vec![
+            folsum::ChecksumOutcome::OutsideDirectory,
+            folsum::ChecksumOutcome::OutsideDirectory,
+            folsum::ChecksumOutcome::Verified,
+        ]
+    );
+    // Test: Nothing about the outside files makes it into findings, since they were never opened.
+    for escaping_finding in &checksum_findings[..2] {
+        assert_eq!(escaping_finding.details.actual_hash, None);
+        assert_eq!(escaping_finding.details.file_size, None);
+        assert!(escaping_finding.details.error.is_some());
+    }
+    // Test: Listings that couldn't be checked keep the run from passing.
+    assert_eq!(
+        folsum::audit_verdict(&checksum_findings, &folsum::SeverityRules::default()).content,
+        folsum::ContentVerdict::Unconfirmed
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn test_checksum_files_cant_follow_symlinks_outside_their_folder() {
+    let test_directory = PathBuf::from("checksum_symlink_escape_test_dir");
+    let evidence_folder = test_directory.join("evidence");
+    fs::create_dir_all(evidence_folder.join("inside_folder")).unwrap();
+    // Mock a file next to the evidence, and a symlinked file and folder in the evidence that lead to it.
+    let outside_file = test_directory.join("outside.txt");
+    fs::write(&outside_file, "abc").unwrap();
+    let absolute_outside: PathBuf = fs::canonicalize(&outside_file).unwrap();
+    std::os::unix::fs::symlink(&absolute_outside, evidence_folder.join("linked.txt")).unwrap();
+    std::os::unix::fs::symlink(
+        absolute_outside.parent().unwrap(),
+        evidence_folder.join("linked_folder"),
+    )
+    .unwrap();
+    fs::write(evidence_folder.join("inside.txt"), "abc").unwrap();
+    // Mock a symlink that stays inside of the evidence.
+    std::os::unix::fs::symlink(
+        fs::canonicalize(evidence_folder.join("inside.txt")).unwrap(),
+        evidence_folder
+            .join("inside_folder")
+            .join("linked_inside.txt"),
+    )
+    .unwrap();
+    let checksum_file = evidence_folder.join("checksums.md5");
+    fs::write(
+        &checksum_file,
+        "900150983cd24fb0d6963f7d28e17f72  linked.txt\n\
+         900150983cd24fb0d6963f7d28e17f72  linked_folder/outside.txt\n\
+         900150983cd24fb0d6963f7d28e17f72  inside_folder/linked_inside.txt\n",
+    )
+    .unwrap();
+    let checksum_findings: Vec<folsum::ChecksumFinding> = folsum::verify_checksum_file(
+        &checksum_file,
+        &folsum::SizeOnlyRules::default(),
+        &mut folsum::ReadBuffer::new(&folsum::ReadBufferLimits::default()),
+    );
+    let _delete_result = fs::remove_dir_all(&test_directory);
+    let outcomes: Vec<folsum::ChecksumOutcome> = checksum_findings
+        .iter()
+        .map(|finding| finding.outcome)
+        .collect();
+    // Test: Symlinks that lead out of the checksum file's folder are reported instead of being read, while ones that
+    // stay inside of it are still verified.
+    assert_eq!(
+        outcomes,
+        vec![
+            folsum::ChecksumOutcome::OutsideDirectory,
+            folsum::ChecksumOutcome::OutsideDirectory,
+            folsum::ChecksumOutcome::Verified,
+        ]
+    );
+    // Test: Nothing about the linked outside file makes it into findings, since it was never opened.
+    for escaping_finding in &checksum_findings[..2] {
+        assert_eq!(escaping_finding.details.actual_hash, None);
+    }
+}
+
+#[test]
+fn test_resaved_checksum_files_are_decoded() {
+    let checksum_line = "900150983cd24fb0d6963f7d28e17f72  good.txt\n";
+    let expected_checksums = vec![(
+        String::from("900150983cd24fb0d6963f7d28e17f72"),
+        String::from("good.txt"),
+    )];
+    // Mock the same checksum file after Notepad saved it as UTF-8 with a BOM, UTF-16 LE, and UTF-16 BE.
+    let utf8_with_bom: Vec<u8> = [&[0xEF, 0xBB, 0xBF], checksum_line.as_bytes()].concat();
+    let utf16_le: Vec<u8> = [0xFF, 0xFE]
+        .into_iter()
+        .chain(checksum_line.encode_utf16().flat_map(u16::to_le_bytes))
+        .collect();
+    let utf16_be: Vec<u8> = [0xFE, 0xFF]
+        .into_iter()
+        .chain(checksum_line.encode_utf16().flat_map(u16::to_be_bytes))
+        .collect();
+    // Test: Byte order marks are dropped and UTF-16 is decoded, so the hash on the first line isn't lost.
+    for resaved_bytes in [
+        checksum_line.as_bytes().to_vec(),
+        utf8_with_bom,
+        utf16_le,
+        utf16_be,
+    ] {
+        let decoded_text: String = folsum::decode_text(&resaved_bytes).unwrap();
+        assert_eq!(
+            folsum::parse_checksum_file(&decoded_text),
+            expected_checksums
+        );
+    }
+    // Test: Encodings that can't be told apart, like Windows-1252, are refused with a clear error.
+    let windows_1252: Vec<u8> = [checksum_line.as_bytes(), b"caf\xe9.txt\n"].concat();
+    assert!(folsum::decode_text(&windows_1252)
+        .unwrap_err()
+        .contains("encoding isn't supported"));
+}
+
+#[test]
+fn test_os_metadata_is_ignored() {
+    let summarized_directory = PathBuf::from("ignore_presets_test_dir");
+    fs::create_dir_all(summarized_directory.join(".Spotlight-V100")).unwrap();
+    // Mock a drive that's been plugged into a Mac and a Windows PC, with one real file on it.
+    for metadata_file in [
+        ".DS_Store",
+        "._photo.jpg",
+        "Thumbs.db",
+        ".Spotlight-V100/store.db",
+    ] {
+        fs::write(summarized_directory.join(metadata_file), "").unwrap();
+    }
+    fs::write(summarized_directory.join("photo.jpg"), "abc").unwrap();
+    // Mock a checksum file that was made on a Mac, so it lists Finder's metadata too.
+    fs::write(
+        summarized_directory.join("checksums.md5"),
+        "900150983cd24fb0d6963f7d28e17f72  photo.jpg\n\
+         d41d8cd98f00b204e9800998ecf8427e  .DS_Store\n",
+    )
+    .unwrap();
+    let extension_counts = Arc::new(Mutex::new(HashMap::new()));
+    let checksum_findings = Arc::new(Mutex::new(Vec::new()));
+    let _summarization_attempt = folsum::summarize_directory(
+        &Arc::new(Mutex::new(Some(summarized_directory.clone()))),
+        &folsum::SummarizationOptions {
+            verify_checksum_files: true,
+            ignore_presets: folsum::IgnorePresets {
+                macos: true,
+                windows: true,
+                linux: false,
+            },
+            ..Default::default()
+        },
+        &folsum::SummarizationState {
+            extension_counts: Arc::clone(&extension_counts),
+            checksum_findings: Arc::clone(&checksum_findings),
+            ..Default::default()
+        },
+    );
+    // Wait a bit so the summarization thread has a chance to do it's thing.
+    thread::sleep(Duration::from_secs(1));
+    let counted_extensions: HashMap<String, u32> = extension_counts.lock().unwrap().clone();
+    let checked_files: Vec<PathBuf> = checksum_findings
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|finding| finding.listed_file.clone())
+        .collect();
+    let _delete_result = fs::remove_dir_all(&summarized_directory);
+    // Test: Only the real files are counted, and ignored folders are skipped along with what's inside of them.
+    assert_eq!(
+        counted_extensions,
+        HashMap::from([(String::from("jpg"), 1), (String::from("md5"), 1)])
+    );
+    // Test: Ignored files that a checksum file lists are left out of its findings.
+    assert_eq!(checked_files, vec![summarized_directory.join("photo.jpg")]);
+    // Test: Presets that weren't chosen don't ignore anything.
+    let linux_only = folsum::IgnorePresets {
+        linux: true,
+        ..Default::default()
+    };
+    assert!(!linux_only.ignores(OsStr::new("Thumbs.db")));
+    assert!(linux_only.ignores(OsStr::new(".Trash-1000")));
+}
+
+#[test]
+fn test_size_only_rules_skip_hashing() {
+    let summarized_directory = PathBuf::from("size_only_test_dir");
+    fs::create_dir_all(&summarized_directory).unwrap();
+    // Mock camera footage whose listed hash is wrong, which should go unnoticed because it's never hashed.
+    fs::write(summarized_directory.join("footage.MOV"), "abd").unwrap();
+    fs::write(summarized_directory.join("notes.txt"), "abc").unwrap();
+    fs::write(
+        summarized_directory.join("checksums.md5"),
+        "900150983cd24fb0d6963f7d28e17f72  footage.MOV\n\
+         900150983cd24fb0d6963f7d28e17f72  notes.txt\n",
+    )
+    .unwrap();
+    let checksum_findings = Arc::new(Mutex::new(Vec::new()));
+    let _summarization_attempt = folsum::summarize_directory(
+        &Arc::new(Mutex::new(Some(summarized_directory.clone()))),
+        &folsum::SummarizationOptions {
+            verify_checksum_files: true,
+            size_only_rules: folsum::SizeOnlyRules {
+                extensions: String::from("braw, .mov"),
+                larger_than_mib: None,
+            },
+            ..Default::default()
+        },
+        &folsum::SummarizationState {
+            checksum_findings: Arc::clone(&checksum_findings),
+            ..Default::default()
+        },
+    );
+    // Wait a bit so the summarization thread has a chance to do it's thing.
+    thread::sleep(Duration::from_secs(1));
+    let outcomes: Vec<(PathBuf, folsum::ChecksumOutcome)> = checksum_findings
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|finding| (finding.listed_file.clone(), finding.outcome))
+        .collect();
+    let _delete_result = fs::remove_dir_all(&summarized_directory);
+    // Test: Files matching a rule (case-insensitively) only get their size recorded, and other files are still hashed.
+    assert_eq!(
+        outcomes,
+        vec![
+            (
+                summarized_directory.join("footage.MOV"),
+                folsum::ChecksumOutcome::SizeOnly { file_size: 3 }
+            ),
+            (
+                summarized_directory.join("notes.txt"),
+                folsum::ChecksumOutcome::Verified
+            ),
+        ]
+    );
+    // Test: Size thresholds apply whatever the extension.
+    let size_rules = folsum::SizeOnlyRules {
+        extensions: String::new(),
+        larger_than_mib: Some(1),
+    };
+    assert!(!size_rules.applies_to(Path::new("small.txt"), 1024 * 1024));
+    assert!(size_rules.applies_to(Path::new("big.txt"), 1024 * 1024 + 1));
+}
+
+#[test]
+#[cfg(windows)]
+fn test_locked_files_are_told_apart() {
+    use std::os::windows::fs::OpenOptionsExt;
+    let locked_directory = PathBuf::from("locked_test_dir");
+    fs::create_dir_all(&locked_directory).unwrap();
+    let locked_file = locked_directory.join("mailbox.pst");
+    fs::write(&locked_file, "abc").unwrap();
+    // Hold the file open without sharing it, like Outlook does with its mailbox.
+    let file_holder = fs::OpenOptions::new()
+        .read(true)
+        .share_mode(0)
+        .open(&locked_file)
+        .unwrap();
+    // Test: Files that are in use get their own error instead of a generic "couldn't open" one.
+    assert_eq!(
+        folsum::hash_file(&locked_file),
+        Err(folsum::FILE_IN_USE_ERROR)
+    );
+    drop(file_holder);
+    // Test: The same file hashes fine once it's closed.
+    assert!(folsum::hash_file(&locked_file).is_ok());
+    let _delete_result = fs::remove_dir_all(&locked_directory);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_extra_streams_are_found() {
+    let summarized_directory = PathBuf::from("extra_streams_test_dir");
+    fs::create_dir_all(&summarized_directory).unwrap();
+    let tagged_file = summarized_directory.join("tagged.txt");
+    fs::write(&tagged_file, "contents").unwrap();
+    fs::write(summarized_directory.join("plain.txt"), "contents").unwrap();
+    // Mock data that's hidden in an extended attribute. Skip the test on filesystems that don't support them.
+    if xattr::set(&tagged_file, "user.folsum_note", b"abc").is_err() {
+        let _delete_result = fs::remove_dir_all(&summarized_directory);
+        return;
+    }
+    let extra_streams = Arc::new(Mutex::new(Vec::new()));
+    let _summarization_attempt = folsum::summarize_directory(
+        &Arc::new(Mutex::new(Some(summarized_directory.clone()))),
+        &folsum::SummarizationOptions {
+            find_extra_streams: true,
+            ..Default::default()
+        },
+        &folsum::SummarizationState {
+            extra_streams: Arc::clone(&extra_streams),
+            ..Default::default()
+        },
+    );
+    // Wait a bit so the summarization thread has a chance to do it's thing.
+    thread::sleep(Duration::from_secs(1));
+    let found_streams: Vec<folsum::ExtraStream> = extra_streams.lock().unwrap().clone();
+    let _delete_result = fs::remove_dir_all(&summarized_directory);
+    // Test: The attribute is found on the file that has it (and nowhere else), with its size and hashes.
+    assert_eq!(
+        found_streams,
+        vec![folsum::ExtraStream {
+            file: tagged_file,
+            stream_name: String::from("user.folsum_note"),
+            stream_size: 3,
+            hashes: Some(folsum::hash_bytes(b"abc")),
+        }]
+    );
+    assert_eq!(
+        found_streams[0].hashes.as_ref().unwrap().md5,
+        "900150983cd24fb0d6963f7d28e17f72"
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn test_sparse_files_are_reported() {
+    use std::os::unix::fs::MetadataExt;
+    let summarized_directory = PathBuf::from("sparse_test_dir");
+    fs::create_dir_all(&summarized_directory).unwrap();
+    // Mock a virtual machine disk that's 8 MiB long without any data written to it.
+    let disk_image = summarized_directory.join("disk.vmdk");
+    File::create(&disk_image)
+        .unwrap()
+        .set_len(8 * 1024 * 1024)
+        .unwrap();
+    // Skip the test on filesystems that fill holes in right away.
+    let disk_metadata = fs::metadata(&disk_image).unwrap();
+    if disk_metadata.blocks() * 512 >= disk_metadata.len() {
+        let _delete_result = fs::remove_dir_all(&summarized_directory);
+        return;
+    }
+    let extension_bytes = Arc::new(Mutex::new(HashMap::new()));
+    let storage_totals = Arc::new(Mutex::new(folsum::StorageTotals::default()));
+    let _summarization_attempt = folsum::summarize_directory(
+        &Arc::new(Mutex::new(Some(summarized_directory.clone()))),
+        &folsum::SummarizationOptions::default(),
+        &folsum::SummarizationState {
+            extension_bytes: Arc::clone(&extension_bytes),
+            storage_totals: Arc::clone(&storage_totals),
+            ..Default::default()
+        },
+    );
+    // Wait a bit so the summarization thread has a chance to do it's thing.
+    thread::sleep(Duration::from_secs(1));
+    let _delete_result = fs::remove_dir_all(&summarized_directory);
+    // Test: The file's whole length counts toward its extension's size, but not toward the room it takes up on disk.
+    assert_eq!(
+        *extension_bytes.lock().unwrap(),
+        HashMap::from([(String::from("vmdk"), 8 * 1024 * 1024)])
+    );
+    let storage_totals = storage_totals.lock().unwrap();
+    assert_eq!(storage_totals.sparse_files, 1);
+    assert_eq!(storage_totals.allocated_bytes, disk_metadata.blocks() * 512);
+}
+
+proptest! {
+    // Test: Hand-edited or corrupted checksum files never crash the parser, and only well-formed hashes get through.
+    #[test]
+    fn test_checksum_parser_survives_any_input(contents in "\\PC*(\n\\PC*){0,5}") {
+        for (listed_hash, _listed_filename) in folsum::parse_checksum_file(&contents) {
+            prop_assert!(matches!(listed_hash.len(), 32 | 64));
+            prop_assert!(listed_hash.chars().all(|character| matches!(character, '0'..='9' | 'a'..='f')));
+        }
+    }
+
+    // Test: Checksum lines written in GNU and BSD styles are read back as the same hashes and filenames.
+    #[test]
+    fn test_checksum_parser_round_trip(
+        listed_checksums in prop::collection::vec(
+            ("[0-9a-fA-F]{32}|[0-9a-fA-F]{64}", "[a-zA-Z0-9_.-]{1,12}( [a-zA-Z0-9_.-]{1,12})?", 0..3_u8),
+            0..10,
+        )
+    ) {
+        let contents: String = listed_checksums
+            .iter()
+            .map(|(listed_hash, listed_filename, line_style)| match line_style {
+                0 => format!("{}  {}\n", listed_hash, listed_filename),
+                1 => format!("{} *{}\n", listed_hash, listed_filename),
+                _ => format!("SHA256 ({}) = {}\n", listed_filename, listed_hash),
+            })
+            .collect();
+        let expected_checksums: Vec<(String, String)> = listed_checksums
+            .into_iter()
+            .map(|(listed_hash, listed_filename, _)| (listed_hash.to_lowercase(), listed_filename))
+            .collect();
+        prop_assert_eq!(folsum::parse_checksum_file(&contents), expected_checksums);
+    }
+}
+
+#[test]
+fn test_audit_verdict() {
+    let listed_at: DateTime<Local> = Local.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    let listed_finding = |listed_name: &str, outcome: folsum::ChecksumOutcome, hours_later: i64| {
+        folsum::ChecksumFinding {
+            checksum_file: PathBuf::from("evidence/checksums.md5"),
+            listed_file: PathBuf::from(listed_name),
+            listed_hash: String::from("900150983cd24fb0d6963f7d28e17f72"),
+            outcome,
+            details: folsum::ListedFileDetails {
+                modified: Some(listed_at + chrono::Duration::hours(hours_later)),
+                ..Default::default()
+            },
+            listed_at: Some(listed_at),
+        }
+    };
+    let mut checksum_findings = vec![
+        listed_finding(
+            "evidence/untouched.jpg",
+            folsum::ChecksumOutcome::Verified,
+            -1,
+        ),
+        listed_finding("evidence/touched.jpg", folsum::ChecksumOutcome::Verified, 1),
+    ];
+    // Test: Files that were modified after being listed are metadata drift, which doesn't fail the content check.
+    let drifted_verdict =
+        folsum::audit_verdict(&checksum_findings, &folsum::SeverityRules::default());
+    assert_eq!(drifted_verdict.content, folsum::ContentVerdict::Passed);
+    assert_eq!(
+        drifted_verdict.metadata_drift,
+        vec![PathBuf::from("evidence/touched.jpg")]
+    );
+
+    // Test: Files that couldn't be read leave the content unconfirmed, and they aren't metadata drift.
+    checksum_findings.push(listed_finding(
+        "evidence/locked.pst",
+        folsum::ChecksumOutcome::Locked,
+        1,
+    ));
+    let unconfirmed_verdict =
+        folsum::audit_verdict(&checksum_findings, &folsum::SeverityRules::default());
+    assert_eq!(
+        unconfirmed_verdict.content,
+        folsum::ContentVerdict::Unconfirmed
+    );
+    assert_eq!(unconfirmed_verdict.metadata_drift.len(), 1);
+
+    // Test: Mismatched files fail the content check, whatever else was found.
+    checksum_findings.push(listed_finding(
+        "evidence/altered.jpg",
+        folsum::ChecksumOutcome::Mismatch,
+        1,
+    ));
+    assert_eq!(
+        folsum::audit_verdict(&checksum_findings, &folsum::SeverityRules::default()).content,
+        folsum::ContentVerdict::Failed
+    );
+    // Test: Severity rules decide the verdict, so mismatches can be made warnings instead of failing the audit.
+    let lenient_rules = folsum::SeverityRules {
+        mismatch: folsum::Severity::Warning,
+        ..Default::default()
+    };
+    assert_eq!(
+        folsum::audit_verdict(&checksum_findings, &lenient_rules).content,
+        folsum::ContentVerdict::Unconfirmed
+    );
+    // Test: Findings for noise extensions are only informational, however they're written.
+    let noise_rules = folsum::SeverityRules {
+        info_extensions: String::from("jpg, .PST"),
+        ..Default::default()
+    };
+    assert_eq!(
+        noise_rules.severity(&checksum_findings[3]),
+        folsum::Severity::Info
+    );
+    assert_eq!(
+        folsum::audit_verdict(&checksum_findings, &noise_rules).content,
+        folsum::ContentVerdict::Passed
+    );
+}
+
+#[test]
+fn test_summarization_by_top_level_folder() {
+    let summarized_directory = PathBuf::from("folder_groups_test_dir");
+    // Mock a directory with one loose file and two top-level folders, one of them nested.
+    fs::create_dir_all(summarized_directory.join("photos").join("2023")).unwrap();
+    fs::create_dir_all(summarized_directory.join("documents")).unwrap();
+    File::create(summarized_directory.join("readme.txt")).unwrap();
+    File::create(summarized_directory.join("photos").join("cat.jpg")).unwrap();
+    File::create(
+        summarized_directory
+            .join("photos")
+            .join("2023")
+            .join("dog.jpg"),
+    )
+    .unwrap();
+    File::create(summarized_directory.join("documents").join("will.pdf")).unwrap();
+    let folder_counts = Arc::new(Mutex::new(HashMap::new()));
+    let _summarization_attempt = folsum::summarize_directory(
+        &Arc::new(Mutex::new(Some(summarized_directory.clone()))),
+        &folsum::SummarizationOptions::default(),
+        &folsum::SummarizationState {
+            folder_counts: Arc::clone(&folder_counts),
+            ..Default::default()
+        },
+    );
+    // Wait a bit so the summarization thread has a chance to do it's thing.
+    thread::sleep(Duration::from_secs(1));
+    // Test: Nested files roll up into their top-level folder and loose files get their own group.
+    assert_eq!(
+        *folder_counts.lock().unwrap(),
+        HashMap::from([
+            (
+                String::from(folsum::TOP_LEVEL_GROUP),
+                HashMap::from([(String::from("txt"), 1)])
+            ),
+            (
+                String::from("photos"),
+                HashMap::from([(String::from("jpg"), 2)])
+            ),
+            (
+                String::from("documents"),
+                HashMap::from([(String::from("pdf"), 1)])
+            ),
+        ])
+    );
+    let _delete_result = fs::remove_dir_all(&summarized_directory);
+}
+
+#[test]
+fn test_worker_failures_are_reported() {
+    // Mock an export into a directory that doesn't exist, which can't be created.
+    let export_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    let _export_attempt = folsum::export_csv(
+        &Arc::new(Mutex::new(Some(PathBuf::from(
+            "no_such_directory/export.csv",
+        )))),
+        &Arc::new(Mutex::new(HashMap::from([(String::from("pdf"), 1)]))),
+        &None,
+        &folsum::ExportMetadata::default(),
+        &export_status,
+    );
+    // Mock work that panics partway through.
+    let panicked_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    folsum::spawn_worker(&panicked_status, || panic!("Ran out of luck"));
+    // Wait a sec for both workers to fail.
+    thread::sleep(Duration::from_secs(1));
+
+    // Test: Failed exports say why instead of looking like they're still running.
+    assert!(matches!(
+        &*export_status.lock().unwrap(),
+        folsum::WorkerStatus::Failed(failure_reason) if failure_reason.starts_with("Failed to create CSV export file")
+    ));
+    // Test: Panics are caught and reported with their message.
+    assert_eq!(
+        *panicked_status.lock().unwrap(),
+        folsum::WorkerStatus::Failed(String::from("Ran out of luck"))
+    );
+}
+
+#[test]
+fn test_panicked_workers_dont_leave_locks_unusable() {
+    let summarized_directory = PathBuf::from("poisoned_lock_test");
+    fs::create_dir_all(&summarized_directory).unwrap();
+    fs::write(summarized_directory.join("report.pdf"), "abc").unwrap();
+    // Mock a worker that panics while it's in the middle of counting extensions.
+    let extension_counts = Arc::new(Mutex::new(HashMap::new()));
+    let extension_counts_copy = Arc::clone(&extension_counts);
+    let panicked_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    folsum::spawn_worker(&panicked_status, move || {
+        let mut locked_counts = extension_counts_copy.lock().unwrap();
+        locked_counts.insert(String::from("half-counted"), 1);
+        panic!("Ran out of luck while counting");
+    });
+    // Wait a sec for the worker to fail.
+    thread::sleep(Duration::from_secs(1));
+    let poisoned: bool = extension_counts.lock().is_err();
+    // Summarize again into the same results, like the GUI does when the user tries again.
+    let summarization_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    let _summarization_attempt = folsum::summarize_directory(
+        &Arc::new(Mutex::new(Some(summarized_directory.clone()))),
+        &folsum::SummarizationOptions::default(),
+        &folsum::SummarizationState {
+            extension_counts: Arc::clone(&extension_counts),
+            summarization_status: Arc::clone(&summarization_status),
+            ..Default::default()
+        },
+    );
+    // Wait a sec for the summarization to finish.
+    thread::sleep(Duration::from_secs(1));
+    let _delete_result = fs::remove_dir_all(&summarized_directory);
+
+    // Test: The panic is reported, even though it left the results locked.
+    assert!(poisoned);
+    assert_eq!(
+        *folsum::lock_shared(&panicked_status),
+        folsum::WorkerStatus::Failed(String::from("Ran out of luck while counting"))
+    );
+    // Test: The next summarization can still use the results, and replaces what the panicked one left behind.
+    assert_eq!(
+        *folsum::lock_shared(&summarization_status),
+        folsum::WorkerStatus::Finished
+    );
+    assert_eq!(
+        *folsum::lock_shared(&extension_counts),
+        HashMap::from([(String::from("pdf"), 1)])
+    );
+}
+
+#[test]
+fn test_quick_hash() {
+    let hashed_file = PathBuf::from("quick_hash_test.txt");
+    fs::write(&hashed_file, "abc").unwrap();
+    let hash_attempt = folsum::hash_file(&hashed_file);
+    let _delete_result = fs::remove_file(&hashed_file);
+
+    // Compare with the well-known hashes of "abc".
+    let file_hashes = hash_attempt.unwrap();
+    assert_eq!(file_hashes.md5, "900150983cd24fb0d6963f7d28e17f72");
+    assert_eq!(
+        file_hashes.sha256,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+
+    // Pasted hashes are compared regardless of case and surrounding whitespace.
+    assert_eq!(
+        file_hashes.matches(" 900150983CD24FB0D6963F7D28E17F72\n"),
+        Some(true)
+    );
+    assert_eq!(file_hashes.matches(&"0".repeat(64)), Some(false));
+    assert_eq!(file_hashes.matches("not a hash"), None);
+
+    // Folders can't be quick hashed.
+    assert!(folsum::hash_file(&PathBuf::from(".")).is_err());
+}
+
+#[test]
+fn test_quick_hash_in_background() {
+    // Mock a big file that takes a while to hash, and a small one that's picked right after it.
+    let (big_file, small_file) = (
+        PathBuf::from("quick_hash_big_test.bin"),
+        PathBuf::from("quick_hash_small_test.txt"),
+    );
+    fs::write(&big_file, vec![0_u8; 32 * 1024 * 1024]).unwrap();
+    fs::write(&small_file, "abc").unwrap();
+    let quick_hash = Arc::new(Mutex::new(None));
+    let big_status = folsum::hash_file_in_background(
+        big_file.clone(),
+        &folsum::ReadBufferLimits::default(),
+        &quick_hash,
+    );
+    let small_status = folsum::hash_file_in_background(
+        small_file.clone(),
+        &folsum::ReadBufferLimits::default(),
+        &quick_hash,
+    );
+    // Wait for both files to be hashed.
+    assert!(folsum::wait_for_worker(
+        &big_status,
+        Duration::from_secs(30)
+    ));
+    assert!(folsum::wait_for_worker(
+        &small_status,
+        Duration::from_secs(30)
+    ));
+    let _delete_results = (fs::remove_file(&big_file), fs::remove_file(&small_file));
+    let latest_hash: folsum::QuickHash = folsum::lock_shared(&quick_hash).clone().unwrap();
+
+    // Test: Hashing runs as a worker, so it's waited for and its status is known.
+    assert_eq!(
+        *folsum::lock_shared(&big_status),
+        folsum::WorkerStatus::Finished
+    );
+    assert_eq!(
+        *folsum::lock_shared(&small_status),
+        folsum::WorkerStatus::Finished
+    );
+    // Test: The big file's hashes, which finish last, don't show up under the file that was picked after it.
+    assert_eq!(latest_hash.file_path, small_file);
+    assert_eq!(latest_hash.generation, 1);
+    assert_eq!(
+        latest_hash.hash_result.unwrap().unwrap().md5,
+        "900150983cd24fb0d6963f7d28e17f72"
+    );
+}
+
+#[test]
+fn test_read_buffer_tuning() {
+    let mut read_buffer = folsum::ReadBuffer::new(&folsum::ReadBufferLimits {
+        min_kib: 64,
+        max_kib: 256,
+        ..Default::default()
+    });
+    // Test: Tiny files only get enough buffer to read them in one go, and big files get the tuned size.
+    assert_eq!(read_buffer.for_file(10).len(), 11);
+    assert_eq!(read_buffer.for_file(10_000_000).len(), 64 * 1024);
+
+    // Test: Files that are too small to fill the buffer a few times over don't change its size.
+    read_buffer.record_read(100, Duration::from_millis(1));
+    assert_eq!(read_buffer.tuned_size(), 64 * 1024);
+    // Test: Big reads grow the buffer while they keep getting faster, up to the maximum.
+    read_buffer.record_read(10_000_000, Duration::from_secs(1));
+    assert_eq!(read_buffer.tuned_size(), 128 * 1024);
+    read_buffer.record_read(10_000_000, Duration::from_millis(500));
+    assert_eq!(read_buffer.tuned_size(), 256 * 1024);
+    read_buffer.record_read(10_000_000, Duration::from_millis(250));
+    assert_eq!(read_buffer.tuned_size(), 256 * 1024);
+    // Test: The buffer shrinks back when reads get slower.
+    read_buffer.record_read(10_000_000, Duration::from_secs(2));
+    assert_eq!(read_buffer.tuned_size(), 128 * 1024);
+
+    // Test: Limits that were entered backwards are swapped.
+    let backwards_buffer = folsum::ReadBuffer::new(&folsum::ReadBufferLimits {
+        min_kib: 256,
+        max_kib: 64,
+        ..Default::default()
+    });
+    assert_eq!(backwards_buffer.tuned_size(), 64 * 1024);
+
+    // Test: A reused buffer hashes files the same way as a fresh one.
+    let hashed_file = PathBuf::from("read_buffer_test.txt");
+    fs::write(&hashed_file, "abc".repeat(100_000)).unwrap();
+    let reused_hashes = folsum::hash_file_with_buffer(&hashed_file, &mut read_buffer);
+    let fresh_hashes = folsum::hash_file(&hashed_file);
+    let _delete_result = fs::remove_file(&hashed_file);
+    assert_eq!(reused_hashes.unwrap(), fresh_hashes.unwrap());
+}
+
+#[test]
+fn test_device_read_permits() {
+    // Use a made-up device so reads by other tests don't get in the way.
+    let device: u64 = u64::MAX - 3199;
+    let first_permit = folsum::DeviceReadPermit::acquire(Some(device), 1).unwrap();
+    assert_eq!(folsum::active_readers(device), 1);
+
+    // Test: A second reader on the same device waits until the first one's done.
+    let second_reader = thread::spawn(move || {
+        let wait_start = Instant::now();
+        let _second_permit = folsum::DeviceReadPermit::acquire(Some(device), 1).unwrap();
+        wait_start.elapsed()
+    });
+    // Test: Readers on other devices, and on unknown devices, don't wait.
+    let other_permit = folsum::DeviceReadPermit::acquire(Some(device - 1), 1).unwrap();
+    let unknown_permit = folsum::DeviceReadPermit::acquire(None, 1).unwrap();
+    drop((other_permit, unknown_permit));
+    thread::sleep(Duration::from_millis(200));
+    drop(first_permit);
+    assert!(second_reader.join().unwrap() >= Duration::from_millis(150));
+    assert_eq!(folsum::active_readers(device), 0);
+
+    // Test: Raising the limit lets more readers share a device.
+    let shared_permits = (
+        folsum::DeviceReadPermit::acquire(Some(device), 2).unwrap(),
+        folsum::DeviceReadPermit::acquire(Some(device), 2).unwrap(),
+    );
+    assert_eq!(folsum::active_readers(device), 2);
+    drop(shared_permits);
+
+    // Test: Readers that are stopped while they wait for a busy device give up instead of waiting for their turn.
+    let busy_permit = folsum::DeviceReadPermit::acquire(Some(device), 1).unwrap();
+    let waiting_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    folsum::spawn_worker(&waiting_status, move || {
+        let _waiting_permit = folsum::DeviceReadPermit::acquire(Some(device), 1)?;
+        Ok(())
+    });
+    thread::sleep(Duration::from_millis(200));
+    folsum::stop_worker(&waiting_status);
+    assert!(folsum::wait_for_worker(
+        &waiting_status,
+        Duration::from_secs(5)
+    ));
+    drop(busy_permit);
+    assert_eq!(
+        *folsum::lock_shared(&waiting_status),
+        folsum::WorkerStatus::Failed(String::from(folsum::STOPPED_REASON))
+    );
+    assert_eq!(folsum::active_readers(device), 0);
+
+    // Test: Files on disk are tied to a device where the platform says which one they're on.
+    #[cfg(any(unix, windows))]
+    {
+        let hashed_file = PathBuf::from("device_read_permits_test.txt");
+        fs::write(&hashed_file, "abc").unwrap();
+        let hashed_device: Option<u64> = folsum::device_id(&File::open(&hashed_file).unwrap());
+        assert!(hashed_device.is_some());
+        // Mock another reader that has the device's only turn.
+        let busy_permit = folsum::DeviceReadPermit::acquire(hashed_device, 1).unwrap();
+        let hashed_file_copy: PathBuf = hashed_file.clone();
+        let hasher = thread::spawn(move || {
+            folsum::hash_file_with_buffer(
+                &hashed_file_copy,
+                &mut folsum::ReadBuffer::new(&folsum::ReadBufferLimits::default()),
+            )
+        });
+        thread::sleep(Duration::from_millis(200));
+        // Test: Hashing waits for its turn on the device instead of reading alongside the other reader.
+        let waited_for_turn: bool = !hasher.is_finished();
+        drop(busy_permit);
+        let hash_attempt = hasher.join().unwrap();
+        let _delete_result = fs::remove_file(&hashed_file);
+        assert!(waited_for_turn);
+        assert_eq!(
+            hash_attempt.unwrap().md5,
+            "900150983cd24fb0d6963f7d28e17f72"
+        );
+    }
+}
+
+#[test]
+fn test_options_profiles() {
+    let mut profiles: Vec<folsum::OptionsProfile> = folsum::starter_profiles();
+    // Test: Profiles are found by name regardless of case, like from `--profile court-grade`.
+    let court_grade = folsum::find_profile(&profiles, "court-grade").unwrap();
+    assert!(court_grade.options.find_extra_streams);
+    assert!(folsum::find_profile(&profiles, "nonexistent").is_none());
+
+    // Test: Saving under a new name adds a profile.
+    let mut custom_options = folsum::SummarizationOptions {
+        max_depth: Some(2),
+        ..Default::default()
+    };
+    folsum::save_profile(&mut profiles, " Shallow ", &custom_options).unwrap();
+    assert_eq!(profiles.len(), folsum::starter_profiles().len() + 1);
+    assert_eq!(
+        folsum::find_profile(&profiles, "shallow").unwrap().options,
+        custom_options
+    );
+    // Test: Saving under an existing name replaces that profile's options.
+    custom_options.max_depth = Some(3);
+    folsum::save_profile(&mut profiles, "SHALLOW", &custom_options).unwrap();
+    assert_eq!(profiles.len(), folsum::starter_profiles().len() + 1);
+    assert_eq!(
+        folsum::find_profile(&profiles, "Shallow")
+            .unwrap()
+            .options
+            .max_depth,
+        Some(3)
+    );
+    // Test: Profiles need names.
+    assert!(folsum::save_profile(&mut profiles, "  ", &custom_options).is_err());
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_seeded_test_trees() {
+    let tree_options = folsum::TestTreeOptions {
+        seed: 3151,
+        ..Default::default()
+    };
+    let first_tree = folsum::TestTree::new(&tree_options).unwrap();
+    let second_tree = folsum::TestTree::new(&tree_options).unwrap();
+    // Test: The same seed produces the same files in separate directories.
+    assert_ne!(first_tree.base_path, second_tree.base_path);
+    assert_eq!(first_tree.extension_counts, second_tree.extension_counts);
+    assert_eq!(first_tree.file_hashes, second_tree.file_hashes);
+    assert_eq!(first_tree.file_hashes.len(), tree_options.file_count);
+
+    // Test: A different seed produces different files.
+    let other_tree = folsum::TestTree::new(&folsum::TestTreeOptions {
+        seed: 3152,
+        ..Default::default()
+    })
+    .unwrap();
+    assert_ne!(first_tree.file_hashes, other_tree.file_hashes);
+
+    // Test: The answer key's hashes match what's on disk.
+    for (relative_path, file_hashes) in &first_tree.file_hashes {
+        assert_eq!(
+            &folsum::hash_file(&first_tree.path_of(relative_path)).unwrap(),
+            file_hashes
+        );
+    }
+
+    // Test: Summarizing the tree matches the answer key's extension counts.
+    let extension_counts = Arc::new(Mutex::new(HashMap::new()));
+    let summarization_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    let _summarization_attempt = folsum::summarize_directory(
+        &Arc::new(Mutex::new(Some(first_tree.base_path.clone()))),
+        &folsum::SummarizationOptions::default(),
+        &folsum::SummarizationState {
+            extension_counts: Arc::clone(&extension_counts),
+            summarization_status: Arc::clone(&summarization_status),
+            ..Default::default()
+        },
+    );
+    thread::sleep(Duration::from_secs(1));
+    assert_eq!(
+        *summarization_status.lock().unwrap(),
+        folsum::WorkerStatus::Finished
+    );
+    assert_eq!(
+        *extension_counts.lock().unwrap(),
+        first_tree.extension_counts
+    );
+
+    // Test: Dropping a tree deletes its directory.
+    let dropped_path = other_tree.base_path.clone();
+    drop(other_tree);
+    assert!(!dropped_path.exists());
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_end_to_end_with_perturbed_files() {
+    // Create a tree of files and a checksum file that vouches for every one of them.
+    let test_tree = folsum::TestTree::new(&folsum::TestTreeOptions {
+        seed: 3154,
+        ..Default::default()
+    })
+    .unwrap();
+    let mut listed_files: Vec<&PathBuf> = test_tree.file_hashes.keys().collect();
+    listed_files.sort();
+    let checksum_lines: String = listed_files
+        .iter()
+        .map(|relative_path| {
+            format!(
+                "{}  {}\n",
+                test_tree.file_hashes[*relative_path].sha256,
+                relative_path.to_string_lossy()
+            )
+        })
+        .collect();
+    fs::write(test_tree.path_of("checksums.sha256"), checksum_lines).unwrap();
+    let mut expected_counts: HashMap<String, u32> = test_tree.extension_counts.clone();
+    *expected_counts.entry(String::from("sha256")).or_insert(0) += 1;
+
+    // Perturb the tree: modify one file, delete one, move one, and add one that isn't listed.
+    let (modified_file, deleted_file, moved_file) =
+        (listed_files[0], listed_files[1], listed_files[2]);
+    fs::write(test_tree.path_of(modified_file), "tampered").unwrap();
+    fs::remove_file(test_tree.path_of(deleted_file)).unwrap();
+    let deleted_extension = deleted_file.extension().unwrap().to_string_lossy();
+    *expected_counts.get_mut(deleted_extension.as_ref()).unwrap() -= 1;
+    expected_counts.retain(|_extension, count| *count > 0);
+    fs::rename(
+        test_tree.path_of(moved_file),
+        test_tree.path_of(format!(
+            "moved_{}",
+            moved_file.file_name().unwrap().to_string_lossy()
+        )),
+    )
+    .unwrap();
+    fs::write(test_tree.path_of("added.txt"), "new").unwrap();
+    *expected_counts.entry(String::from("txt")).or_insert(0) += 1;
+
+    // Summarize the perturbed tree and verify it against the checksum file.
+    let extension_counts = Arc::new(Mutex::new(HashMap::new()));
+    let checksum_findings = Arc::new(Mutex::new(Vec::new()));
+    let summarization_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    let _summarization_attempt = folsum::summarize_directory(
+        &Arc::new(Mutex::new(Some(test_tree.base_path.clone()))),
+        &folsum::SummarizationOptions {
+            verify_checksum_files: true,
+            ..Default::default()
+        },
+        &folsum::SummarizationState {
+            extension_counts: Arc::clone(&extension_counts),
+            checksum_findings: Arc::clone(&checksum_findings),
+            summarization_status: Arc::clone(&summarization_status),
+            ..Default::default()
+        },
+    );
+    thread::sleep(Duration::from_secs(2));
+    assert_eq!(
+        *summarization_status.lock().unwrap(),
+        folsum::WorkerStatus::Finished
+    );
+    // Test: Extension counts reflect the deleted and added files.
+    assert_eq!(*extension_counts.lock().unwrap(), expected_counts);
+    // Test: Each perturbation shows up as its own finding, and untouched files are verified.
+    let outcomes: HashMap<PathBuf, folsum::ChecksumOutcome> = checksum_findings
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|finding| (finding.listed_file.clone(), finding.outcome))
+        .collect();
+    assert_eq!(outcomes.len(), listed_files.len());
+    for relative_path in &listed_files {
+        let expected_outcome = match *relative_path {
+            listed_file if listed_file == modified_file => folsum::ChecksumOutcome::Mismatch,
+            listed_file if listed_file == deleted_file || listed_file == moved_file => {
+                folsum::ChecksumOutcome::Missing
+            }
+            _ => folsum::ChecksumOutcome::Verified,
+        };
+        assert_eq!(
+            outcomes[&test_tree.path_of(relative_path)],
+            expected_outcome,
+            "Wrong outcome for {:?}",
+            relative_path
+        );
+    }
+
+    // Export the summarization outside of the summarized tree.
+    let export_file =
+        std::env::temp_dir().join(format!("folsum_end_to_end_{}.csv", std::process::id()));
+    let export_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    let _export_attempt = folsum::export_csv(
+        &Arc::new(Mutex::new(Some(export_file.clone()))),
+        &extension_counts,
+        &None,
+        &folsum::ExportMetadata::default(),
+        &export_status,
+    );
+    thread::sleep(Duration::from_secs(1));
+    let exported_counts = read_csv_contents(&export_file);
+    let _delete_result = fs::remove_file(&export_file);
+    // Test: The export has the same counts as the summarization.
+    assert_eq!(
+        *export_status.lock().unwrap(),
+        folsum::WorkerStatus::Finished
+    );
+    assert_eq!(
+        exported_counts
+            .unwrap()
+            .into_iter()
+            .collect::<HashMap<_, _>>(),
+        expected_counts
+    );
+}
+
+#[test]
+fn test_grouped_discrepancies() {
+    let listed_finding =
+        |listed_name: &str, outcome: folsum::ChecksumOutcome| folsum::ChecksumFinding {
+            checksum_file: PathBuf::from("evidence/checksums.md5"),
+            listed_file: PathBuf::from(listed_name),
+            listed_hash: String::from("900150983cd24fb0d6963f7d28e17f72"),
+            outcome,
+            details: folsum::ListedFileDetails::default(),
+            listed_at: None,
+        };
+    let checksum_findings = vec![
+        listed_finding("evidence/gone.jpg", folsum::ChecksumOutcome::Missing),
+        listed_finding("evidence/fine.jpg", folsum::ChecksumOutcome::Verified),
+        listed_finding("evidence/altered.jpg", folsum::ChecksumOutcome::Mismatch),
+        listed_finding("evidence/README", folsum::ChecksumOutcome::Missing),
+    ];
+    let grouped_names: Vec<(folsum::ChecksumOutcome, Vec<String>)> =
+        folsum::group_discrepancies(&checksum_findings)
+            .into_iter()
+            .map(|(outcome, grouped_findings)| {
+                let grouped_names = grouped_findings
+                    .iter()
+                    .map(|finding| folsum::extension_name(&finding.listed_file))
+                    .collect();
+                (outcome, grouped_names)
+            })
+            .collect();
+    // Test: Discrepancies are grouped by what went wrong, mismatches first, leaving out files that held up.
+    // Test: Files are tied to the results table rows that they're counted in, including extensionless ones.
+    assert_eq!(
+        grouped_names,
+        vec![
+            (folsum::ChecksumOutcome::Mismatch, vec![String::from("jpg")]),
+            (
+                folsum::ChecksumOutcome::Missing,
+                vec![String::from("jpg"), String::from("No extension")]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_waiting_for_workers() {
+    // Mock slow work, like a summarization of a big network share.
+    let worker_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    folsum::spawn_worker(&worker_status, || {
+        thread::sleep(Duration::from_millis(500));
+        Ok(())
+    });
+    // Test: Workers are counted as soon as they're started.
+    assert!(folsum::running_workers() >= 1);
+    // Test: Waiting gives up when time runs out, instead of holding up closing FolSum forever.
+    assert!(!folsum::wait_for_workers(Duration::ZERO));
+    // Test: Waiting finishes once every worker's done. Other tests' workers are counted too, so give them time.
+    assert!(folsum::wait_for_workers(Duration::from_secs(60)));
+    assert_eq!(
+        *worker_status.lock().unwrap(),
+        folsum::WorkerStatus::Finished
+    );
+    // Test: Nothing asks workers to stop until FolSum's closing.
+    assert!(!folsum::stop_requested());
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_stopped_workers_leave_nothing_behind() {
+    let test_tree = folsum::TestTree::new(&folsum::TestTreeOptions {
+        seed: 3213,
+        file_count: 500,
+        ..Default::default()
+    })
+    .unwrap();
+    let summarized_directory = Arc::new(Mutex::new(Some(test_tree.base_path.clone())));
+    // Start a summarization, and stop it before it gets through the tree.
+    let summarization_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    let _summarization_attempt = folsum::summarize_directory(
+        &summarized_directory,
+        &folsum::SummarizationOptions::default(),
+        &folsum::SummarizationState {
+            summarization_status: Arc::clone(&summarization_status),
+            ..Default::default()
+        },
+    );
+    folsum::stop_worker(&summarization_status);
+    // Start an SPDX export into the tree, and stop it before it's written.
+    let export_path: PathBuf = test_tree.path_of("stopped.spdx");
+    let export_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    let _export_attempt = folsum::export_spdx(
+        &Arc::new(Mutex::new(Some(export_path.clone()))),
+        &summarized_directory,
+        &None,
+        &folsum::ExportMetadata::default(),
+        &folsum::SummarizationOptions::default(),
+        &export_status,
+    );
+    folsum::stop_worker(&export_status);
+    // Wait for just these workers to give up, since other tests' workers might still be writing.
+    assert!(folsum::wait_for_worker(
+        &summarization_status,
+        Duration::from_secs(60)
+    ));
+    assert!(folsum::wait_for_worker(
+        &export_status,
+        Duration::from_secs(60)
+    ));
+    let left_behind: Vec<String> = fs::read_dir(&test_tree.base_path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|file_name| file_name.starts_with(folsum::TEMP_FILE_PREFIX))
+        .collect();
+
+    // Test: Stopped work says it was stopped, instead of looking like it finished.
+    assert_eq!(
+        *folsum::lock_shared(&summarization_status),
+        folsum::WorkerStatus::Failed(String::from(folsum::STOPPED_REASON))
+    );
+    assert_eq!(
+        *folsum::lock_shared(&export_status),
+        folsum::WorkerStatus::Failed(String::from(folsum::STOPPED_REASON))
+    );
+    // Test: Stopped exports leave neither a half-written export nor its temporary file behind.
+    assert!(!export_path.exists());
+    assert!(left_behind.is_empty());
+    // Test: Stopping one worker doesn't ask the rest of FolSum to stop.
+    assert!(!folsum::stop_requested());
+}
+
+#[test]
+fn test_tool_self_test() {
+    let self_test: folsum::SelfTest = folsum::run_self_test();
+    // Test: Every hash algorithm that FolSum uses gives the published answers.
+    assert!(self_test.passed());
+    assert_eq!(self_test.summary(), "passed (MD5, SHA-1, SHA-256)");
+    // Test: Failures name the algorithms that gave wrong answers, so they stand out in export headers.
+    let failed_self_test = folsum::SelfTest {
+        algorithms: vec![("MD5", true), ("SHA-1", false), ("SHA-256", true)],
+    };
+    assert!(!failed_self_test.passed());
+    assert_eq!(failed_self_test.summary(), "FAILED (SHA-1)");
+}
+
+#[test]
+fn test_atomic_writes() {
+    let write_directory = PathBuf::from("atomic_write_test_dir");
+    fs::create_dir_all(&write_directory).unwrap();
+    let export_filename: PathBuf = write_directory.join("export.csv");
+    folsum::write_atomically(&export_filename, b"File Extension, Occurrences\npdf,1\n").unwrap();
+    let first_export: String = fs::read_to_string(&export_filename).unwrap();
+    // Mock an export that's killed halfway through writing with an error, like when a network share drops out.
+    let failed_write = folsum::write_atomically_with(&export_filename, |temp_file| {
+        temp_file.write_all(b"File Extension, Occurr")?;
+        Err(io::Error::new(io::ErrorKind::Interrupted, "Killed midway"))
+    });
+    let after_failure: String = fs::read_to_string(&export_filename).unwrap();
+    // Mock an export that's killed halfway through writing with a panic.
+    let panicked_write = std::panic::catch_unwind(|| {
+        folsum::write_atomically_with(&export_filename, |temp_file| {
+            temp_file.write_all(b"File Extension, Occurr")?;
+            panic!("Killed midway");
+        })
+    });
+    let after_panic: String = fs::read_to_string(&export_filename).unwrap();
+    // Mock a new export that's killed before it's done.
+    let never_finished: PathBuf = write_directory.join("never_finished.csv");
+    let _failed_write = folsum::write_atomically_with(&never_finished, |temp_file| {
+        temp_file.write_all(b"File Extension")?;
+        Err(io::Error::new(io::ErrorKind::Interrupted, "Killed midway"))
+    });
+    let left_behind: Vec<String> = fs::read_dir(&write_directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    folsum::write_atomically(&export_filename, b"File Extension, Occurrences\n").unwrap();
+    let second_export: String = fs::read_to_string(&export_filename).unwrap();
+    let _delete_result = fs::remove_dir_all(&write_directory);
+    // Test: Whole writes land as they were written.
+    assert_eq!(first_export, "File Extension, Occurrences\npdf,1\n");
+    // Test: Interrupted writes fail, and leave the file that was there before alone.
+    assert!(failed_write.is_err());
+    assert!(panicked_write.is_err());
+    assert_eq!(after_failure, first_export);
+    assert_eq!(after_panic, first_export);
+    // Test: No half-written files or temporary files are left behind.
+    assert_eq!(left_behind, vec![String::from("export.csv")]);
+    // Test: Finished writes replace the file that was there before.
+    assert_eq!(second_export, "File Extension, Occurrences\n");
+}
+
+#[test]
+fn test_double_started_summarization() {
+    let actual_extensions = TestFiles::new("double_start_test_dir").unwrap();
+    let extension_counts = Arc::new(Mutex::new(HashMap::new()));
+    let summarization_path = Arc::new(Mutex::new(Some(actual_extensions.base_path.clone())));
+    let summarization_start = Arc::new(Mutex::new(Instant::now()));
+    let summarization_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    let start_summarization = || {
+        folsum::summarize_directory(
+            &summarization_path,
+            &folsum::SummarizationOptions::default(),
+            &folsum::SummarizationState {
+                extension_counts: Arc::clone(&extension_counts),
+                summarization_start: Arc::clone(&summarization_start),
+                summarization_status: Arc::clone(&summarization_status),
+                ..Default::default()
+            },
+        )
+    };
+    // Hold the first summarization at its starting line (it starts its stopwatch first), so it's still running when
+    // the second click comes in, however fast the computer is.
+    let held_start = summarization_start.lock().unwrap();
+    let first_attempt = start_summarization();
+    let second_attempt = start_summarization();
+    drop(held_start);
+    // Wait a bit so the summarization thread has a chance to do it's thing.
+    thread::sleep(Duration::from_secs(1));
+    // Test: The first click starts a summarization, and the second one is refused while it runs.
+    assert_eq!(first_attempt, Ok(()));
+    assert!(second_attempt.is_err());
+    assert_eq!(
+        *summarization_status.lock().unwrap(),
+        folsum::WorkerStatus::Finished
+    );
+    // Test: Each file is only counted once.
+    verify_extension_counts(&extension_counts.lock().unwrap(), &actual_extensions);
+    // Test: Summarizations can be started again once the last one's finished.
+    assert_eq!(start_summarization(), Ok(()));
+    thread::sleep(Duration::from_secs(1));
+    verify_extension_counts(&extension_counts.lock().unwrap(), &actual_extensions);
+}
+
+#[test]
+fn test_finding_bulk_actions() {
+    let evidence_directory = PathBuf::from("bulk_actions_test_dir");
+    fs::create_dir_all(&evidence_directory).unwrap();
+    let listed_file: PathBuf = evidence_directory.join("late.txt");
+    let altered_file: PathBuf = evidence_directory.join("altered.txt");
+    fs::write(&altered_file, "abd").unwrap();
+    let listed_finding = |listed_file: &PathBuf, outcome: folsum::ChecksumOutcome| {
+        folsum::ChecksumFinding {
+            checksum_file: evidence_directory.join("checksums.md5"),
+            listed_file: listed_file.clone(),
+            // MD5 of "abc".
+            listed_hash: String::from("900150983cd24fb0d6963f7d28e17f72"),
+            outcome,
+            details: folsum::ListedFileDetails::default(),
+            listed_at: None,
+        }
+    };
+    let checksum_findings = Arc::new(Mutex::new(vec![
+        listed_finding(&listed_file, folsum::ChecksumOutcome::Missing),
+        listed_finding(&altered_file, folsum::ChecksumOutcome::Mismatch),
+    ]));
+    // Mock the missing file turning up, like after a slow copy finished.
+    fs::write(&listed_file, "abc").unwrap();
+    let reverify_status = Arc::new(Mutex::new(folsum::WorkerStatus::default()));
+    let selected_files: Vec<PathBuf> = vec![listed_file.clone(), altered_file.clone()];
+    let _reverify_attempt = folsum::reverify_findings(
+        &checksum_findings,
+        &selected_files,
+        &folsum::SummarizationOptions::default(),
+        &reverify_status,
+    );
+    // Wait a sec for the files to be verified again.
+    thread::sleep(Duration::from_secs(1));
+    let _delete_result = fs::remove_dir_all(&evidence_directory);
+    assert_eq!(
+        *reverify_status.lock().unwrap(),
+        folsum::WorkerStatus::Finished
+    );
+    let locked_findings = checksum_findings.lock().unwrap();
+    // Test: Selected findings are updated in place with what's on disk now.
+    assert_eq!(
+        locked_findings[0].outcome,
+        folsum::ChecksumOutcome::Verified
+    );
+    assert_eq!(
+        locked_findings[1].outcome,
+        folsum::ChecksumOutcome::Mismatch
+    );
+    // Test: Nothing's verified again when nothing's selected.
+    assert!(folsum::reverify_findings(
+        &checksum_findings,
+        &[],
+        &folsum::SummarizationOptions::default(),
+        &reverify_status,
+    )
+    .is_err());
+
+    let mut finding_decisions = folsum::FindingDecisions::default();
+    finding_decisions.accept(&[&locked_findings[1]]);
+    // Test: Accepted files stay accepted as long as their content doesn't change again.
+    assert!(finding_decisions.accepts(&locked_findings[1]));
+    let mut changed_again: folsum::ChecksumFinding = locked_findings[1].clone();
+    changed_again.details.actual_hash = Some(String::from("0cc175b9c0f1b6a831c399e269772661"));
+    assert!(!finding_decisions.accepts(&changed_again));
+    finding_decisions.ignore(&[&locked_findings[0], &locked_findings[0]]);
+    // Test: Ignoring the same file twice only lists it once.
+    assert_eq!(finding_decisions.ignored, vec![listed_file.clone()]);
+    // Test: Exports keep decided findings, and say what was decided about them.
+    let exported_rows: Vec<String> = folsum::findings_rows(
+        &locked_findings,
+        &finding_decisions,
+        &folsum::SeverityRules::default(),
+    );
+    assert!(exported_rows[0].ends_with(
+        ",verified,info,900150983cd24fb0d6963f7d28e17f72,900150983cd24fb0d6963f7d28e17f72,ignored"
+    ));
+    assert!(exported_rows[1].ends_with(
+        ",mismatch,critical,900150983cd24fb0d6963f7d28e17f72,4911e516e5aa21d327512e0c8b197616,accepted"
+    ));
+    // Test: Undoing decisions takes them all back.
+    finding_decisions.undo(&[&locked_findings[0], &locked_findings[1]]);
+    assert_eq!(finding_decisions, folsum::FindingDecisions::default());
+}
+
+#[test]
+fn test_audit_events() {
+    let listed_finding = |outcome: folsum::ChecksumOutcome| folsum::ChecksumFinding {
+        checksum_file: PathBuf::from("evidence/checksums.md5"),
+        listed_file: PathBuf::from("evidence/photo.jpg"),
+        listed_hash: String::from("900150983cd24fb0d6963f7d28e17f72"),
+        outcome,
+        details: folsum::ListedFileDetails::default(),
+        listed_at: None,
+    };
+    // Test: Only findings that didn't hold up are discrepancies.
+    assert_eq!(
+        folsum::AuditEvent::checksum_discrepancy(&listed_finding(
+            folsum::ChecksumOutcome::Verified
+        )),
+        None
+    );
+    assert_eq!(
+        folsum::AuditEvent::checksum_discrepancy(&listed_finding(
+            folsum::ChecksumOutcome::SizeOnly { file_size: 3 }
+        )),
+        None
+    );
+    let mismatch_event = folsum::AuditEvent::checksum_discrepancy(&listed_finding(
+        folsum::ChecksumOutcome::Mismatch,
+    ))
+    .unwrap();
+    // Test: Log lines say what happened and to which file or directory.
+    assert_eq!(
+        mismatch_event.message(),
+        format!(
+            "Checksum discrepancy (mismatch): {}",
+            Path::new("evidence/photo.jpg").display()
+        )
+    );
+    assert_eq!(
+        folsum::AuditEvent::summarization_finished(Path::new("evidence"), 2).message(),
+        "Finished summarizing evidence (2 files)"
+    );
+    // Test: File counts past what one extension's count can hold are logged whole.
+    assert_eq!(
+        folsum::AuditEvent::summarization_finished(Path::new("evidence"), 4_294_967_297).message(),
+        "Finished summarizing evidence (4294967297 files)"
+    );
+}
+
+#[test]
+fn test_human_friendly_formatting() {
+    // Durations are broken into hours, minutes, and seconds, without leading zero units.
+    assert_eq!(
+        folsum::format_duration(&Duration::from_millis(350)),
+        "350ms"
+    );
+    assert_eq!(folsum::format_duration(&Duration::from_secs(12)), "12s");
+    assert_eq!(
+        folsum::format_duration(&Duration::from_secs(5412)),
+        "1h 30m 12s"
+    );
+    assert_eq!(
+        folsum::format_duration(&Duration::from_secs(3600)),
+        "1h 0m 0s"
+    );
+
+    // Counts use the thousands separator of the user's language.
+    assert_eq!(folsum::format_count(999, folsum::Language::English), "999");
+    assert_eq!(
+        folsum::format_count(1_250_000, folsum::Language::English),
+        "1,250,000"
+    );
+    assert_eq!(
+        folsum::format_count(1_250_000, folsum::Language::Spanish),
+        "1.250.000"
+    );
+    assert_eq!(
+        folsum::format_count(1_250_000, folsum::Language::Ukrainian),
+        "1\u{a0}250\u{a0}000"
+    );
+
+    // Byte sizes use the biggest binary unit that fits, with the language's decimal separator.
+    assert_eq!(
+        folsum::format_bytes(512, folsum::Language::English),
+        "512 B"
+    );
+    assert_eq!(
+        folsum::format_bytes(1_572_864, folsum::Language::English),
+        "1.5 MiB"
+    );
+    assert_eq!(
+        folsum::format_bytes(1_572_864, folsum::Language::Spanish),
+        "1,5 MiB"
+    );
+
+    // Long hashes are cut down to their first and last six characters, and short ones are left alone.
+    assert_eq!(
+        folsum::format_hash("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        "ba7816…0015ad"
+    );
+    assert_eq!(folsum::format_hash("ba7816bf"), "ba7816bf");
+}
+
+#[test]
+fn test_natural_sort_order() {
+    let mut filenames = vec![
+        "file10.pdf",
+        "File3.pdf",
+        "file2.pdf",
+        "file02.pdf",
+        "file1.pdf",
+        "file.pdf",
+        "file99999999999999999999999.pdf",
+    ];
+    filenames.sort_by(|a, b| folsum::natural_cmp(a, b));
+    // Test: Numbers are ordered by value, case doesn't split up names, and huge numbers don't overflow.
+    assert_eq!(
+        filenames,
+        vec![
+            "file.pdf",
+            "file1.pdf",
+            "file02.pdf",
+            "file2.pdf",
+            "File3.pdf",
+            "file10.pdf",
+            "file99999999999999999999999.pdf",
+        ]
+    );
+    // Test: Extensions with the same count are listed in natural order in the table and in exports.
+    let extension_counts: HashMap<String, u32> = HashMap::from([
+        (String::from("r10"), 1),
+        (String::from("r2"), 1),
+        (String::from("rar"), 2),
+    ]);
+    let sorted_extensions: Vec<&str> = folsum::sort_counts(&extension_counts)
+        .into_iter()
+        .map(|(extension_name, _)| extension_name.as_str())
+        .collect();
+    assert_eq!(sorted_extensions, vec!["rar", "r2", "r10"]);
+}
+
+#[test]
+#[cfg(feature = "gui")]
+fn test_diagnostics_bundle() {
+    use std::io::Read;
+
+    let bundle_path = std::env::temp_dir().join(format!(
+        "folsum_diagnostics_test_{}.zip",
+        std::process::id()
+    ));
+    let save_attempt = folsum::save_diagnostics_bundle(&bundle_path, "(settings)", "Files: 3\n");
+    let bundle_file = File::open(&bundle_path);
+    let _delete_result = fs::remove_file(&bundle_path);
+    assert_eq!(save_attempt, Ok(()));
+
+    // Test: The bundle has the build description, settings, and summary in separate files.
+    let mut bundle = zip::ZipArchive::new(bundle_file.unwrap()).unwrap();
+    for (entry_name, expected_contents) in [
+        ("about.txt", folsum::build_description()),
+        ("settings.ron", String::from("(settings)")),
+        ("last_summary.txt", String::from("Files: 3\n")),
+    ] {
+        let mut entry_contents = String::new();
+        bundle
+            .by_name(entry_name)
+            .unwrap()
+            .read_to_string(&mut entry_contents)
+            .unwrap();
+        assert_eq!(entry_contents, expected_contents);
+    }
+    assert!(bundle.by_name("recent_log.txt").is_ok());
+    // Test: The build description says which version it's from.
+    assert!(folsum::build_description().contains(folsum::FOLSUM_VERSION));
+}
+
+#[test]
+#[cfg(feature = "rest-api")]
+fn test_results_api() {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    // Mock results that the GUI would share with the API after summarizing.
+    let api_results = folsum::ApiResults {
+        summarization_path: Arc::new(Mutex::new(Some(PathBuf::from("evidence")))),
+        extension_counts: Arc::new(Mutex::new(HashMap::from([(String::from("jpg"), 2)]))),
+        extension_bytes: Arc::new(Mutex::new(HashMap::from([(String::from("jpg"), 10)]))),
+        checksum_findings: Arc::new(Mutex::new(Vec::new())),
+        severity_rules: Arc::new(Mutex::new(folsum::SeverityRules::default())),
+        phase_timings: Arc::new(Mutex::new(folsum::PhaseTimings {
+            walk: Duration::from_millis(1500),
+            ..Default::default()
+        })),
+        summarization_status: Arc::new(Mutex::new(folsum::WorkerStatus::Finished)),
+    };
+    // Test: The API won't start without a token.
+    assert!(folsum::serve_results(&folsum::ApiSettings::default(), &api_results).is_err());
+    // Let the OS pick a free port so the test doesn't collide with anything.
+    let api_server = folsum::serve_results(
+        &folsum::ApiSettings {
+            enabled: true,
+            port: 0,
+            token: String::from("hunter2"),
+        },
+        &api_results,
+    )
+    .unwrap();
+    let send_request = |request: &str| -> String {
+        let mut client = TcpStream::connect(("127.0.0.1", api_server.port)).unwrap();
+        client.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        response
+    };
+    // Test: Requests without the right token are refused.
+    assert!(send_request("GET /summary HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 401"));
+    assert!(
+        send_request("GET /summary HTTP/1.1\r\nAuthorization: Bearer hunter3\r\n\r\n")
+            .starts_with("HTTP/1.1 401")
+    );
+    // Test: The summary lists the chosen directory and each extension's count and size.
+    let summary_response =
+        send_request("GET /summary HTTP/1.1\r\nAuthorization: Bearer hunter2\r\n\r\n");
+    assert!(summary_response.starts_with("HTTP/1.1 200"));
+    assert!(summary_response.contains(r#""directory":"evidence""#));
+    assert!(summary_response.contains(r#""status":"finished""#));
+    assert!(summary_response.contains(r#""extension":"jpg""#));
+    assert!(summary_response.contains(r#""total_bytes":10"#));
+    // Test: The summary says how long each phase took, leaving out phases that haven't run.
+    assert!(summary_response.contains(r#""walk_ms":1500"#));
+    assert!(summary_response.contains(r#""export_write_ms":null"#));
+    // Test: The summary has the audit verdict, which passes when nothing's been found wrong.
+    assert!(summary_response.contains(r#""content":"passed""#));
+    // Test: Total files add up past what one extension's count can hold.
+    folsum::lock_shared(&api_results.extension_counts).insert(String::from("png"), u32::MAX);
+    let summary_response =
+        send_request("GET /summary HTTP/1.1\r\nAuthorization: Bearer hunter2\r\n\r\n");
+    assert!(summary_response.contains(r#""total_files":4294967297"#));
+    // Test: A client that connects without sending anything doesn't hold up other clients.
+    let _silent_client = TcpStream::connect(("127.0.0.1", api_server.port)).unwrap();
+    let request_start = Instant::now();
+    let summary_response =
+        send_request("GET /summary HTTP/1.1\r\nAuthorization: Bearer hunter2\r\n\r\n");
+    assert!(summary_response.starts_with("HTTP/1.1 200"));
+    assert!(request_start.elapsed() < Duration::from_millis(400));
+    // Test: Unknown endpoints and changes are turned away.
+    assert!(
+        send_request("GET /secrets HTTP/1.1\r\nAuthorization: Bearer hunter2\r\n\r\n")
+            .starts_with("HTTP/1.1 404")
+    );
+    assert!(
+        send_request("DELETE /summary HTTP/1.1\r\nAuthorization: Bearer hunter2\r\n\r\n")
+            .starts_with("HTTP/1.1 405")
+    );
+    // Test: Clients past the limit are told to come back later instead of each getting a thread. Wait for the server
+    // to give up on the earlier silent client first, so it doesn't take up one of the spots.
+    thread::sleep(Duration::from_millis(600));
+    let silent_clients: Vec<TcpStream> = (0..folsum::MOST_API_CLIENTS)
+        .map(|_| TcpStream::connect(("127.0.0.1", api_server.port)).unwrap())
+        .collect();
+    thread::sleep(Duration::from_millis(300));
+    assert!(
+        send_request("GET /summary HTTP/1.1\r\nAuthorization: Bearer hunter2\r\n\r\n")
+            .starts_with("HTTP/1.1 503")
+    );
+    // Test: Clients are answered again once others hang up.
+    drop(silent_clients);
+    thread::sleep(Duration::from_millis(300));
+    assert!(
+        send_request("GET /summary HTTP/1.1\r\nAuthorization: Bearer hunter2\r\n\r\n")
+            .starts_with("HTTP/1.1 200")
+    );
+}
+
+#[test]
+#[cfg(feature = "gui")]
+fn test_action_log_is_append_only() {
+    let log_directory = PathBuf::from("action_log_test_dir");
+    let log_path = log_directory.join("action_log.tsv");
+    folsum::append_action(&log_path, "summarize", Path::new("evidence")).unwrap();
+    folsum::append_action(&log_path, "export", Path::new("reports/with\ttab.csv")).unwrap();
+    let logged_lines: Vec<Vec<String>> = fs::read_to_string(&log_path)
+        .unwrap()
+        .lines()
+        .map(|log_line| log_line.split('\t').map(String::from).collect())
+        .collect();
+    let _delete_result = fs::remove_dir_all(&log_directory);
+    // Test: Each action gets its own line, in order, without earlier lines being overwritten.
+    assert_eq!(logged_lines.len(), 2);
+    // Test: Lines say when, which OS user, what was done, and to what, with tabs kept out of paths.
+    for (logged_line, (action, target)) in logged_lines.iter().zip([
+        ("summarize", "evidence"),
+        ("export", "reports/with tab.csv"),
+    ]) {
+        assert!(DateTime::parse_from_rfc3339(&logged_line[0]).is_ok());
+        assert_eq!(logged_line[1], folsum::os_user_name());
+        assert_eq!(logged_line[2], action);
+        assert_eq!(Path::new(&logged_line[3]), Path::new(target));
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn test_os_user_name() {
+    let id_output = std::process::Command::new("id")
+        .arg("-un")
+        .output()
+        .unwrap();
+    // Test: The account's name comes from the user database, like it does for `id`.
+    assert_eq!(
+        folsum::os_user_name(),
+        String::from_utf8_lossy(&id_output.stdout).trim()
+    );
+}
+
+#[test]
+#[cfg(feature = "gui")]
+fn test_every_message_is_translated() {
+    // Use the English messages as the list of everything that the GUI shows.
+    let message_ids: Vec<&str> = include_str!("../locales/en-US.ftl")
+        .lines()
+        .filter(|line| line.starts_with(|first: char| first.is_ascii_lowercase()))
+        .filter_map(|line| line.split_once(" =").map(|(message_id, _)| message_id))
+        .collect();
+    assert!(!message_ids.is_empty());
+    for language in folsum::Language::ALL {
+        let translations = folsum::Translations::new(language);
+        for message_id in message_ids.iter() {
+            // Missing messages are shown as their IDs.
+            assert_ne!(
+                translations.get(message_id),
+                *message_id,
+                "{:?} is missing a translation for {}",
+                language,
+                message_id
+            );
+        }
+    }
+}
+
 /// Test if the occurrences (the number of times a file with a given extension was encountered) for each
 /// file extension is accurate.
 fn verify_extension_counts(
@@ -117,17 +2473,43 @@ fn read_csv_contents(export_file: &PathBuf) -> io::Result<Vec<(String, u32)>> {
     Ok(extension_counts)
 }
 
-/// Create nested subdirectories with empty files of various extensions in `test_dir/`.
+/// Split CSV into rows of fields the way spreadsheets do, unquoting quoted fields that have commas or newlines.
+fn parse_quoted_csv(contents: &str) -> Vec<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut characters = contents.chars().peekable();
+    while let Some(character) = characters.next() {
+        match (in_quotes, character) {
+            // Doubled quotes inside of a quoted field are one literal quote.
+            (true, '"') if characters.peek() == Some(&'"') => {
+                field.push('"');
+                characters.next();
+            }
+            (_, '"') => in_quotes = !in_quotes,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (_, character) => field.push(character),
+        }
+    }
+    rows
+}
+
+/// Create nested subdirectories with empty files of various extensions in a test directory.
 struct TestFiles {
-    // Create the test directory in `./test-dir`.
+    // Create the test directory in the current directory, like `./test_dir`.
     base_path: PathBuf,
     // Remember the number of files created for each extension as a test "answer key."
     extension_counts: HashMap<String, u32>,
 }
 
 impl TestFiles {
-    fn new() -> std::io::Result<Self> {
-        let base_path = PathBuf::from("test_dir");
+    fn new(directory_name: &str) -> std::io::Result<Self> {
+        let base_path = PathBuf::from(directory_name);
         let mut current_path = base_path.clone();
         // Keep track of how many files of each extension are created.
         let mut extension_counts: HashMap<String, u32> = HashMap::new();
@@ -1,19 +1,20 @@
 use std::{
     env,
-    fs::{create_dir_all, read_to_string},
+    fs::{create_dir_all, read_to_string, remove_file, write},
     io::{self, Write},
     path::{Path, PathBuf},
     process::{Command, Output},
+    str::FromStr,
 };
 
 use env_logger::Env;
 use log::{debug, info};
 use toml::Value;
 
-use tauri_bundler::PackageType::MacOsBundle;
+use tauri_bundler::PackageType::{AppImage, Deb, MacOsBundle};
 use tauri_bundler::{
-    bundle_project, Bundle, BundleBinary, BundleSettings, PackageSettings, Settings,
-    SettingsBuilder,
+    bundle_project, AppCategory, Bundle, BundleBinary, BundleSettings, DebianSettings,
+    PackageSettings, PackageType, Settings, SettingsBuilder,
 };
 
 type DynError = Box<dyn std::error::Error>;
@@ -47,6 +48,12 @@ fn try_main() -> Result<(), DynError> {
         Some("bundle") => bundle(&folsum_root, &project_root),
         // If "dist" was passed as the first command line argument, then build and bundle the application.
         Some("dist") => dist(&folsum_root, &project_root),
+        // If "sign" was passed as the first command line argument, then codesign the bundled `.app`.
+        Some("sign") => sign(&project_root),
+        // If "notarize" was passed as the first command line argument, then notarize and staple the signed `.app`.
+        Some("notarize") => notarize(&project_root),
+        // If "integrate" was passed as the first command line argument, then add FolSum to the file manager.
+        Some("integrate") => integrate(&project_root, env::args().nth(2)),
         // If "help" was passed as the first command line argument, then describe available tasks.
         Some("help") => print_help(),
         // If the first command line argument was unrecognized, then describe available tasks.
@@ -57,8 +64,15 @@ fn try_main() -> Result<(), DynError> {
 fn print_help() -> Result<(), DynError> {
     info!("Tasks:
 
-           build           builds application
+           build           builds application (on macOS, a universal binary for Intel and Apple Silicon)
+           bundle          bundles the built application (`.app` on macOS, `.deb` and AppImage on Linux)
            dist            builds and bundles application (equivalent to running `build` and `bundle`)
+           sign            codesigns the bundled `.app` with the identity in `MACOS_CERTIFICATE_NAME` (macOS only)
+           notarize        notarizes the signed `.app` with Apple and staples the ticket to it (macOS only), using
+                           `PROD_MACOS_NOTARIZATION_APPLE_ID`, `PROD_MACOS_NOTARIZATION_TEAM_ID`, and
+                           `PROD_MACOS_NOTARIZATION_PWD`
+           integrate       adds \"Inventory with FolSum\" to the file manager's context menu for directories
+                           (optionally pass the path to the FolSum binary, defaults to `target/release/folsum`)
            help            prints this help message
            "
     );
@@ -71,19 +85,69 @@ fn dist(folsum_root: &PathBuf, project_root: &PathBuf) -> Result<(), DynError> {
 
     // Bundle binaries.
     bundle(&folsum_root, &project_root)?;
-    info!("Bundled binaries into .app bundle");
+    info!("Bundled binaries");
     Ok(())
 }
 
 fn build(project_root: &PathBuf) -> Result<(), DynError> {
+    match env::consts::OS {
+        // Make one binary that runs on both Intel and Apple Silicon Macs.
+        "macos" => build_universal(project_root),
+        _ => cargo_build(project_root, None),
+    }
+}
+
+// Architectures that go into a MacOS universal binary, paired with the names that `lipo` uses for them.
+const MACOS_TARGETS: [(&str, &str); 2] = [
+    ("x86_64-apple-darwin", "x86_64"),
+    ("aarch64-apple-darwin", "arm64"),
+];
+
+fn build_universal(project_root: &PathBuf) -> Result<(), DynError> {
+    // Build a binary for each architecture, like `target/aarch64-apple-darwin/release/folsum`.
+    for (target_triple, _) in MACOS_TARGETS {
+        cargo_build(project_root, Some(target_triple))?;
+    }
+    // Meld them into `target/release/folsum`, which is where the bundler expects to find the binary.
+    let universal_path: PathBuf = project_root.join("target/release/folsum");
+    create_dir_all(project_root.join("target/release"))?;
+    let mut lipo_command = Command::new("lipo");
+    lipo_command
+        .arg("-create")
+        .arg("-output")
+        .arg(&universal_path);
+    for (target_triple, lipo_architecture) in MACOS_TARGETS {
+        lipo_command
+            .args(["-arch", lipo_architecture])
+            .arg(project_root.join(format!("target/{}/release/folsum", target_triple)));
+    }
+    info!("Creating universal binary with `lipo`");
+    run(&mut lipo_command)?;
+    // Show which architectures made it in so a missing one is easy to spot.
+    run(Command::new("lipo").arg("-archs").arg(&universal_path))?;
+    info!("Created universal binary at {:?}", universal_path);
+    Ok(())
+}
+
+fn cargo_build(project_root: &PathBuf, target_triple: Option<&str>) -> Result<(), DynError> {
     // Get the path to the `cargo` executable in a reliable way. Defaults to `cargo` if not found.
     let cargo_path: String = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
     debug!("using `cargo` executable: {}", cargo_path);
-    // Run `cargo build --release` in `folsum/folsum/`.
-    info!("Starting build with `cargo build --release`");
-    let build_result: Output = Command::new(cargo_path)
+    let mut build_command = Command::new(cargo_path);
+    build_command
         .current_dir(project_root)
-        .args(&["build", "--release", "--color", "always"])
+        .args(["build", "--release", "--color", "always"]);
+    match target_triple {
+        // Only build FolSum for other targets because xtask doesn't need to run there.
+        // The target must be installed first, like with `rustup target add aarch64-apple-darwin`.
+        Some(target_triple) => {
+            info!("Starting build with `cargo build --release --target {target_triple}`");
+            build_command.args(["--package", "folsum", "--target", target_triple]);
+        }
+        // Run `cargo build --release` in `folsum/folsum/`.
+        None => info!("Starting build with `cargo build --release`"),
+    }
+    let build_result: Output = build_command
         .output()
         .expect("Failed to cargo build FolSum");
     debug!("build status: {}", build_result.status);
@@ -120,13 +184,24 @@ fn bundle(folsum_root: &PathBuf, project_root: &PathBuf) -> Result<(), DynError>
         .as_str()
         .expect("Failed to extract package description");
 
+    // Extract package authors, which Debian packages list as their maintainers.
+    let package_authors: Option<Vec<String>> =
+        cargo_values["package"]["authors"]
+            .as_array()
+            .map(|authors| {
+                authors
+                    .iter()
+                    .filter_map(|author| author.as_str().map(String::from))
+                    .collect()
+            });
+
     // Create package settings for Tauri Bundler with package values extracted from Cargo.toml.
     let package_settings: PackageSettings = PackageSettings {
         product_name: package_name.to_string(),
         version: package_version.to_string(),
         description: package_description.to_string(),
         homepage: None,
-        authors: None,
+        authors: package_authors,
         default_run: None,
     };
 
@@ -155,6 +230,21 @@ fn bundle(folsum_root: &PathBuf, project_root: &PathBuf) -> Result<(), DynError>
         .as_str()
         .expect("Failed to extract bundle copyright");
 
+    // Extract bundle category, which Linux desktop entries turn into menu categories.
+    let bundle_category: Option<AppCategory> = cargo_values["package"]["metadata"]["bundle"]
+        ["category"]
+        .as_str()
+        .and_then(|category| AppCategory::from_str(category).ok());
+
+    // Use our own desktop entry so Linux file managers offer FolSum in "Open With" menus for directories.
+    let desktop_template: PathBuf = project_root.join("xtask/templates/folsum.desktop");
+    // Depend on GTK because native file dialogs use it on Linux.
+    let debian_settings: DebianSettings = DebianSettings {
+        depends: Some(vec!["libgtk-3-0".to_string()]),
+        desktop_template: Some(desktop_template),
+        ..Default::default()
+    };
+
     // Create bundle settings for Tauri Bundler with bundle values extracted from Cargo.toml.
     let bundle_settings: BundleSettings = BundleSettings {
         identifier: Some(bundle_identifier.to_string()),
@@ -166,6 +256,9 @@ fn bundle(folsum_root: &PathBuf, project_root: &PathBuf) -> Result<(), DynError>
                 .collect(),
         ),
         copyright: Some(bundle_copyright.to_string()),
+        category: bundle_category,
+        short_description: Some(package_description.to_string()),
+        deb: debian_settings,
         ..Default::default()
     };
 
@@ -183,7 +276,7 @@ fn bundle(folsum_root: &PathBuf, project_root: &PathBuf) -> Result<(), DynError>
     let binary_name: &str = cargo_values["package"]["name"]
         .as_str()
         .expect("Failed to extract binary name");
-    // Expect the (universal) binary (created by `lipo`) to be `target/release/folsum`.
+    // Expect the binary to be `target/release/folsum`, which is a universal binary (created by `lipo`) on macOS.
     let binary_path: PathBuf = output_dir.join(binary_name);
 
     // Ensure that the binary exists and that it's a file. Otherwise, panic decriptively.
@@ -205,6 +298,13 @@ fn bundle(folsum_root: &PathBuf, project_root: &PathBuf) -> Result<(), DynError>
         .set_src_path(Some(binary_path.into_os_string().into_string().unwrap()));
     debug!("Defined binary settings: {:?}", binary_settings);
 
+    // Make packages for the platform that we're on because each bundler needs its platform's tools.
+    let package_types: Vec<PackageType> = match env::consts::OS {
+        "linux" => vec![Deb, AppImage],
+        _ => vec![MacOsBundle],
+    };
+    debug!("Bundling package types: {:?}", package_types);
+
     // Make a settings builder for Tauri Bundler.
     let settings_builder: SettingsBuilder = SettingsBuilder::new()
         // Add package settings to settings builder.
@@ -215,8 +315,8 @@ fn bundle(folsum_root: &PathBuf, project_root: &PathBuf) -> Result<(), DynError>
         .binaries(vec![binary_settings])
         // Set the project output directory.
         .project_out_directory(&output_dir)
-        // Set the package type to MacOsBundle.
-        .package_types(vec![MacOsBundle]);
+        // Set the package types for this platform.
+        .package_types(package_types);
     debug!("Defined all bundler settings");
 
     let bundler_settings: Settings = settings_builder
@@ -230,6 +330,191 @@ fn bundle(folsum_root: &PathBuf, project_root: &PathBuf) -> Result<(), DynError>
     Ok(())
 }
 
+fn sign(project_root: &Path) -> Result<(), DynError> {
+    let app_path: PathBuf = bundled_app_path(project_root)?;
+    // Use the same variable as CI so a signing identity only has to be set up once.
+    let signing_identity: String = required_env("MACOS_CERTIFICATE_NAME")?;
+    // Codesign the app bundle with hardened runtime so it passes notarization.
+    info!("Signing {:?}", app_path);
+    run(Command::new("codesign")
+        .args([
+            "--force",
+            "--sign",
+            &signing_identity,
+            "--options",
+            "runtime",
+            "--verbose",
+        ])
+        .arg(&app_path))?;
+    // Ensure that the signature holds up before it's sent off for notarization.
+    run(Command::new("codesign")
+        .args(["--verify", "--deep", "--strict", "--verbose=2"])
+        .arg(&app_path))?;
+    info!("Signed {:?}", app_path);
+    Ok(())
+}
+
+fn notarize(project_root: &Path) -> Result<(), DynError> {
+    let app_path: PathBuf = bundled_app_path(project_root)?;
+    let apple_id: String = required_env("PROD_MACOS_NOTARIZATION_APPLE_ID")?;
+    let team_id: String = required_env("PROD_MACOS_NOTARIZATION_TEAM_ID")?;
+    let app_password: String = required_env("PROD_MACOS_NOTARIZATION_PWD")?;
+    // App bundles can't be notarized directly, so compress it into a temporary archive.
+    let archive_path: PathBuf = app_path.with_extension("zip");
+    info!("Creating temporary notarization archive {:?}", archive_path);
+    run(Command::new("ditto")
+        .args(["-c", "-k", "--keepParent"])
+        .arg(&app_path)
+        .arg(&archive_path))?;
+    // Send the archive to Apple's notary service and wait for its verdict.
+    info!("Submitting {:?} for notarization", archive_path);
+    let submission_result = run(Command::new("xcrun")
+        .args(["notarytool", "submit"])
+        .arg(&archive_path)
+        .args(["--apple-id", &apple_id, "--team-id", &team_id])
+        .args(["--password", &app_password, "--wait"]));
+    // Delete the archive whether or not notarization worked so it isn't mistaken for a deliverable.
+    let _delete_result = remove_file(&archive_path);
+    submission_result?;
+    // "Attach the staple" to the app so MacOS can validate it offline.
+    info!("Stapling notarization ticket to {:?}", app_path);
+    run(Command::new("xcrun")
+        .args(["stapler", "staple"])
+        .arg(&app_path))?;
+    info!("Notarized {:?}", app_path);
+    Ok(())
+}
+
+// Find the `.app` that `cargo xtask bundle` made, like `target/release/bundle/macos/folsum.app`.
+fn bundled_app_path(project_root: &Path) -> Result<PathBuf, DynError> {
+    if env::consts::OS != "macos" {
+        return Err("Signing and notarization only work on macOS".into());
+    }
+    let app_path: PathBuf = project_root.join("target/release/bundle/macos/folsum.app");
+    match app_path.is_dir() {
+        true => Ok(app_path),
+        false => Err(format!(
+            "App bundle not found at {:?}, so run `cargo xtask dist` first",
+            app_path
+        )
+        .into()),
+    }
+}
+
+// Read an environment variable that a task can't run without, naming it if it's missing.
+fn required_env(variable_name: &str) -> Result<String, DynError> {
+    env::var(variable_name)
+        .map_err(|_| format!("Environment variable `{}` must be set", variable_name).into())
+}
+
+// Run a command, pass its output through, and fail if it fails.
+fn run(command: &mut Command) -> Result<(), DynError> {
+    // Log only the program because some arguments are secrets, like the notarization password.
+    debug!("running: {:?}", command.get_program());
+    let command_result: Output = command.output()?;
+    io::stdout().write_all(&command_result.stdout)?;
+    io::stderr().write_all(&command_result.stderr)?;
+    match command_result.status.success() {
+        true => Ok(()),
+        false => Err(format!(
+            "{:?} failed with {}",
+            command.get_program(),
+            command_result.status
+        )
+        .into()),
+    }
+}
+
+fn integrate(project_root: &Path, binary_argument: Option<String>) -> Result<(), DynError> {
+    // Use the user's FolSum binary if they gave one. Otherwise, assume it was built with `cargo xtask build`.
+    let binary_path: PathBuf = match binary_argument {
+        Some(binary_argument) => PathBuf::from(binary_argument),
+        None => project_root
+            .join("target/release/folsum")
+            .with_extension(env::consts::EXE_EXTENSION),
+    };
+    // Ensure that the binary exists so the context menu entry doesn't point at nothing.
+    if !binary_path.is_file() {
+        return Err(format!(
+            "FolSum binary not found at {:?}, so run `cargo xtask build` first",
+            binary_path
+        )
+        .into());
+    }
+    // Make the binary's path absolute because file managers don't launch programs from the project root.
+    let binary_path: PathBuf = binary_path.canonicalize()?;
+    debug!("integrating binary: {:?}", binary_path);
+    match env::consts::OS {
+        "windows" => integrate_windows(&binary_path),
+        "macos" => integrate_macos(&binary_path),
+        _ => integrate_linux(&binary_path),
+    }
+}
+
+fn integrate_windows(binary_path: &Path) -> Result<(), DynError> {
+    // Add a context menu entry for directories to the current user's registry hive, which doesn't need admin rights.
+    let menu_key = r"HKCU\Software\Classes\Directory\shell\FolSum";
+    // Pass the right-clicked directory (`%1`) to FolSum as its first argument.
+    let launch_command = format!("\"{}\" \"%1\"", binary_path.display());
+    for (registry_key, registry_value) in [
+        (menu_key.to_string(), "Inventory with FolSum".to_string()),
+        (format!(r"{menu_key}\command"), launch_command),
+    ] {
+        info!("Setting registry key {}", registry_key);
+        let reg_result: Output = Command::new("reg")
+            .args(["add", &registry_key, "/ve", "/d", &registry_value, "/f"])
+            .output()?;
+        io::stderr().write_all(&reg_result.stderr)?;
+        // Ensure that the registry key was set.
+        assert!(reg_result.status.success());
+    }
+    info!("Added \"Inventory with FolSum\" to Explorer's context menu for folders");
+    Ok(())
+}
+
+fn integrate_linux(binary_path: &Path) -> Result<(), DynError> {
+    // Put the desktop entry where freedesktop-compliant file managers look for user applications.
+    let applications_dir: PathBuf =
+        PathBuf::from(env::var("HOME")?).join(".local/share/applications");
+    create_dir_all(&applications_dir)?;
+    let desktop_entry_path: PathBuf = applications_dir.join("folsum.desktop");
+    // Offer FolSum in "Open With" menus for directories and pass it the chosen directory (`%f`).
+    let desktop_entry = format!(
+        "[Desktop Entry]
+Type=Application
+Name=FolSum
+GenericName=Inventory with FolSum
+Comment=Summarize the contents of a directory
+Exec=\"{}\" %f
+MimeType=inode/directory;
+Categories=Utility;
+Terminal=false
+",
+        binary_path.display()
+    );
+    write(&desktop_entry_path, desktop_entry)?;
+    info!("Wrote desktop entry to {:?}", desktop_entry_path);
+    info!("Directories can now be opened with FolSum from the file manager's \"Open With\" menu");
+    Ok(())
+}
+
+fn integrate_macos(binary_path: &Path) -> Result<(), DynError> {
+    // Finder Quick Actions are Automator workflows, which can't be reliably generated outside of Automator, so
+    // walk the user through creating one instead.
+    info!(
+        "To add \"Inventory with FolSum\" to Finder's Quick Actions menu:
+
+           1. Open Automator and create a new Quick Action
+           2. Set \"Workflow receives current\" to \"folders\" in \"Finder\"
+           3. Add a \"Run Shell Script\" action with \"Pass input\" set to \"as arguments\"
+           4. Use this script: \"{}\" \"$1\" &
+           5. Save the Quick Action as \"Inventory with FolSum\"
+           ",
+        binary_path.display()
+    );
+    Ok(())
+}
+
 fn get_project_root() -> PathBuf {
     // Get the path to the project root, as defined by `Cargo.toml` in the project root (with the workspace members field).
     Path::new(&env!("CARGO_MANIFEST_DIR"))